@@ -1,13 +1,70 @@
 mod problems;
+mod watch;
 
+use std::time::Instant;
 use clap::Parser;
+use crate::problems::aocday::{DayOptions, ImageFormat};
+use crate::watch::Answers;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     day: usize,
 
-    input: std::path::PathBuf
+    input: std::path::PathBuf,
+
+    /// Re-run the day every time the input (or example) file changes
+    #[arg(long)]
+    watch: bool,
+
+    /// A second input, such as the puzzle example, solved side by side with the main input
+    #[arg(long)]
+    example: Option<std::path::PathBuf>,
+
+    /// Print how long each part took to solve alongside its answer
+    #[arg(long)]
+    time: bool,
+
+    /// Record the day's simulation as an animated GIF at this path instead of solving it
+    #[arg(long, value_name = "OUT.gif")]
+    visualize: Option<std::path::PathBuf>,
+
+    /// Draw the day's puzzle, with what it found highlighted, as a PNG or PPM image at this path instead of
+    /// solving it
+    #[arg(long, value_name = "OUT.png|OUT.ppm")]
+    render: Option<std::path::PathBuf>,
+
+    /// Frames per second of the animation
+    #[arg(long, default_value_t = 10)]
+    fps: u16,
+
+    /// Pixels per grid cell in the animation or image
+    #[arg(long, default_value_t = 4)]
+    scale: usize,
+
+    /// Keep only every nth frame of the animation, for long simulations
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    frame_step: u64,
+
+    /// What to do with day1 lines that have no digits: error, skip or zero
+    #[arg(long, value_name = "POLICY")]
+    missing_digits: Option<String>,
+
+    /// Write a JSON report of how each line of the input was read (day1)
+    #[arg(long, value_name = "OUT.json")]
+    report: Option<std::path::PathBuf>,
+
+    /// The cubes in the bag for day2, such as "12 red, 13 green, 14 blue"
+    #[arg(long)]
+    bag: Option<String>,
+
+    /// Draw the day3 schematic with its part numbers and gears highlighted, as ansi or html
+    #[arg(long, value_name = "FORMAT")]
+    annotate: Option<String>,
+
+    /// Number the rows and columns of the drawing
+    #[arg(long)]
+    rulers: bool
 }
 
 fn main() {
@@ -17,11 +74,72 @@ fn main() {
     }
 
     let days = problems::get_days();
+    let options = DayOptions {
+        missing_digits: cli.missing_digits.clone(),
+        report: cli.report.clone(),
+        bag: cli.bag.clone(),
+        annotate: cli.annotate.clone(),
+        rulers: cli.rulers
+    };
 
-    if let Some(day) = days.iter().find(|&x| x.day == cli.day) {
-        (day.part1)(&cli.input);
-        (day.part2)(&cli.input);
+    let Some(day) = days.iter().find(|&x| x.day == cli.day) else {
+        println!("!!! Unknown Day !!! Did you remember to update the problems/mod.rs file?");
+        std::process::exit(1);
+    };
+
+    let unsupported = day.unsupported_options(&options);
+    if !unsupported.is_empty() {
+        let flags: Vec<String> = unsupported.iter().map(|name| format!("--{}", name)).collect();
+        fail(&format!("day {} does not take {}", cli.day, flags.join(", ")));
+    }
+
+    if let Some(output) = &cli.visualize {
+        match day.animate(&cli.input, output, cli.frame_step as usize, cli.fps, cli.scale) {
+            Some(Ok(frames)) => println!("Wrote {} frames to {}", frames, output.display()),
+            Some(Err(error)) => fail(&format!("could not write {}: {}", output.display(), error)),
+            None => fail(&format!("day {} has no simulation to visualize", cli.day))
+        }
+    } else if let Some(output) = &cli.render {
+        let Some(format) = ImageFormat::from_path(output) else {
+            fail(&format!("cannot tell the image format of {}, expected a .png or .ppm file", output.display()));
+        };
+
+        match day.draw(&cli.input, cli.scale, format) {
+            Some(image) => match std::fs::write(output, image) {
+                Ok(()) => println!("Wrote {}", output.display()),
+                Err(error) => fail(&format!("could not write {}: {}", output.display(), error))
+            },
+            None => fail(&format!("day {} has nothing to render", cli.day))
+        }
+    } else if cli.watch {
+        watch::watch(day, &cli.input, cli.example.as_deref(), &options);
+    } else if let Some(example) = &cli.example {
+        let example_answers = Answers::compute(day, example, &options);
+        let input_answers = Answers::compute(day, &cli.input, &options);
+        print!("{}", watch::format_report(&[("example", &example_answers), ("input", &input_answers)], &[]));
+    } else if let Some(with_options) = day.with_options.as_ref().filter(|_| !options.is_empty()) {
+        let started = Instant::now();
+        match (with_options.run)(&cli.input, &options) {
+            Ok(output) => print!("Part 1: {}\nPart 2: {}\n{}", output.part1, output.part2, output.notes),
+            Err(error) => fail(&error)
+        }
+        if cli.time {
+            println!("Took {:.2?}", started.elapsed());
+        }
+    } else if cli.time {
+        for (part, solve) in [(1, &day.part1), (2, &day.part2)] {
+            let started = Instant::now();
+            let answer = solve(&cli.input);
+            println!("Part {}: {} ({:.2?})", part, answer, started.elapsed());
+        }
     } else {
-        println!("!!! Unknown Day !!! Did you remember to update the problems/mod.rs file?")
+        println!("Part 1: {}", (day.part1)(&cli.input));
+        println!("Part 2: {}", (day.part2)(&cli.input));
     }
 }
+
+/// Reports `error` and exits with a failing status.
+fn fail(error: &str) -> ! {
+    println!("Error: {}", error);
+    std::process::exit(1);
+}
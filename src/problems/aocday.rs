@@ -1,5 +1,161 @@
+use std::fs::File;
+use std::io::{self, BufWriter};
+use crate::problems::shared::animation::Recorder;
+use crate::problems::shared::gif::AnimationEncoder;
+use crate::problems::shared::render::Canvas;
+
+/// Runs a day's simulation, offering the recorder a frame at each step
+pub type Visualization = Box<dyn Fn(&std::path::PathBuf, &mut Recorder)>;
+
+/// Draws a day's puzzle with what it found highlighted
+pub type Rendering = Box<dyn Fn(&std::path::PathBuf) -> Canvas>;
+
+/// Solves a day under the given options, returning its answers or why it could not
+pub type OptionsRun = Box<dyn Fn(&std::path::PathBuf, &DayOptions) -> Result<OptionsOutput, String>>;
+
+/// How a day that can be solved more than one way is run, and which of the options it reads
+pub struct WithOptions {
+	/// The names of the options `run` reads, as given by `DayOptions::given`
+	pub accepts: &'static [&'static str],
+	pub run: OptionsRun
+}
+
+/// The answers from a day solved under options, and anything else it has to say about them
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OptionsOutput {
+	pub part1: String,
+	pub part2: String,
+	/// Printed after the answers, one or more whole lines
+	pub notes: String
+}
+
+/// Settings from the command line for the days that can be solved more than one way
+#[derive(Clone, Default, Debug)]
+pub struct DayOptions {
+	/// What to do with lines that have no digits: error, skip or zero
+	pub missing_digits: Option<String>,
+	/// Where to write a JSON report of how the input was read
+	pub report: Option<std::path::PathBuf>,
+	/// The cubes in the bag, such as `12 red, 13 green, 14 blue`
+	pub bag: Option<String>,
+	/// How to draw the puzzle: ansi or html
+	pub annotate: Option<String>,
+	/// Whether to number the rows and columns of a drawing
+	pub rulers: bool
+}
+
+impl DayOptions {
+	pub fn is_empty(&self) -> bool {
+		self.given().is_empty()
+	}
+
+	/// The names of the options that were set, such as `missing-digits` for `--missing-digits`.
+	pub fn given(&self) -> Vec<&'static str> {
+		[
+			("missing-digits", self.missing_digits.is_some()),
+			("report", self.report.is_some()),
+			("bag", self.bag.is_some()),
+			("annotate", self.annotate.is_some()),
+			("rulers", self.rulers)
+		].into_iter().filter(|(_, is_given)| *is_given).map(|(name, _)| name).collect()
+	}
+}
+
+/// The kinds of file a drawing can be written as
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ImageFormat {
+	Png,
+	Ppm
+}
+
+impl ImageFormat {
+	/// The format named by the extension of `path`, if it is one we can write.
+	pub fn from_path(path: &std::path::Path) -> Option<ImageFormat> {
+		match path.extension()?.to_str()? {
+			"png" => Some(ImageFormat::Png),
+			"ppm" => Some(ImageFormat::Ppm),
+			_ => None
+		}
+	}
+}
+
 pub struct AoCDay {
 	pub day: usize,
-	pub part1: Box<dyn Fn(&std::path::PathBuf)>,
-	pub part2: Box<dyn Fn(&std::path::PathBuf)>
-}
\ No newline at end of file
+	pub part1: Box<dyn Fn(&std::path::PathBuf) -> String>,
+	pub part2: Box<dyn Fn(&std::path::PathBuf) -> String>,
+	pub visualize: Option<Visualization>,
+	pub render: Option<Rendering>,
+	pub with_options: Option<WithOptions>
+}
+
+impl AoCDay {
+	/// The options that were set which this day does not read.
+	pub fn unsupported_options(&self, options: &DayOptions) -> Vec<&'static str> {
+		let accepts = self.with_options.as_ref().map_or(&[][..], |with_options| with_options.accepts);
+		options.given().into_iter().filter(|name| !accepts.contains(name)).collect()
+	}
+
+	/// Records the day's simulation as an animated GIF at `output`, keeping every `frame_step`th frame and
+	/// encoding each one as soon as it is drawn. Returns the number of frames written, or `None` if the day
+	/// has nothing to animate.
+	pub fn animate(&self, input: &std::path::PathBuf, output: &std::path::Path, frame_step: usize, frames_per_second: u16, scale: usize) -> Option<io::Result<usize>> {
+		let visualize = self.visualize.as_ref()?;
+		Some(File::create(output).and_then(|file| {
+			let mut encoder = AnimationEncoder::new(BufWriter::new(file), frames_per_second);
+			let mut written = Ok(());
+			visualize(input, &mut Recorder::new(frame_step, |frame| {
+				// once a write fails there is no point drawing the rest
+				if written.is_ok() {
+					written = encoder.add_frame(&frame.to_image(scale));
+				}
+			}));
+			written?;
+			encoder.finish()
+		}))
+	}
+
+	/// Draws the day's puzzle as a picture with `scale` pixels to a cell, returning the file contents, or
+	/// `None` if the day has nothing to draw.
+	pub fn draw(&self, input: &std::path::PathBuf, scale: usize, format: ImageFormat) -> Option<Vec<u8>> {
+		let render = self.render.as_ref()?;
+		let image = render(input).to_image(scale);
+		Some(match format {
+			ImageFormat::Png => image.to_png(),
+			ImageFormat::Ppm => image.to_ppm()
+		})
+	}
+}
+#[cfg(test)]
+mod tests {
+	use crate::problems::aocday::{AoCDay, DayOptions, OptionsOutput, WithOptions};
+
+	fn day(with_options: Option<WithOptions>) -> AoCDay {
+		AoCDay {
+			day: 1,
+			part1: Box::new(|_| "1".to_string()),
+			part2: Box::new(|_| "2".to_string()),
+			visualize: None,
+			render: None,
+			with_options
+		}
+	}
+
+	#[test]
+	fn finds_options_a_day_does_not_read() {
+		let options = DayOptions {
+			bag: Some("1 red".to_string()),
+			rulers: true,
+			..DayOptions::default()
+		};
+		assert_eq!(vec!["bag", "rulers"], options.given());
+		assert!(DayOptions::default().is_empty());
+
+		assert_eq!(vec!["bag", "rulers"], day(None).unsupported_options(&options));
+		let reads_bags = day(Some(WithOptions {
+			accepts: &["bag"],
+			run: Box::new(|_, _| Ok(OptionsOutput { part1: String::new(), part2: String::new(), notes: String::new() }))
+		}));
+		assert_eq!(vec!["rulers"], reads_bags.unsupported_options(&options));
+		assert!(reads_bags.unsupported_options(&DayOptions::default()).is_empty());
+	}
+}
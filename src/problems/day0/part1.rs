@@ -1,6 +1,6 @@
 use std::fs;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let _contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-	println!("Part 1");
+    String::new()
 }
\ No newline at end of file
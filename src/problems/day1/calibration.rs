@@ -0,0 +1,157 @@
+use crate::problems::shared::aho_corasick::AhoCorasick;
+
+const NUMERALS: [(&str, u32); 10] = [("0", 0), ("1", 1), ("2", 2), ("3", 3), ("4", 4), ("5", 5), ("6", 6), ("7", 7), ("8", 8), ("9", 9)];
+
+/// A digit found in a line, and the bytes of the line it was spelled with.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DigitMatch {
+    pub digit: u32,
+    pub start: usize,
+    pub end: usize
+}
+
+impl DigitMatch {
+    /// The text the digit was found in, such as `"7"` or `"seven"`.
+    pub fn token<'a>(&self, line: &'a str) -> &'a str {
+        &line[self.start..self.end]
+    }
+}
+
+/// The first and last digits of a line, which make up its calibration value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CalibrationValue {
+    pub first: DigitMatch,
+    pub last: DigitMatch
+}
+
+impl CalibrationValue {
+    pub fn value(&self) -> u32 {
+        self.first.digit * 10 + self.last.digit
+    }
+}
+
+/// The tokens that count as digits when reading a calibration line.
+pub struct DigitVocabulary {
+    matcher: AhoCorasick<u32>
+}
+
+impl DigitVocabulary {
+    /// Any set of tokens, each standing for the digit it is paired with.
+    pub fn from_tokens<I, S>(tokens: I) -> DigitVocabulary where I: IntoIterator<Item = (S, u32)>, S: AsRef<str> {
+        let tokens: Vec<(String, u32)> = tokens.into_iter().map(|(token, digit)| (token.as_ref().to_string(), digit)).collect();
+        DigitVocabulary {
+            matcher: AhoCorasick::new(tokens)
+        }
+    }
+
+    /// Only the numerals 0 to 9.
+    pub fn numerals() -> DigitVocabulary {
+        DigitVocabulary::from_tokens(NUMERALS)
+    }
+
+    /// The numerals and the English words for one to nine.
+    pub fn english() -> DigitVocabulary {
+        DigitVocabulary::with_words(["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"])
+    }
+
+    /// The numerals and the German words for one to nine.
+    #[allow(dead_code)]
+    pub fn german() -> DigitVocabulary {
+        DigitVocabulary::with_words(["eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"])
+    }
+
+    /// The numerals and the Roman numerals I to IX.
+    #[allow(dead_code)]
+    pub fn roman() -> DigitVocabulary {
+        DigitVocabulary::with_words(["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX"])
+    }
+
+    /// The numerals, plus `words` standing for one to nine in order.
+    fn with_words(words: [&str; 9]) -> DigitVocabulary {
+        let words = words.into_iter().zip(1..);
+        DigitVocabulary::from_tokens(NUMERALS.into_iter().chain(words))
+    }
+
+    /// Every digit in `line`, overlapping ones included, so "twone" has both a two and a one. Ordered by
+    /// where they start, then longest first.
+    pub fn find_digits(&self, line: &str) -> Vec<DigitMatch> {
+        let mut digits: Vec<DigitMatch> = self.matcher
+            .find_overlapping(line.as_bytes())
+            .into_iter()
+            .map(|found| DigitMatch {
+                digit: *self.matcher.value(found.pattern),
+                start: found.start,
+                end: found.end
+            })
+            .collect();
+        digits.sort_by_key(|digit| (digit.start, std::cmp::Reverse(digit.end)));
+        digits
+    }
+
+    /// The first digit to start in `line` and the last to end, reading the line from each side. Where
+    /// several tokens start (or end) together the longest wins, so "VIII" reads as eight both ways rather
+    /// than as a five or a one. `None` if the line has no digits.
+    pub fn calibration_value(&self, line: &str) -> Option<CalibrationValue> {
+        let digits = self.find_digits(line);
+        let first = *digits.first()?;
+        let last = *digits.iter().max_by_key(|digit| (digit.end, std::cmp::Reverse(digit.start)))?;
+        Some(CalibrationValue {
+            first,
+            last
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::day1::calibration::{DigitMatch, DigitVocabulary};
+
+    fn value(vocabulary: &DigitVocabulary, line: &str) -> Option<u32> {
+        vocabulary.calibration_value(line).map(|calibration| calibration.value())
+    }
+
+    #[test]
+    fn reads_single_digits_both_ways() {
+        let english = DigitVocabulary::english();
+        assert_eq!(Some(11), value(&english, "1"));
+        assert_eq!(Some(11), value(&english, "one"));
+        assert_eq!(None, value(&english, "abc"));
+        assert_eq!(None, value(&DigitVocabulary::numerals(), "one"));
+    }
+
+    #[test]
+    fn overlapping_words_count_twice() {
+        let english = DigitVocabulary::english();
+        assert_eq!(Some(21), value(&english, "twone"));
+        assert_eq!(Some(82), value(&english, "eightwo"));
+        assert_eq!(
+            vec![
+                DigitMatch { digit: 8, start: 0, end: 5 },
+                DigitMatch { digit: 2, start: 4, end: 7 },
+                DigitMatch { digit: 3, start: 7, end: 12 },
+                DigitMatch { digit: 4, start: 12, end: 13 }
+            ],
+            english.find_digits("eightwothree4")
+        );
+
+        let calibration = english.calibration_value("xtwone3four").unwrap();
+        assert_eq!(("two", "four"), (calibration.first.token("xtwone3four"), calibration.last.token("xtwone3four")));
+    }
+
+    #[test]
+    fn solves_the_example() {
+        let example = ["two1nine", "eightwothree", "abcone2threexyz", "xtwone3four", "4nineeightseven2", "zoneight234", "7pqrstsixteen"];
+        let english = DigitVocabulary::english();
+        assert_eq!(281, example.iter().map(|line| value(&english, line).unwrap()).sum::<u32>());
+    }
+
+    #[test]
+    fn supports_other_vocabularies() {
+        assert_eq!(Some(58), value(&DigitVocabulary::german(), "xfünfzweiacht"));
+        assert_eq!(Some(88), value(&DigitVocabulary::roman(), "VIII"));
+        assert_eq!(Some(94), value(&DigitVocabulary::roman(), "IXIV"));
+
+        let custom = DigitVocabulary::from_tokens([("uno", 1), ("dos", 2), ("*", 7)]);
+        assert_eq!(Some(27), value(&custom, "dos-uno-*"));
+    }
+}
@@ -1,2 +1,5 @@
 pub mod part1;
-pub mod part2;
\ No newline at end of file
+pub mod part2;
+pub mod options;
+mod calibration;
+mod report;
\ No newline at end of file
@@ -0,0 +1,66 @@
+use std::fmt::Write;
+use std::fs;
+use crate::problems::aocday::{DayOptions, OptionsOutput};
+use crate::problems::day1::calibration::DigitVocabulary;
+use crate::problems::day1::report::{CalibrationReport, MissingDigitPolicy};
+
+/// Solves both parts under the `--missing-digits` policy, noting the lines without digits and writing both
+/// reports to `--report` as JSON if asked. A part that cannot be read gives its error as its answer, so the
+/// other part is still solved, and is `null` in the report.
+pub fn execute(input_path: &std::path::PathBuf, options: &DayOptions) -> Result<OptionsOutput, String> {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let policy = match &options.missing_digits {
+        Some(policy) => policy.parse()?,
+        None => MissingDigitPolicy::Error
+    };
+
+    let part1 = CalibrationReport::build(&contents, &DigitVocabulary::numerals(), policy);
+    let part2 = CalibrationReport::build(&contents, &DigitVocabulary::english(), policy);
+    if let Some(path) = &options.report {
+        let to_json = |report: &Result<CalibrationReport, _>| report.as_ref().map_or("null".to_string(), |report| report.to_json());
+        let json = format!("{{\"part1\":{},\"part2\":{}}}", to_json(&part1), to_json(&part2));
+        fs::write(path, json).map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+    }
+
+    let mut notes = String::new();
+    for (part, report) in [(1, &part1), (2, &part2)] {
+        let Ok(report) = report else {
+            continue;
+        };
+        let missing: Vec<String> = report.lines_without_digits().map(|line| line.line.to_string()).collect();
+        if !missing.is_empty() {
+            writeln!(notes, "Part {}: no digits on lines {}", part, missing.join(", ")).expect("Writing to a string cannot fail");
+        }
+    }
+
+    let answer = |report: &Result<CalibrationReport, _>| match report {
+        Ok(report) => report.sum().to_string(),
+        Err(error) => format!("Error: {}", error)
+    };
+    Ok(OptionsOutput {
+        part1: answer(&part1),
+        part2: answer(&part2),
+        notes
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::aocday::DayOptions;
+    use crate::problems::day1::options;
+
+    #[test]
+    fn reports_each_parts_error_in_its_own_answer() {
+        let input = std::env::temp_dir().join("aoc_2023_day1_word_digits.txt");
+        std::fs::write(&input, "two1nine\nthree\n").unwrap();
+
+        let strict = options::execute(&input, &DayOptions { report: Some(std::env::temp_dir().join("aoc_2023_day1_report.json")), ..DayOptions::default() }).unwrap();
+        assert!(strict.part1.starts_with("Error: "), "{}", strict.part1);
+        assert_eq!("62", strict.part2);
+
+        let skipping = options::execute(&input, &DayOptions { missing_digits: Some("skip".to_string()), ..DayOptions::default() }).unwrap();
+        assert_eq!("11", skipping.part1);
+        assert_eq!("62", skipping.part2);
+        assert_eq!("Part 1: no digits on lines 2\n", skipping.notes);
+    }
+}
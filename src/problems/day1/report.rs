@@ -0,0 +1,190 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use crate::problems::day1::calibration::{CalibrationValue, DigitMatch, DigitVocabulary};
+use crate::problems::shared::parse::{self, ParseError};
+
+/// What to do with a line that has no digits in it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MissingDigitPolicy {
+    /// Stop at the line with an error.
+    Error,
+    /// Leave the line out of the sum.
+    Skip,
+    /// Count the line as a calibration value of zero.
+    Zero
+}
+
+impl MissingDigitPolicy {
+    fn name(&self) -> &'static str {
+        match self {
+            MissingDigitPolicy::Error => "error",
+            MissingDigitPolicy::Skip => "skip",
+            MissingDigitPolicy::Zero => "zero"
+        }
+    }
+}
+
+impl FromStr for MissingDigitPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<MissingDigitPolicy, String> {
+        match s {
+            "error" => Ok(MissingDigitPolicy::Error),
+            "skip" => Ok(MissingDigitPolicy::Skip),
+            "zero" => Ok(MissingDigitPolicy::Zero),
+            _ => Err(format!("unknown policy {:?}, expected error, skip or zero", s))
+        }
+    }
+}
+
+/// How one line of the calibration document was read.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LineReport {
+    /// 1-based line number within the input.
+    pub line: usize,
+    pub text: String,
+    /// `None` when the line has no digits.
+    pub calibration: Option<CalibrationValue>,
+    /// What the line adds to the sum, or `None` if it was skipped.
+    pub value: Option<u32>
+}
+
+/// Every line of a calibration document with the digits it was read from, so two readings of the same
+/// input can be compared line by line.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CalibrationReport {
+    pub policy: MissingDigitPolicy,
+    pub lines: Vec<LineReport>
+}
+
+impl CalibrationReport {
+    /// Reads every line of `contents` with `vocabulary`. Only fails under `MissingDigitPolicy::Error`, at
+    /// the first line without digits.
+    pub fn build(contents: &str, vocabulary: &DigitVocabulary, policy: MissingDigitPolicy) -> Result<CalibrationReport, ParseError> {
+        let mut lines = vec![];
+        for line in parse::lines(contents) {
+            let calibration = vocabulary.calibration_value(line.text);
+            let value = match (calibration, policy) {
+                (Some(calibration), _) => Some(calibration.value()),
+                (None, MissingDigitPolicy::Error) => return Err(line.error("did not find a digit")),
+                (None, MissingDigitPolicy::Skip) => None,
+                (None, MissingDigitPolicy::Zero) => Some(0)
+            };
+
+            lines.push(LineReport {
+                line: line.number,
+                text: line.text.to_string(),
+                calibration,
+                value
+            });
+        }
+
+        Ok(CalibrationReport {
+            policy,
+            lines
+        })
+    }
+
+    pub fn sum(&self) -> u32 {
+        self.lines.iter().filter_map(|line| line.value).sum()
+    }
+
+    /// The lines that had no digits in them.
+    pub fn lines_without_digits(&self) -> impl Iterator<Item = &LineReport> {
+        self.lines.iter().filter(|line| line.calibration.is_none())
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = format!("{{\"policy\":\"{}\",\"sum\":{},\"lines\":[", self.policy.name(), self.sum());
+        for (index, line) in self.lines.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+
+            let digit = |found: Option<DigitMatch>| match found {
+                Some(found) => format!(
+                    "{{\"digit\":{},\"token\":{},\"start\":{},\"end\":{}}}",
+                    found.digit,
+                    json_string(found.token(&line.text)),
+                    found.start,
+                    found.end
+                ),
+                None => "null".to_string()
+            };
+            let value = line.value.map_or("null".to_string(), |value| value.to_string());
+            write!(
+                json,
+                "{{\"line\":{},\"text\":{},\"first\":{},\"last\":{},\"value\":{}}}",
+                line.line,
+                json_string(&line.text),
+                digit(line.calibration.map(|c| c.first)),
+                digit(line.calibration.map(|c| c.last)),
+                value
+            ).expect("Writing to a string cannot fail");
+        }
+
+        json.push_str("]}");
+        json
+    }
+}
+
+/// `text` as a quoted JSON string.
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::day1::calibration::DigitVocabulary;
+    use crate::problems::day1::report::{CalibrationReport, MissingDigitPolicy};
+
+    const INPUT: &str = "two1nine
+no digits
+xtwone3four";
+
+    #[test]
+    fn applies_the_missing_digit_policy() {
+        let english = DigitVocabulary::english();
+        let error = CalibrationReport::build(INPUT, &english, MissingDigitPolicy::Error).unwrap_err();
+        assert_eq!(2, error.line);
+
+        let skipped = CalibrationReport::build(INPUT, &english, MissingDigitPolicy::Skip).unwrap();
+        assert_eq!(29 + 24, skipped.sum());
+        assert_eq!(vec![2], skipped.lines_without_digits().map(|line| line.line).collect::<Vec<usize>>());
+        assert_eq!(None, skipped.lines[1].value);
+
+        let zeroed = CalibrationReport::build(INPUT, &english, MissingDigitPolicy::Zero).unwrap();
+        assert_eq!(29 + 24, zeroed.sum());
+        assert_eq!(Some(0), zeroed.lines[1].value);
+
+        assert_eq!(Ok(MissingDigitPolicy::Zero), "zero".parse());
+        assert!("lenient".parse::<MissingDigitPolicy>().is_err());
+    }
+
+    #[test]
+    fn exports_json() {
+        let report = CalibrationReport::build("a\"1\nb", &DigitVocabulary::numerals(), MissingDigitPolicy::Skip).unwrap();
+        assert_eq!(
+            concat!(
+                r#"{"policy":"skip","sum":11,"lines":["#,
+                r#"{"line":1,"text":"a\"1","first":{"digit":1,"token":"1","start":2,"end":3},"last":{"digit":1,"token":"1","start":2,"end":3},"value":11},"#,
+                r#"{"line":2,"text":"b","first":null,"last":null,"value":null}]}"#
+            ),
+            report.to_json()
+        );
+    }
+}
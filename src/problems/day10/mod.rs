@@ -1,6 +1,6 @@
 pub mod part1;
 pub mod part2;
+pub mod render;
 mod pipe;
 mod pipe_grid;
-mod pipe_node;
-mod position;
\ No newline at end of file
+mod pipe_node;
\ No newline at end of file
@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day10::pipe_grid::PipeGrid;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let grid = PipeGrid::parse(&contents);
-    println!("Part 2: {}", grid.find_area_enclosed_by_loop());
+    let grid = PipeGrid::parse(&contents).unwrap();
+    grid.find_area_enclosed_by_loop().to_string()
 }
\ No newline at end of file
@@ -11,17 +11,31 @@ pub enum PipeTile {
 }
 
 impl PipeTile {
-    pub fn parse(c: char) -> PipeTile {
+    pub fn parse(c: char) -> Option<PipeTile> {
         match c {
-            '|' => PipeTile::Vertical,
-            '-' => PipeTile::Horizontal,
-            'L' => PipeTile::BendNorthEast,
-            'J' => PipeTile::BendNorthWest,
-            '7' => PipeTile::BendSouthWest,
-            'F' => PipeTile::BendSouthEast,
-            '.' => PipeTile::Ground,
-            'S' => PipeTile::StartPosition,
-            _ => panic!("Invalid pipe tile")
+            '|' => Some(PipeTile::Vertical),
+            '-' => Some(PipeTile::Horizontal),
+            'L' => Some(PipeTile::BendNorthEast),
+            'J' => Some(PipeTile::BendNorthWest),
+            '7' => Some(PipeTile::BendSouthWest),
+            'F' => Some(PipeTile::BendSouthEast),
+            '.' => Some(PipeTile::Ground),
+            'S' => Some(PipeTile::StartPosition),
+            _ => None
+        }
+    }
+
+    /// The character the tile was parsed from.
+    pub fn glyph(&self) -> char {
+        match self {
+            PipeTile::Vertical => '|',
+            PipeTile::Horizontal => '-',
+            PipeTile::BendNorthEast => 'L',
+            PipeTile::BendNorthWest => 'J',
+            PipeTile::BendSouthWest => '7',
+            PipeTile::BendSouthEast => 'F',
+            PipeTile::Ground => '.',
+            PipeTile::StartPosition => 'S'
         }
     }
 
@@ -1,173 +1,62 @@
-use std::cmp::Ordering;
 use crate::problems::day10::pipe::PipeTile;
 use crate::problems::day10::pipe_node::PipeNode;
-use crate::problems::day10::position::Position;
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::image::Rgb;
+use crate::problems::shared::parse::ParseError;
+use crate::problems::shared::polygon::LatticePolygon;
+use crate::problems::shared::render::{Cell, Overlay, Render};
 
 pub struct PipeGrid {
-    grid: Vec<Vec<PipeNode>>
-}
-
-#[derive(Clone, PartialEq)]
-enum GridStatus {
-    Path,
-    GroupA,
-    GroupB,
-    Unknown
+    grid: Grid2D<PipeNode>
 }
 
 impl PipeGrid {
-    pub fn get_node(&self, position: &Position) -> &PipeNode {
-        &self.grid[position.row][position.col]
+    pub fn get_node(&self, position: &GridPoint2D) -> &PipeNode {
+        &self.grid[*position]
     }
-    pub fn parse(input: &str) -> PipeGrid {
-        let lines = input.lines().filter(|l| !l.is_empty());
-        let unconnected_grid: Vec<Vec<PipeTile>> = lines.map(|l| l.chars().map(|c| PipeTile::parse(c)).collect()).collect();
-        let mut connection_grid: Vec<Vec<PipeNode>> = vec![];
-
-        for row in 0..unconnected_grid.len() {
-            connection_grid.push(vec![]);
-            for col in 0..unconnected_grid[row].len() {
-                let tile = &unconnected_grid[row][col];
-                let tile_pos = Position::new(row, col);
-
-                let connections = match tile {
-                    PipeTile::Vertical => {
-                        let mut connections = vec![];
-                        // top
-                        if row > 0 && unconnected_grid[row - 1][col].has_connections() {
-                            connections.push(Position::new(row - 1, col));
-                        }
-
-                        // down
-                        if row < unconnected_grid.len() - 1 && unconnected_grid[row + 1][col].has_connections() {
-                            connections.push(Position::new(row + 1, col));
-                        }
-
-                        connections
-                    },
-                    PipeTile::Horizontal => {
-                        let mut connections = vec![];
-                        // left
-                        if col > 0 && unconnected_grid[row][col - 1].has_connections() {
-                            connections.push(Position::new(row, col - 1));
-                        }
-
-                        // right
-                        if col < unconnected_grid[row].len() - 1 && unconnected_grid[row][col + 1].has_connections() {
-                            connections.push(Position::new(row, col + 1));
-                        }
-
-                        connections
-                    },
-                    PipeTile::BendNorthEast => {
-                        let mut connections = vec![];
-                        // top
-                        if row > 0 && unconnected_grid[row - 1][col].has_connections() {
-                            connections.push(Position::new(row - 1, col));
-                        }
-
-                        // right
-                        if col < unconnected_grid[row].len() - 1 && unconnected_grid[row][col + 1].has_connections() {
-                            connections.push(Position::new(row, col + 1));
-                        }
-
-                        connections
-                    },
-                    PipeTile::BendNorthWest => {
-                        let mut connections = vec![];
-                        // top
-                        if row > 0 && unconnected_grid[row - 1][col].has_connections() {
-                            connections.push(Position::new(row - 1, col));
-                        }
-
-                        // left
-                        if col > 0 && unconnected_grid[row][col - 1].has_connections() {
-                            connections.push(Position::new(row, col - 1));
-                        }
-
-                        connections
-                    },
-                    PipeTile::BendSouthWest => {
-                        let mut connections = vec![];
-
-                        // left
-                        if col > 0 && unconnected_grid[row][col - 1].has_connections() {
-                            connections.push(Position::new(row, col - 1));
-                        }
-
-                        // down
-                        if row < unconnected_grid.len() - 1 && unconnected_grid[row + 1][col].has_connections() {
-                            connections.push(Position::new(row + 1, col));
-                        }
-
-                        connections
-                    },
-                    PipeTile::BendSouthEast => {
-                        let mut connections = vec![];
-
-                        // right
-                        if col < unconnected_grid[row].len() - 1 && unconnected_grid[row][col + 1].has_connections() {
-                            connections.push(Position::new(row, col + 1));
-                        }
-
-                        // down
-                        if row < unconnected_grid.len() - 1 && unconnected_grid[row + 1][col].has_connections() {
-                            connections.push(Position::new(row + 1, col));
-                        }
-
-                        connections
-                    },
-                    PipeTile::Ground => vec![], // special case, no connections
-                    PipeTile::StartPosition => {
-                        let mut connections = vec![];
-                        // top, only a valid path if the node above moves DOWN
-                        if row > 0 && unconnected_grid[row - 1][col].travels_down() {
-                            connections.push(Position::new(row - 1, col));
-                        }
-
-                        // left
-                        if col > 0 && unconnected_grid[row][col - 1].travels_right() {
-                            connections.push(Position::new(row, col - 1));
-                        }
-
-                        // right
-                        if col < unconnected_grid[row].len() - 1 && unconnected_grid[row][col + 1].travels_left() {
-                            connections.push(Position::new(row, col + 1));
-                        }
 
-                        // down
-                        if row < unconnected_grid.len() - 1 && unconnected_grid[row + 1][col].travels_up() {
-                            connections.push(Position::new(row + 1, col));
-                        }
-
-                        connections
-                    }
-                };
-
-                connection_grid[row].push(PipeNode::new(tile.clone(), tile_pos, connections));
-            }
-        }
-        PipeGrid {
+    pub fn parse(input: &str) -> Result<PipeGrid, ParseError> {
+        let unconnected_grid = Grid2D::parse(input, PipeTile::parse)?;
+        let connection_grid = unconnected_grid.map(|tile_pos, tile| {
+            let connections = unconnected_grid
+                .get_adjacents(&tile_pos)
+                .into_iter()
+                .filter(|adjacent| {
+                    let neighbor = &unconnected_grid[*adjacent];
+                    if *tile == PipeTile::StartPosition {
+                        // the start only connects to pipes that lead back into it
+                        return (adjacent.x < tile_pos.x && neighbor.travels_down())
+                            || (adjacent.x > tile_pos.x && neighbor.travels_up())
+                            || (adjacent.y < tile_pos.y && neighbor.travels_right())
+                            || (adjacent.y > tile_pos.y && neighbor.travels_left());
+                    }
+
+                    let travels_towards_neighbor = (adjacent.x < tile_pos.x && tile.travels_up())
+                        || (adjacent.x > tile_pos.x && tile.travels_down())
+                        || (adjacent.y < tile_pos.y && tile.travels_left())
+                        || (adjacent.y > tile_pos.y && tile.travels_right());
+                    travels_towards_neighbor && neighbor.has_connections()
+                })
+                .collect();
+
+            PipeNode::new(tile.clone(), tile_pos, connections)
+        });
+
+        Ok(PipeGrid {
             grid: connection_grid
-        }
+        })
     }
 
     pub fn find_start_position(&self) -> &PipeNode {
-        for row in 0..self.grid.len() {
-            for col in 0..self.grid[row].len() {
-                if self.grid[row][col].is_start() {
-                    return &self.grid[row][col];
-                }
-            }
-        }
-
-        panic!();
+        let start = self.grid.find(|node| node.is_start()).unwrap();
+        &self.grid[start]
     }
 
-    pub fn find_loop(&self) -> Vec<Position> {
+    pub fn find_loop(&self) -> Vec<GridPoint2D> {
         let start = self.find_start_position();
         for node in &start.connections {
-            let mut path: Vec<Position> = vec![start.position.clone(), node.clone()];
+            let mut path: Vec<GridPoint2D> = vec![start.position, *node];
             loop {
                 let node_under_consideration = self.get_node(&path.last().unwrap());
                 if node_under_consideration.is_start() {
@@ -189,7 +78,7 @@ impl PipeGrid {
                     break; // this path is a dead-end. Ignore it.
                 }
 
-                path.push(next_node.clone());
+                path.push(*next_node);
             }
         }
 
@@ -201,188 +90,38 @@ impl PipeGrid {
         return (path.len() + 1) / 2;
     }
 
-    pub fn get_node_east_of(&self, node_pos: &Position) -> Option<Position> {
-        if node_pos.col == self.grid[0].len() - 1 {
-            return None;
-        }
-
-        return Some(Position::new(node_pos.row, node_pos.col + 1));
-    }
-
-    pub fn get_node_west_of(&self, node_pos: &Position) -> Option<Position> {
-        if node_pos.col == 0 {
-            return None;
-        }
-
-        return Some(Position::new(node_pos.row, node_pos.col - 1));
+    pub fn find_area_enclosed_by_loop(&self) -> usize {
+        let path = self.find_loop();
+        let polygon = LatticePolygon::from_vertices(path.iter().map(|p| (p.x as i64, p.y as i64)).collect());
+        polygon.interior_point_count().expect("Pipe loop area should not overflow") as usize
     }
 
-    pub fn get_node_north_of(&self, node_pos: &Position) -> Option<Position> {
-        if node_pos.row == 0 {
-            return None;
-        }
-
-        return Some(Position::new(node_pos.row - 1, node_pos.col));
+    /// Highlights the pipes that make up the loop through the start.
+    pub fn loop_overlay(&self) -> Overlay {
+        Overlay::new(self.find_loop(), Rgb::new(255, 200, 0))
     }
-    pub fn get_node_south_of(&self, node_pos: &Position) -> Option<Position> {
-        if node_pos.row == self.grid.len() - 1 {
-            return None;
-        }
+}
 
-        return Some(Position::new(node_pos.row + 1, node_pos.col));
+impl Render for PipeGrid {
+    fn render_size(&self) -> (usize, usize) {
+        (self.grid.height(), self.grid.width())
     }
 
-    pub fn find_area_enclosed_by_loop(&self) -> usize {
-        let path = self.find_loop();
-
-        let mut inside_marked_grid_map: Vec<Vec<GridStatus>> = vec![vec![GridStatus::Unknown; self.grid[0].len()]; self.grid.len()];
-        for p in &path {
-            inside_marked_grid_map[p.row][p.col] = GridStatus::Path;
-        }
-
-        let mut nodes_to_expand: Vec<Position> = vec![];
-        for index in 0..path.len() {
-            let current_node = &path[index];
-            let next_node = if index < path.len() - 1 { &path[index + 1] } else { &path[0] };
-
-            // Group A is Left going vertical
-            // Group B is Right going vertical
-            let vertical_direction = current_node.row.cmp(&next_node.row);
-            let horizontal_direction = current_node.col.cmp(&next_node.col);
-
-            let north = self.get_node_north_of(current_node).and_then(|node| if inside_marked_grid_map[node.row][node.col] != GridStatus::Path { Some(node) } else { None });
-            let west = self.get_node_west_of(current_node).and_then(|node| if inside_marked_grid_map[node.row][node.col] != GridStatus::Path { Some(node) } else { None });
-            let south = self.get_node_south_of(current_node).and_then(|node| if inside_marked_grid_map[node.row][node.col] != GridStatus::Path { Some(node) } else { None });
-            let east = self.get_node_east_of(current_node).and_then(|node| if inside_marked_grid_map[node.row][node.col] != GridStatus::Path { Some(node) } else { None });
-            match self.grid[current_node.row][current_node.col].tile {
-                PipeTile::Horizontal => {
-                    if let Some(node) = north {
-                        inside_marked_grid_map[node.row][node.col] = if horizontal_direction == Ordering::Greater { GridStatus::GroupA } else { GridStatus::GroupB };
-                        nodes_to_expand.push(node);
-                    }
-                    if let Some(node) = south {
-                        inside_marked_grid_map[node.row][node.col] = if horizontal_direction == Ordering::Less { GridStatus::GroupA } else { GridStatus::GroupB };
-                        nodes_to_expand.push(node);
-                    }
-                },
-                PipeTile::Vertical => {
-                    if let Some(node) = west {
-                        inside_marked_grid_map[node.row][node.col] = if vertical_direction == Ordering::Less { GridStatus::GroupA } else { GridStatus::GroupB };
-                        nodes_to_expand.push(node);
-                    }
-                    if let Some(node) = east {
-                        inside_marked_grid_map[node.row][node.col] = if vertical_direction == Ordering::Greater { GridStatus::GroupA } else { GridStatus::GroupB };
-                        nodes_to_expand.push(node);
-                    }
-                },
-                PipeTile::BendNorthEast => {
-                    let nodes_are = if current_node.row == next_node.row { GridStatus::GroupA } else { GridStatus::GroupB };
-                    if let Some(node) = west {
-                        inside_marked_grid_map[node.row][node.col] = nodes_are.clone();
-                        nodes_to_expand.push(node);
-                    }
-                    if let Some(node) = north {
-                        inside_marked_grid_map[node.row][node.col] = nodes_are.clone();
-                        nodes_to_expand.push(node);
-                    }
-                },
-                PipeTile::BendNorthWest => {
-                    let nodes_are = if current_node.col == next_node.col { GridStatus::GroupA } else { GridStatus::GroupB };
-                    if let Some(node) = east {
-                        inside_marked_grid_map[node.row][node.col] = nodes_are.clone();
-                        nodes_to_expand.push(node);
-                    }
-                    if let Some(node) = north {
-                        inside_marked_grid_map[node.row][node.col] = nodes_are.clone();
-                        nodes_to_expand.push(node);
-                    }
-                },
-                PipeTile::BendSouthEast => {
-                    let nodes_are = if current_node.col == next_node.col { GridStatus::GroupA } else { GridStatus::GroupB };
-                    if let Some(node) = west {
-                        inside_marked_grid_map[node.row][node.col] = nodes_are.clone();
-                        nodes_to_expand.push(node);
-                    }
-                    if let Some(node) = south {
-                        inside_marked_grid_map[node.row][node.col] = nodes_are.clone();
-                        nodes_to_expand.push(node);
-                    }
-                },
-                PipeTile::BendSouthWest => {
-                    let nodes_are = if current_node.row == next_node.row { GridStatus::GroupA } else { GridStatus::GroupB };
-                    if let Some(node) = east {
-                        inside_marked_grid_map[node.row][node.col] = nodes_are.clone();
-                        nodes_to_expand.push(node);
-                    }
-                    if let Some(node) = south {
-                        inside_marked_grid_map[node.row][node.col] = nodes_are.clone();
-                        nodes_to_expand.push(node);
-                    }
-                },
-                PipeTile::StartPosition => {
-                    // I really don't want to derive the start tile
-                    //println!("Ignoring start position. I can't imagine a state where this would matter.");
-                }
-                _ => panic!("Impossible State")
-            }
-        }
-
-        let mut is_group_a_edge = None;
-        while let Some(node) = nodes_to_expand.pop() {
-            let adjacent_nodes = vec![self.get_node_west_of(&node), self.get_node_south_of(&node), self.get_node_east_of(&node), self.get_node_north_of(&node)];
-            for adjacent in adjacent_nodes {
-                if let Some(adjacent_pos) = adjacent {
-                    if inside_marked_grid_map[adjacent_pos.row][adjacent_pos.col] == GridStatus::Unknown {
-                        if inside_marked_grid_map[node.row][node.col] == GridStatus::Path {
-                            println!("Broken: {}, {}", node.row, node.col);
-                        }
-                        inside_marked_grid_map[adjacent_pos.row][adjacent_pos.col] = inside_marked_grid_map[node.row][node.col].clone();
-                        nodes_to_expand.push(adjacent_pos);
-                    }
-                } else {
-                    if inside_marked_grid_map[node.row][node.col] != GridStatus::Path {
-                        is_group_a_edge = if inside_marked_grid_map[node.row][node.col] == GridStatus::GroupA { Some(true) } else { Some(false) };
-                    }
-                }
-            }
-        }
-
-        // pretty print
-        // for row in &inside_marked_grid_map {
-        //     print!("\r\n");
-        //     for node in row {
-        //         let dbg = match node {
-        //             GridStatus::GroupA => 'A',
-        //             GridStatus::GroupB => 'B',
-        //             GridStatus::Unknown => '*',
-        //             GridStatus::Path => 'P',
-        //         };
-        //         print!("{}", dbg);
-        //     }
-        // }
-        // print!("\r\n");
-
-        let mut inside_nodes = 0;
-        if let Some(is_group_a_edge) = is_group_a_edge {
-            let group_that_is_inside = if is_group_a_edge { GridStatus::GroupB } else { GridStatus::GroupA };
-            for row in &inside_marked_grid_map {
-                for node in row {
-                    if node == &group_that_is_inside {
-                        inside_nodes += 1;
-                    }
-                }
-            }
-        } else {
-            println!("Did not find an edge... I don't even know what to do here. Just count non-empty nodes?")
-        }
-
-        inside_nodes
+    fn render_cell(&self, point: GridPoint2D) -> Cell {
+        let tile = &self.grid[point].tile;
+        let colour = match tile {
+            PipeTile::Ground => Rgb::new(60, 60, 60),
+            PipeTile::StartPosition => Rgb::new(0, 200, 0),
+            _ => Rgb::new(140, 140, 140)
+        };
+        Cell::new(tile.glyph(), colour)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::problems::day10::pipe_grid::PipeGrid;
+    use crate::problems::shared::render::Render;
 
     #[test]
     fn part1_simple_test_input() {
@@ -392,7 +131,7 @@ mod tests {
 .L-J.
 .....";
 
-        let grid = PipeGrid::parse(input);
+        let grid = PipeGrid::parse(input).unwrap();
         assert_eq!(4, grid.find_farthest_distance_from_start());
     }
     #[test]
@@ -403,8 +142,11 @@ SJLL7
 |F--J
 LJ.LJ";
 
-        let grid = PipeGrid::parse(input);
+        let grid = PipeGrid::parse(input).unwrap();
         assert_eq!(8, grid.find_farthest_distance_from_start());
+        assert_eq!(format!("{}\n", input), grid.canvas().to_text());
+        let canvas = grid.canvas().with_overlay(&grid.loop_overlay().with_glyph('*'));
+        assert_eq!("7-**-\n.***7\n**L**\n*****\n**.LJ\n", canvas.to_text());
     }
 
     #[test]
@@ -419,7 +161,7 @@ LJ.LJ";
 .L--J.L--J.
 ...........";
 
-        let grid = PipeGrid::parse(input);
+        let grid = PipeGrid::parse(input).unwrap();
         assert_eq!(4, grid.find_area_enclosed_by_loop());
     }
 
@@ -436,7 +178,7 @@ LJ.LJ";
 ..........";
 
 
-        let grid = PipeGrid::parse(input);
+        let grid = PipeGrid::parse(input).unwrap();
         assert_eq!(4, grid.find_area_enclosed_by_loop());
     }
 
@@ -455,7 +197,7 @@ L--J.L7...LJS7F-7L7.
 ....L---J.LJ.LJLJ...
 ";
 
-        let grid = PipeGrid::parse(input);
+        let grid = PipeGrid::parse(input).unwrap();
         assert_eq!(8, grid.find_area_enclosed_by_loop());
     }
 }
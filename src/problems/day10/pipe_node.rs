@@ -1,15 +1,15 @@
 use crate::problems::day10::pipe::PipeTile;
-use crate::problems::day10::position::Position;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
 
 
 pub struct PipeNode {
     pub tile: PipeTile,
-    pub position: Position,
-    pub connections: Vec<Position>,
+    pub position: GridPoint2D,
+    pub connections: Vec<GridPoint2D>,
 }
 
 impl PipeNode {
-    pub fn new(tile: PipeTile, position: Position, connections: Vec<Position>) -> PipeNode {
+    pub fn new(tile: PipeTile, position: GridPoint2D, connections: Vec<GridPoint2D>) -> PipeNode {
         PipeNode {
             tile,
             position,
@@ -1,14 +0,0 @@
-#[derive(Clone, PartialEq)]
-pub struct Position {
-    pub row: usize,
-    pub col: usize
-}
-
-impl Position {
-    pub fn new(row: usize, col: usize) -> Position {
-        Position {
-            row,
-            col
-        }
-    }
-}
\ No newline at end of file
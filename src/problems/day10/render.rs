@@ -0,0 +1,9 @@
+use std::fs;
+use crate::problems::day10::pipe_grid::PipeGrid;
+use crate::problems::shared::render::{Canvas, Render};
+
+pub fn execute(input_path: &std::path::PathBuf) -> Canvas {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let grid = PipeGrid::parse(&contents).unwrap();
+    grid.canvas().with_overlay(&grid.loop_overlay())
+}
\ No newline at end of file
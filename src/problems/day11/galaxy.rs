@@ -1,3 +1,9 @@
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::image::Rgb;
+use crate::problems::shared::parse::ParseError;
+use crate::problems::shared::render::{Cell, Overlay, Render};
+
 
 #[derive(PartialEq)]
 enum Space {
@@ -6,69 +12,49 @@ enum Space {
 }
 
 pub struct Universe {
-    space: Vec<Vec<Space>>
+    space: Grid2D<Space>
 }
 
 impl Space {
-    pub fn parse(c: char) -> Space {
+    pub fn parse(c: char) -> Option<Space> {
         match c {
-            '.' => Space::Empty,
-            '#' => Space::Galaxy,
-            _ => panic!("unsupported input")
+            '.' => Some(Space::Empty),
+            '#' => Some(Space::Galaxy),
+            _ => None
         }
     }
 }
 
 impl Universe {
-    pub fn parse(content: &str) -> Universe {
-        Universe {
-            space: content.lines().filter(|l| !l.is_empty()).map(|l| l.chars().map(|c| Space::parse(c)).collect()).collect()
-        }
+    pub fn parse(content: &str) -> Result<Universe, ParseError> {
+        Ok(Universe {
+            space: Grid2D::parse(content, Space::parse)?
+        })
     }
 
     fn map_galaxies(&self) -> Vec<(usize, usize)> {
-        let mut galaxies_by_id: Vec<(usize, usize)> = vec![];
-        for row in 0..self.space.len() {
-            for col in 0..self.space[row].len() {
-                if self.space[row][col] == Space::Galaxy {
-                    galaxies_by_id.push((row, col));
-                }
-            }
-        }
-
-        galaxies_by_id
+        self.space
+            .iter()
+            .filter(|(_, space)| **space == Space::Galaxy)
+            .map(|(point, _)| (point.x, point.y))
+            .collect()
     }
 
     fn map_expansion_rows(&self) -> Vec<usize> {
-        let mut expansion_rows = vec![];
-        for row in 0..self.space.len() {
-            let contains_galaxy = self.space[row].contains(&Space::Galaxy);
-            if !contains_galaxy {
-                expansion_rows.push(row);
-            }
-        }
-
-        expansion_rows
+        self.space
+            .rows()
+            .enumerate()
+            .filter(|(_, row)| !row.contains(&Space::Galaxy))
+            .map(|(index, _)| index)
+            .collect()
     }
 
     fn map_expansion_cols(&self) -> Vec<usize> {
-        let mut expansion_cols = vec![];
-
-        for col in 0..self.space[0].len() {
-            let mut contains_galaxy = false;
-            for row in 0..self.space.len() {
-                if self.space[row][col] == Space::Galaxy {
-                    contains_galaxy = true;
-                    break;
-                }
-            }
-
-            if !contains_galaxy {
-                expansion_cols.push(col);
-            }
-        }
-
-        expansion_cols
+        self.space
+            .columns()
+            .enumerate()
+            .filter_map(|(index, mut col)| if col.any(|space| *space == Space::Galaxy) { None } else { Some(index) })
+            .collect()
     }
 
     pub fn find_distance_between_pairs(&self, universe_expansion_factor: usize) -> usize {
@@ -112,11 +98,33 @@ impl Universe {
 
         distance_sum
     }
+
+    /// Highlights the empty rows and columns that grow as the universe expands.
+    pub fn expansion_overlay(&self) -> Overlay {
+        let rows = self.map_expansion_rows();
+        let cols = self.map_expansion_cols();
+        let expanding = self.space.points().filter(|point| rows.contains(&point.x) || cols.contains(&point.y));
+        Overlay::new(expanding, Rgb::new(40, 40, 120))
+    }
+}
+
+impl Render for Universe {
+    fn render_size(&self) -> (usize, usize) {
+        (self.space.height(), self.space.width())
+    }
+
+    fn render_cell(&self, point: GridPoint2D) -> Cell {
+        match self.space[point] {
+            Space::Galaxy => Cell::new('#', Rgb::new(255, 255, 160)),
+            Space::Empty => Cell::new('.', Rgb::new(20, 20, 40))
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::problems::day11::galaxy::Universe;
+    use crate::problems::shared::render::Render;
 
     #[test]
     fn part1_test() {
@@ -132,7 +140,7 @@ mod tests {
 .......#..
 #...#.....
 ";
-        let galaxy = Universe::parse(input);
+        let galaxy = Universe::parse(input).unwrap();
         assert_eq!(374, galaxy.find_distance_between_pairs(2))
     }
 
@@ -150,7 +158,14 @@ mod tests {
 .......#..
 #...#.....
 ";
-        let galaxy = Universe::parse(input);
+        let galaxy = Universe::parse(input).unwrap();
         assert_eq!(1030, galaxy.find_distance_between_pairs(10))
     }
+
+    #[test]
+    fn renders_expanding_space() {
+        let galaxy = Universe::parse("#..\n...\n..#").unwrap();
+        let canvas = galaxy.canvas().with_overlay(&galaxy.expansion_overlay().with_glyph('~'));
+        assert_eq!("#~.\n~~~\n.~#\n", canvas.to_text());
+    }
 }
\ No newline at end of file
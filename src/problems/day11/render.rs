@@ -0,0 +1,9 @@
+use std::fs;
+use crate::problems::day11::galaxy::Universe;
+use crate::problems::shared::render::{Canvas, Render};
+
+pub fn execute(input_path: &std::path::PathBuf) -> Canvas {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let universe = Universe::parse(&contents).unwrap();
+    universe.canvas().with_overlay(&universe.expansion_overlay())
+}
\ No newline at end of file
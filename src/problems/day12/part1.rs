@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day12::springy::{SpringCollection};
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let spring_collection = SpringCollection::parse(&contents, 1);
-	println!("Part 1: {}", spring_collection.get_possible_permutations());
+    let spring_collection = SpringCollection::parse(&contents, 1).unwrap();
+    spring_collection.get_possible_permutations().to_string()
 }
\ No newline at end of file
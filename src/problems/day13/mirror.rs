@@ -1,76 +1,53 @@
-#[derive(PartialEq, Debug)]
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::image::Rgb;
+use crate::problems::shared::parse::{self, Line, ParseError};
+use crate::problems::shared::render::{Canvas, Cell, Overlay, Render};
+
+#[derive(Clone, PartialEq, Debug)]
 pub enum Ground {
     Ash,
     Rocks
 }
 
 impl Ground {
-    pub fn parse(c: char) -> Ground {
+    pub fn parse(c: char) -> Option<Ground> {
         match c {
-            '.' => Ground::Ash,
-            '#' => Ground::Rocks,
-            _ => panic!("unsupported input")
+            '.' => Some(Ground::Ash),
+            '#' => Some(Ground::Rocks),
+            _ => None
         }
     }
 }
 
 pub struct Pattern {
-    layout: Vec<Vec<Ground>>
+    layout: Grid2D<Ground>
 }
 
 impl Pattern {
-    pub fn parse(data: &str) -> Pattern {
-        let layout = data.lines().filter(|l| !l.is_empty()).map(|l| l.chars().map(|c| Ground::parse(c)).collect()).collect();
+    #[allow(dead_code)]
+    pub fn parse(data: &str) -> Result<Pattern, ParseError> {
+        Pattern::from_lines(parse::lines(data))
+    }
 
-        Pattern {
-            layout
-        }
+    fn from_lines<'a>(lines: impl IntoIterator<Item = Line<'a>>) -> Result<Pattern, ParseError> {
+        Ok(Pattern {
+            layout: Grid2D::parse_lines(lines, Ground::parse)?
+        })
     }
 
     pub fn find_vertical_reflection_index_before(&self, reflection_tolerance: usize) -> Option<usize> {
-        let num_columns = self.layout[0].len();
-        let lower_vertical_reflection = if reflection_tolerance > 0 { self.find_vertical_reflection_index_before(reflection_tolerance - 1) } else { None };
-
-        // go until we think we've found a reflection or until the left_index is at the end
-        for left_index in 0..num_columns - 1 {
-            if let Some(previous_index) = lower_vertical_reflection {
-                if previous_index == left_index {
-                    continue; // skip this index
-                }
-            }
-            let mut scan_left = left_index;
-            let mut scan_right = left_index + 1;
-            loop {
-                let mut violating_differences = 0;
-                for row in 0..self.layout.len() {
-                    if self.layout[row][scan_left] != self.layout[row][scan_right] {
-                        violating_differences += 1; // if we changed either to the other, then this could still be a reflection
-                    }
-                }
-
-                if violating_differences <= reflection_tolerance {
-                    if scan_left == 0 || scan_right == num_columns - 1 {
-                        // we hit an edge, this is the actual point
-                        return Some(left_index);
-                    } else {
-                        // check next index
-                        scan_left -= 1;
-                        scan_right += 1;
-                    }
-                } else {
-                    break; // check something else
-                }
-            }
-        }
-
-        None
+        // a vertical reflection is a horizontal reflection of the transposed pattern
+        let transposed = Pattern {
+            layout: self.layout.transpose()
+        };
+        transposed.find_horizontal_reflection_index_before(reflection_tolerance)
     }
 
-
     pub fn find_horizontal_reflection_index_before(&self, reflection_tolerance: usize) -> Option<usize> {
-        // go until we think we've found a reflection or until the left_index is at the end
+        // go until we think we've found a reflection or until the top_index is at the end
         let lower_horizontal_reflection = if reflection_tolerance > 0 { self.find_horizontal_reflection_index_before(reflection_tolerance - 1) } else { None };
-        for top_index in 0..self.layout.len() - 1 {
+        for top_index in 0..self.layout.height() - 1 {
             if let Some(previous_index) = lower_horizontal_reflection {
                 if previous_index == top_index {
                     continue; // skip this index
@@ -79,15 +56,14 @@ impl Pattern {
             let mut scan_top = top_index;
             let mut scan_bottom = top_index + 1;
             loop {
-                let mut violating_differences = 0;
-                for col in 0..self.layout[top_index].len() {
-                    if self.layout[scan_top][col] != self.layout[scan_bottom][col] {
-                        violating_differences += 1;
-                    }
-                }
+                let violating_differences = self.layout.row(scan_top)
+                    .iter()
+                    .zip(self.layout.row(scan_bottom))
+                    .filter(|(top, bottom)| top != bottom)
+                    .count(); // if we changed either to the other, then this could still be a reflection
 
                 if violating_differences <= reflection_tolerance {
-                    if scan_top == 0 || scan_bottom == self.layout.len() - 1 {
+                    if scan_top == 0 || scan_bottom == self.layout.height() - 1 {
                         // we hit an edge, this is the actual point
                         return Some(top_index);
                     } else {
@@ -126,27 +102,60 @@ impl Pattern {
     }
 }
 
+impl Pattern {
+    /// Highlights the two rows or columns either side of the line of reflection. Panics if there isn't one,
+    /// like `find_mirror_value`.
+    pub fn reflection_overlay(&self, reflection_tolerance: usize) -> Overlay {
+        let points: Vec<GridPoint2D> = match self.find_mirror_value(reflection_tolerance) {
+            value if value >= 100 => {
+                let row = value / 100 - 1;
+                self.layout.points().filter(|p| p.x == row || p.x == row + 1).collect()
+            },
+            value => self.layout.points().filter(|p| p.y + 1 == value || p.y == value).collect()
+        };
+        Overlay::new(points, Rgb::new(80, 160, 255))
+    }
+}
+
+impl Render for Pattern {
+    fn render_size(&self) -> (usize, usize) {
+        (self.layout.height(), self.layout.width())
+    }
+
+    fn render_cell(&self, point: GridPoint2D) -> Cell {
+        match self.layout[point] {
+            Ground::Ash => Cell::new('.', Rgb::new(150, 150, 150)),
+            Ground::Rocks => Cell::new('#', Rgb::new(90, 60, 40))
+        }
+    }
+}
+
 pub struct Observation {
     patterns: Vec<Pattern>
 }
 
 impl Observation {
-    pub fn parse(data: &str) -> Observation {
-        let patterns = data.split("\n\n").filter(|p| !p.is_empty());
-        let patterns = patterns.map(|p| Pattern::parse(p)).collect();
-        Observation {
+    pub fn parse(data: &str) -> Result<Observation, ParseError> {
+        let patterns = parse::blocks(data).into_iter().map(Pattern::from_lines).collect::<Result<Vec<Pattern>, ParseError>>()?;
+        Ok(Observation {
             patterns
-        }
+        })
     }
 
     pub fn find_mirror_values(&self, reflection_tolerance: usize) -> usize {
         self.patterns.iter().map(|p| p.find_mirror_value(reflection_tolerance)).sum()
     }
+
+    /// Every pattern, one below the next, with its line of reflection highlighted.
+    pub fn reflection_canvas(&self, reflection_tolerance: usize) -> Canvas {
+        Canvas::stacked(self.patterns.iter().map(|p| p.canvas().with_overlay(&p.reflection_overlay(reflection_tolerance))))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::problems::day13::mirror::Pattern;
+    use crate::problems::shared::render::Render;
 
     #[test]
     fn part1_test_input() {
@@ -158,8 +167,10 @@ mod tests {
 ..#.##.#.
 ..##..##.
 #.#.##.#.";
-        let pattern = Pattern::parse(vertical_input);
+        let pattern = Pattern::parse(vertical_input).unwrap();
         assert_eq!(5, pattern.find_mirror_value(0));
+        let canvas = pattern.canvas().with_overlay(&pattern.reflection_overlay(0).with_glyph('|'));
+        assert_eq!("#.##||##.", canvas.to_text().lines().next().unwrap());
 
         let horizontal_input = "
 #...##..#
@@ -169,7 +180,7 @@ mod tests {
 #####.##.
 ..##..###
 #....#..#";
-        let pattern = Pattern::parse(horizontal_input);
+        let pattern = Pattern::parse(horizontal_input).unwrap();
         assert_eq!(400, pattern.find_mirror_value(0));
     }
 
@@ -183,7 +194,7 @@ mod tests {
 ..#.##.#.
 ..##..##.
 #.#.##.#.";
-        let pattern = Pattern::parse(input1);
+        let pattern = Pattern::parse(input1).unwrap();
         assert_eq!(300, pattern.find_mirror_value(1));
 
         let input2 = "
@@ -194,7 +205,7 @@ mod tests {
 #####.##.
 ..##..###
 #....#..#";
-        let pattern = Pattern::parse(input2);
+        let pattern = Pattern::parse(input2).unwrap();
         assert_eq!(100, pattern.find_mirror_value(1));
     }
 }
\ No newline at end of file
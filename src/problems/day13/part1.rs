@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day13::mirror::Observation;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let observations = Observation::parse(&contents);
-	println!("Part 1: {}", observations.find_mirror_values(0));
+    let observations = Observation::parse(&contents).unwrap();
+    observations.find_mirror_values(0).to_string()
 }
\ No newline at end of file
@@ -0,0 +1,9 @@
+use std::fs;
+use crate::problems::day13::mirror::Observation;
+use crate::problems::shared::render::Canvas;
+
+pub fn execute(input_path: &std::path::PathBuf) -> Canvas {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let observations = Observation::parse(&contents).unwrap();
+    observations.reflection_canvas(0)
+}
\ No newline at end of file
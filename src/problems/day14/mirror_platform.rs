@@ -1,5 +1,12 @@
-use std::collections::HashMap;
-use std::fmt;
+use crate::problems::shared::animation::Recorder;
+use crate::problems::shared::bit_grid::BitGrid;
+use crate::problems::shared::cycle::find_cycle;
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::image::Rgb;
+use crate::problems::shared::parse::ParseError;
+use crate::problems::shared::render::{Cell, Render};
 
 #[derive(PartialEq, Hash, Eq, Clone)]
 pub enum Tile {
@@ -8,25 +15,6 @@ pub enum Tile {
     RoundedRock,
 }
 
-impl fmt::Display for Tile {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let c = match self {
-            Tile::CubeRock => '#',
-            Tile::Empty => '.',
-            Tile::RoundedRock => 'O'
-        };
-        write!(f, "{}", c)
-    }
-}
-
-#[derive(PartialEq, Hash, Eq, Debug)]
-pub enum TiltDirection {
-    Up,
-    Down,
-    Left,
-    Right
-}
-
 impl Tile {
     pub fn parse(c: char) -> Option<Tile> {
         match c {
@@ -39,128 +27,122 @@ impl Tile {
 }
 
 pub struct Platform {
-    grid: Vec<Vec<Tile>>
+    cube_rocks: BitGrid,
+    rounded_rocks: BitGrid
 }
 
 impl Platform {
-    pub fn parse(contents: &str) -> Option<Platform> {
-        Some(Platform {
-            grid: contents.lines().filter(|l| !l.is_empty()).map(|l| l.chars().map(|c| Tile::parse(c).unwrap()).collect()).collect()
+    pub fn parse(contents: &str) -> Result<Platform, ParseError> {
+        let grid = Grid2D::parse(contents, Tile::parse)?;
+        Ok(Platform {
+            cube_rocks: BitGrid::from_grid(&grid, |tile| *tile == Tile::CubeRock),
+            rounded_rocks: BitGrid::from_grid(&grid, |tile| *tile == Tile::RoundedRock)
         })
     }
 
     pub fn compute_load(&self) -> usize {
-        let mut load = 0;
-        for row in 0..self.grid.len() {
-            for col in 0..self.grid[row].len() {
-                if self.grid[row][col] == Tile::RoundedRock {
-                    load += self.grid.len() - row;
-                }
-
-            }
-        }
-        load
+        let height = self.rounded_rocks.height();
+        (0..height).map(|row| self.rounded_rocks.count_ones_in_row(row) * (height - row)).sum()
     }
 
     #[allow(dead_code)]
     fn print(&self) {
-        for row in 0..self.grid.len() {
-            for col in 0..self.grid[row].len() {
-                print!("{}", self.grid[row][col])
-            }
-            print!("\n");
-        }
-        print!("\n");
+        println!("{}", self.canvas().to_text());
     }
 
+    /// Runs `cycles_to_run` spin cycles, skipping ahead once the platform starts repeating itself.
     pub fn spin(&mut self, cycles_to_run: usize) {
-        let mut cycle_solutions = HashMap::new();
-        let mut cycle_index = 0;
-        while cycle_index < cycles_to_run {
-            let directions = vec![TiltDirection::Up, TiltDirection::Left, TiltDirection::Down, TiltDirection::Right];
-            for direction in directions {
-                self.tilt(&direction);
-                if let Some(cycle_starts_at_idx) = cycle_solutions.get(&(self.grid.to_vec())) {
-                    // advance the index to the index N cycles ahead
-                    let cycle_length = cycle_index - cycle_starts_at_idx;
-                    let number_of_cycles = (cycles_to_run - cycle_starts_at_idx) / cycle_length;
-                    let final_cycle_start_index = number_of_cycles * cycle_length + cycle_starts_at_idx;
-                    cycle_index = final_cycle_start_index;
-                } else {
-                    cycle_solutions.insert(self.grid.to_vec(), cycle_index);
-                }
-            }
+        self.spin_recorded(cycles_to_run, &mut Recorder::disabled());
+    }
+
+    /// Like `spin`, offering `recorder` a frame after every tilt, up until the platform starts repeating
+    /// itself. The cycles skipped over are not recorded.
+    pub fn spin_recorded(&mut self, cycles_to_run: usize, recorder: &mut Recorder) {
+        let cycle = find_cycle(self.rounded_rocks.clone(), |rounded_rocks| {
+            Platform::spin_once(&self.cube_rocks, rounded_rocks.clone(), recorder)
+        });
 
-            cycle_index += 1;
+        self.rounded_rocks = cycle.state_at(cycles_to_run).clone();
+    }
+
+    fn spin_once(cube_rocks: &BitGrid, mut rounded_rocks: BitGrid, recorder: &mut Recorder) -> BitGrid {
+        for direction in [Grid2dDirection::Up, Grid2dDirection::Left, Grid2dDirection::Down, Grid2dDirection::Right] {
+            rounded_rocks = Platform::tilted(cube_rocks, rounded_rocks, direction);
+            Platform::record(cube_rocks, &rounded_rocks, recorder);
         }
+
+        rounded_rocks
     }
 
-    pub fn tilt(&mut self, direction: &TiltDirection) {
-        match direction {
-            TiltDirection::Down => {
-                for row in (0..self.grid.len()).rev() {
-                    for col in 0..self.grid[row].len() {
-                        if self.grid[row][col] == Tile::RoundedRock {
-                            let mut tilt = row;
-                            while tilt < self.grid.len() - 1 && self.grid[tilt + 1][col] == Tile::Empty {
-                                self.grid[tilt + 1][col] = Tile::RoundedRock; // make new tile me
-                                self.grid[tilt][col] = Tile::Empty; // make old tile empty
-                                tilt += 1;
-                            }
-                        }
-                    }
-                }
-            },
-            TiltDirection::Up => {
-                for row in 0..self.grid.len() {
-                    for col in 0..self.grid[row].len() {
-                       if self.grid[row][col] == Tile::RoundedRock {
-                           let mut tilt = row;
-                           while tilt > 0 && self.grid[tilt - 1][col] == Tile::Empty {
-                               self.grid[tilt - 1][col] = Tile::RoundedRock; // make new tile me
-                               self.grid[tilt][col] = Tile::Empty; // make old tile empty
-                               tilt -= 1;
-                           }
-                       }
-                    }
-                }
-            },
-            TiltDirection::Left => {
-                for row in 0..self.grid.len() {
-                    for col in 0..self.grid[row].len() {
-                        if self.grid[row][col] == Tile::RoundedRock {
-                            let mut tilt = col;
-                            while tilt > 0 && self.grid[row][tilt - 1] == Tile::Empty {
-                                self.grid[row][tilt - 1] = Tile::RoundedRock; // make new tile me
-                                self.grid[row][tilt] = Tile::Empty; // make old tile empty
-                                tilt -= 1;
-                            }
-                        }
-                    }
-                }
-            },
-            TiltDirection::Right => {
-                for row in 0..self.grid.len() {
-                    for col in (0..self.grid[row].len()).rev() {
-                        if self.grid[row][col] == Tile::RoundedRock {
-                            let mut tilt = col;
-                            while tilt < self.grid[row].len() - 1 && self.grid[row][tilt + 1] == Tile::Empty {
-                                self.grid[row][tilt + 1] = Tile::RoundedRock; // make new tile me
-                                self.grid[row][tilt] = Tile::Empty; // make old tile empty
-                                tilt += 1;
-                            }
-                        }
-                    }
-                }
-            },
+    /// Rolls every rounded rock in the given direction until it hits something. All the rocks with an
+    /// empty tile in front of them move together, one tile at a time, until none can move.
+    fn tilted(cube_rocks: &BitGrid, mut rounded_rocks: BitGrid, direction: Grid2dDirection) -> BitGrid {
+        loop {
+            let mut moved = rounded_rocks.shifted(direction);
+            moved.difference_with(cube_rocks);
+            moved.difference_with(&rounded_rocks);
+            if moved.is_empty() {
+                return rounded_rocks;
+            }
+
+            rounded_rocks.difference_with(&moved.shifted(direction.opposite()));
+            rounded_rocks.union_with(&moved);
         }
     }
+
+    fn record(cube_rocks: &BitGrid, rounded_rocks: &BitGrid, recorder: &mut Recorder) {
+        recorder.record(|| Platform {
+            cube_rocks: cube_rocks.clone(),
+            rounded_rocks: rounded_rocks.clone()
+        }.canvas());
+    }
+
+    pub fn tilt(&mut self, direction: &Grid2dDirection) {
+        self.tilt_recorded(direction, &mut Recorder::disabled());
+    }
+
+    /// Like `tilt`, offering `recorder` a frame once the rocks have stopped rolling.
+    pub fn tilt_recorded(&mut self, direction: &Grid2dDirection, recorder: &mut Recorder) {
+        self.rounded_rocks = Platform::tilted(&self.cube_rocks, self.rounded_rocks.clone(), *direction);
+        Platform::record(&self.cube_rocks, &self.rounded_rocks, recorder);
+    }
 }
 
 
+impl Render for Platform {
+    fn render_size(&self) -> (usize, usize) {
+        (self.cube_rocks.height(), self.cube_rocks.width())
+    }
+
+    fn render_cell(&self, point: GridPoint2D) -> Cell {
+        if self.cube_rocks.get(&point) {
+            Cell::new('#', Rgb::new(110, 110, 110))
+        } else if self.rounded_rocks.get(&point) {
+            Cell::new('O', Rgb::new(230, 180, 90))
+        } else {
+            Cell::new('.', Rgb::new(30, 30, 30))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::problems::day14::mirror_platform::{Platform, TiltDirection};
+    use crate::problems::day14::mirror_platform::Platform;
+    use crate::problems::shared::animation::Recorder;
+    use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+    use crate::problems::shared::render::{Canvas, Render};
+
+    const EXAMPLE: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
 
     #[test]
     fn part1() {
@@ -176,8 +158,21 @@ O.#..O.#.#
 #OO..#....
 ";
         let mut grid = Platform::parse(input).unwrap();
-        grid.tilt(&TiltDirection::Up);
+        grid.tilt(&Grid2dDirection::Up);
         assert_eq!(136, grid.compute_load());
+
+        let tilted = "OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....
+";
+        assert_eq!(tilted, grid.canvas().to_text());
     }
 
     #[test]
@@ -198,4 +193,26 @@ O.#..O.#.#
 
         assert_eq!(64, grid.compute_load());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn records_each_tilt() {
+        let mut platform = Platform::parse("O\n.\n.\n#\n").unwrap();
+        let mut frames = vec![];
+        let mut recorder = Recorder::new(1, |frame: Canvas| frames.push(frame.to_text()));
+        platform.tilt_recorded(&Grid2dDirection::Down, &mut recorder);
+        platform.tilt_recorded(&Grid2dDirection::Up, &mut recorder);
+        drop(recorder);
+        assert_eq!(vec![".\n.\nO\n#\n", "O\n.\n.\n#\n"], frames);
+    }
+
+    #[test]
+    fn stops_recording_once_the_spins_repeat() {
+        let mut platform = Platform::parse(EXAMPLE).unwrap();
+        let mut frames = 0;
+        platform.spin_recorded(1000000000, &mut Recorder::new(1, |_| frames += 1));
+
+        // three spins lead into a loop of seven, and each spin tilts four ways
+        assert_eq!(4 * (3 + 7), frames);
+        assert_eq!(64, platform.compute_load());
+    }
+}
@@ -1,9 +1,10 @@
 use std::fs;
-use crate::problems::day14::mirror_platform::{Platform, TiltDirection};
+use crate::problems::day14::mirror_platform::Platform;
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
     let mut platform = Platform::parse(&contents).unwrap();
-    platform.tilt(&TiltDirection::Up);
-	println!("Part 1: {}", platform.compute_load());
+    platform.tilt(&Grid2dDirection::Up);
+    platform.compute_load().to_string()
 }
\ No newline at end of file
@@ -0,0 +1,11 @@
+use std::fs;
+use crate::problems::day14::mirror_platform::Platform;
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+use crate::problems::shared::render::{Canvas, Render};
+
+pub fn execute(input_path: &std::path::PathBuf) -> Canvas {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let mut platform = Platform::parse(&contents).unwrap();
+    platform.tilt(&Grid2dDirection::Up);
+    platform.canvas()
+}
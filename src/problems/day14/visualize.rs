@@ -0,0 +1,9 @@
+use std::fs;
+use crate::problems::day14::mirror_platform::Platform;
+use crate::problems::shared::animation::Recorder;
+
+pub fn execute(input_path: &std::path::PathBuf, recorder: &mut Recorder) {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let mut platform = Platform::parse(&contents).unwrap();
+    platform.spin_recorded(1000000000, recorder);
+}
\ No newline at end of file
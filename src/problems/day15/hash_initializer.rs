@@ -1,9 +1,8 @@
+use crate::problems::shared::parse::{self, Line};
+
 pub fn determine_hash(sequence: &str) -> usize {
     let mut current_hash = 0;
     for c in sequence.chars().filter(|c| c.is_ascii()) {
-        if c == '\n' || c == '\r' {
-            continue;
-        }
         let char_value = c as usize; // only need 8 bits, let's us do math
         current_hash += char_value;
         current_hash *= 17;
@@ -13,10 +12,15 @@ pub fn determine_hash(sequence: &str) -> usize {
     current_hash
 }
 
+/// The comma separated steps of the initialization sequence, ignoring line endings.
+pub fn initialization_steps(contents: &str) -> impl Iterator<Item = Line<'_>> {
+    parse::lines(contents).flat_map(|line| line.split(",")).filter(|step| !step.text.is_empty())
+}
+
 pub fn determine_hash_sum(contents: &str) -> usize {
     let mut hash_result = 0;
-    for sequence in contents.split(',').filter(|l| !l.is_empty()) {
-        hash_result += determine_hash(sequence);
+    for step in initialization_steps(contents) {
+        hash_result += determine_hash(step.text);
     }
 
     hash_result
@@ -1,4 +1,5 @@
-use crate::problems::day15::hash_initializer::determine_hash;
+use crate::problems::day15::hash_initializer::{determine_hash, initialization_steps};
+use crate::problems::shared::parse::ParseError;
 
 #[derive(Clone)]
 struct Lens {
@@ -49,42 +50,15 @@ impl HashmapBox {
         focus
     }
 
-    pub fn execute(instructions: &str) -> u32 {
+    pub fn execute(instructions: &str) -> Result<u32, ParseError> {
         let mut boxes = vec![HashmapBox::new(); 256];
 
-        for sequence in instructions.split(',') {
-            let mut lens_id = None;
-            let mut operation = None;
-            let mut operation_idx = 0;
-            for (idx, c) in sequence.chars().enumerate() {
-                match c {
-                    '-' => {
-                        operation = Some('-');
-                        operation_idx = idx;
-
-                    },
-                    '=' => {
-                        operation = Some('=');
-                        operation_idx = idx;
-                    },
-                    data => {
-                        if data.is_numeric() {
-                            lens_id = data.to_digit(10);
-                        }
-                    }
-                }
-            }
-
-            let hash = determine_hash(&sequence[0..operation_idx]);
-            let label = sequence[0..operation_idx].to_string();
-            let lens_box = &mut boxes[hash];
-
-            let operation = operation.unwrap();
-            if operation == '-' {
-                lens_box.remove_lens(label);
+        for step in initialization_steps(instructions) {
+            if let Some(label) = step.text.strip_suffix('-') {
+                boxes[determine_hash(label)].remove_lens(label.to_string());
             } else {
-                let lens_id = lens_id.unwrap();
-                lens_box.add_or_update_lens(label, lens_id);
+                let (label, lens_type) = step.split_once("=")?;
+                boxes[determine_hash(label.text)].add_or_update_lens(label.text.to_string(), lens_type.parse::<u32>()?);
             }
         }
 
@@ -92,6 +66,18 @@ impl HashmapBox {
         for (idx, lens_box) in boxes.iter().enumerate() {
             result += lens_box.compute_focus(idx as u32);
         }
-        result
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::day15::hashmap_box::HashmapBox;
+
+    #[test]
+    fn part2() {
+        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\r\n";
+        assert_eq!(Ok(145), HashmapBox::execute(input));
+        assert_eq!("line 1: could not parse \"x\"", HashmapBox::execute("rn=x").unwrap_err().to_string());
     }
 }
\ No newline at end of file
@@ -1,129 +1,137 @@
 use crate::problems::day16::mirror::MirrorTile;
+use crate::problems::shared::animation::Recorder;
+use crate::problems::shared::bit_grid::BitGrid;
+use crate::problems::shared::bitset::BitSet;
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::image::Rgb;
+use crate::problems::shared::parse::ParseError;
+use crate::problems::shared::render::{Cell, Overlay, Render};
 
 pub struct LavaFactory {
-    grid: Vec<Vec<MirrorTile>>
+    grid: Grid2D<MirrorTile>
 }
 
 #[derive(Clone, PartialEq)]
 struct LaserPoint {
-    row: usize,
-    col: usize,
-    row_vel: i32,
-    col_vel: i32
+    point: GridPoint2D,
+    direction: Grid2dDirection
 }
 
-impl LavaFactory {
-    pub fn parse(contents: &str) -> Option<LavaFactory> {
-        let mut grid: Vec<Vec<MirrorTile>> = vec![];
-
-        for line in contents
-            .lines()
-            .filter(|l| !l.is_empty()) {
-            let mut row = vec![];
-            for c in line.chars() {
-                if let Some(tile) = MirrorTile::parse(c) {
-                    row.push(tile);
-                } else {
-                    return None;
-                }
-            }
-            grid.push(row);
+impl LaserPoint {
+    fn new(row: usize, col: usize, direction: Grid2dDirection) -> LaserPoint {
+        LaserPoint {
+            point: GridPoint2D::new(row, col),
+            direction
         }
+    }
+}
 
-        Some(LavaFactory {
-            grid
+impl LavaFactory {
+    pub fn parse(contents: &str) -> Result<LavaFactory, ParseError> {
+        Ok(LavaFactory {
+            grid: Grid2D::parse(contents, MirrorTile::parse)?
         })
     }
 
     pub fn compute_maximum_energy(&self) -> usize {
         let mut maximum_energy = 0;
-        for row in 0..self.grid.len() {
-            maximum_energy = std::cmp::max(maximum_energy, self.compute_energized_with_start(LaserPoint {
-                row,
-                col: 0,
-                row_vel: 0,
-                col_vel: 1
-            }));
-
-            maximum_energy = std::cmp::max(maximum_energy, self.compute_energized_with_start(LaserPoint {
-                row,
-                col: self.grid[row].len() - 1,
-                row_vel: 0,
-                col_vel: -1
-            }));
+        for row in 0..self.grid.height() {
+            maximum_energy = std::cmp::max(maximum_energy, self.compute_energized_with_start(LaserPoint::new(row, 0, Grid2dDirection::Right)));
+            maximum_energy = std::cmp::max(maximum_energy, self.compute_energized_with_start(LaserPoint::new(row, self.grid.width() - 1, Grid2dDirection::Left)));
         }
 
-        for col in 0..self.grid.len() {
-            maximum_energy = std::cmp::max(maximum_energy, self.compute_energized_with_start(LaserPoint {
-                row: 0,
-                col: col,
-                row_vel: 1,
-                col_vel: 0
-            }));
-
-            maximum_energy = std::cmp::max(maximum_energy, self.compute_energized_with_start(LaserPoint {
-                row: self.grid.len() - 1,
-                col: 0,
-                row_vel: -1,
-                col_vel: 0
-            }));
+        for col in 0..self.grid.width() {
+            maximum_energy = std::cmp::max(maximum_energy, self.compute_energized_with_start(LaserPoint::new(0, col, Grid2dDirection::Down)));
+            maximum_energy = std::cmp::max(maximum_energy, self.compute_energized_with_start(LaserPoint::new(self.grid.height() - 1, 0, Grid2dDirection::Up)));
         }
 
         maximum_energy
     }
 
     fn compute_energized_with_start(&self, starting_node: LaserPoint) -> usize {
-        let mut energized_grid_points: Vec<LaserPoint> = vec![];
+        self.energize(starting_node, &mut Recorder::disabled()).count_ones()
+    }
+
+    /// Every tile a beam passes through when it enters at `starting_node`. `recorder` is offered a frame
+    /// each time another tile is energized.
+    fn energize(&self, starting_node: LaserPoint, recorder: &mut Recorder) -> BitGrid {
+        // one bit per tile and direction a beam can cross it in
+        let width = self.grid.width();
+        let beam_index = |laser: &LaserPoint| {
+            let direction = Grid2dDirection::all().iter().position(|d| *d == laser.direction).expect("Every direction is listed");
+            (laser.point.x * width + laser.point.y) * 4 + direction
+        };
+
+        let mut explored_beams = BitSet::with_capacity(self.grid.height() * width * 4);
+        let mut energized_tiles = BitGrid::new(self.grid.height(), width);
         let mut active_laser_paths = vec![starting_node];
         while let Some(active_laser) = active_laser_paths.pop() {
-            if let Some(_) = energized_grid_points.iter().find(|visited_laser| **visited_laser == active_laser) {
+            if !explored_beams.insert(beam_index(&active_laser)) {
                 continue; // this path has been explored already. It could end up being recursive, so ignore it -- its already defined
             }
 
             // I visit this node!
-            energized_grid_points.push(active_laser.clone());
-
-            let node = &self.grid[active_laser.row][active_laser.col];
-            let new_paths = node.encounter((active_laser.row_vel, active_laser.col_vel));
-            for (row_vel, col_vel) in new_paths {
-                if (row_vel < 0 && active_laser.row == 0) || (row_vel > 0 && active_laser.row == self.grid.len() - 1) {
-                    continue;
-                }
+            if !energized_tiles.get(&active_laser.point) {
+                energized_tiles.set(active_laser.point, true);
+                recorder.record(|| self.canvas().with_overlay(&LavaFactory::energized_tiles_overlay(&energized_tiles)));
+            }
 
-                if (col_vel < 0 && active_laser.col == 0) || (col_vel > 0 && active_laser.col == self.grid[active_laser.row].len() - 1) {
-                    continue;
+            let node = &self.grid[active_laser.point];
+            for direction in node.encounter(active_laser.direction) {
+                if let Some(point) = self.grid.step(&active_laser.point, direction) {
+                    active_laser_paths.push(LaserPoint {
+                        point,
+                        direction
+                    })
                 }
-
-                let next_row = (active_laser.row as i32 + row_vel) as usize;
-                let next_col = (active_laser.col as i32 + col_vel) as usize;
-                active_laser_paths.push(LaserPoint {
-                    row: next_row,
-                    col: next_col,
-                    row_vel,
-                    col_vel
-                })
             }
         }
 
-        let mut energized_tiles = energized_grid_points.iter().map(|l| (l.row, l.col)).collect::<Vec<(usize, usize)>>();
-        energized_tiles.sort();
-        energized_tiles.dedup();
-        energized_tiles.len()
+        energized_tiles
     }
 
     pub fn compute_energized(&self) -> usize {
-        self.compute_energized_with_start(LaserPoint {
-            row: 0,
-            col: 0,
-            row_vel: 0,
-            col_vel: 1
-        })
+        self.compute_energized_with_start(LaserPoint::new(0, 0, Grid2dDirection::Right))
+    }
+
+    /// Highlights the tiles energized by a beam entering the top left corner heading right.
+    pub fn energized_overlay(&self) -> Overlay {
+        let energized = self.energize(LaserPoint::new(0, 0, Grid2dDirection::Right), &mut Recorder::disabled());
+        LavaFactory::energized_tiles_overlay(&energized)
+    }
+
+    /// Offers `recorder` a frame for every tile the beam from the top left corner energizes.
+    pub fn record_energizing(&self, recorder: &mut Recorder) {
+        self.energize(LaserPoint::new(0, 0, Grid2dDirection::Right), recorder);
+    }
+
+    fn energized_tiles_overlay(energized: &BitGrid) -> Overlay {
+        Overlay::new(energized.ones(), Rgb::new(255, 140, 0))
+    }
+}
+
+impl Render for LavaFactory {
+    fn render_size(&self) -> (usize, usize) {
+        (self.grid.height(), self.grid.width())
+    }
+
+    fn render_cell(&self, point: GridPoint2D) -> Cell {
+        let tile = &self.grid[point];
+        let colour = match tile {
+            MirrorTile::EmptySpace => Rgb::new(40, 40, 40),
+            _ => Rgb::new(170, 200, 230)
+        };
+        Cell::new(tile.glyph(), colour)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::problems::day16::lava_factory::LavaFactory;
+    use crate::problems::shared::animation::Recorder;
+    use crate::problems::shared::render::Render;
 
     #[test]
     fn part1() {
@@ -139,6 +147,26 @@ mod tests {
 ..//.|....";
         let factory = LavaFactory::parse(input).unwrap();
         assert_eq!(46, factory.compute_energized());
+
+        let energized = "######....
+.#...#....
+.#...#####
+.#...##...
+.#...##...
+.#...##...
+.#..####..
+########..
+.#######..
+.#...#.#..
+";
+        // draw energized tiles as `#` and blank out the mirrors that stay dark
+        let drawn = factory.canvas().with_overlay(&factory.energized_overlay().with_glyph('#')).to_text();
+        assert_eq!(energized, drawn.replace(|c| c != '#' && c != '\n', "."));
+
+        let mut frames = vec![];
+        factory.record_energizing(&mut Recorder::new(1, |frame| frames.push(frame)));
+        assert_eq!(46, frames.len());
+        assert_eq!(drawn, frames[45].clone().with_overlay(&factory.energized_overlay().with_glyph('#')).to_text());
     }
 
     #[test]
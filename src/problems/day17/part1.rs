@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day17::factory_city::FactoryCity;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let factory = FactoryCity::parse(&contents);
-	println!("Part 1: {}", factory.compute_lowest_heat_loss(1, 3));
+    let factory = FactoryCity::parse(&contents).unwrap();
+    factory.compute_lowest_heat_loss(1, 3).to_string()
 }
\ No newline at end of file
@@ -0,0 +1,9 @@
+use std::fs;
+use crate::problems::day17::factory_city::FactoryCity;
+use crate::problems::shared::render::{Canvas, Render};
+
+pub fn execute(input_path: &std::path::PathBuf) -> Canvas {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let factory = FactoryCity::parse(&contents).unwrap();
+    factory.canvas().with_overlay(&factory.route_overlay(1, 3))
+}
\ No newline at end of file
@@ -1,143 +1,155 @@
 use crate::problems::day18::dig_plan::DigPlan;
+use crate::problems::shared::compression::CoordinateCompression;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::image::Rgb;
+use crate::problems::shared::render::{Cell, Render};
+use crate::problems::shared::sparse_grid::SparseGrid;
 
 #[derive(Copy, Clone, PartialEq)]
 enum GridSpace {
-    Empty,
     Wall,
     Outside
 }
 
-impl GridSpace {
-    pub fn pretty_print(&self) {
-        match self {
-            GridSpace::Empty => print!("."),
-            GridSpace::Wall => print!("#"),
-            GridSpace::Outside => print!("O")
-        }
-    }
-}
-
 pub struct DigGrid {
-    grid: Vec<Vec<GridSpace>>
+    // undug ground is left unset
+    grid: SparseGrid<GridSpace>,
+    // when set, the grid holds compressed cells rather than single tiles
+    compression: Option<CoordinateCompression>
 }
 
 impl DigGrid {
-    #[allow(dead_code)]
-    pub fn pretty_print(&self) {
-        // debug printer
-        for row in 0..self.grid.len() {
-            for col in 0..self.grid[row].len() {
-                self.grid[row][col].pretty_print();
-            }
-
-            print!("\r\n");
-        }
-    }
-
-    #[allow(dead_code)]
     pub fn count_inside(&mut self) -> u64 {
         // first, construct a map of the edges
         // then compute the number of tiles that are inside
 
         // now map inside/outside
-        // basically, any nodes that touch a node on the outside are outside
-        let mut outside_nodes = vec![];
-        for col in 0..self.grid[0].len() {
-            if self.grid[0][col] == GridSpace::Empty {
-                outside_nodes.push((0, col));
-            }
-
-            if self.grid[self.grid.len() - 1][col] == GridSpace::Empty {
-                outside_nodes.push((self.grid.len() - 1, col));
-            }
-        }
-
-        for row in 0..self.grid.len() {
-            if self.grid[row][0] == GridSpace::Empty {
-                outside_nodes.push((row, 0));
-            }
-
-            if self.grid[row][self.grid[row].len() - 1] == GridSpace::Empty {
-                outside_nodes.push((row, self.grid[row].len() - 1));
-            }
-        }
-
+        // pad the trench by a ring of ground so the outside is connected, then flood it from a corner
+        let Some(trench) = self.grid.bounds() else {
+            return 0;
+        };
+        let area = trench.grow(1);
+        let weight = |node: (i64, i64)| self.compression.as_ref().map_or(1, |c| c.weight(node));
+        let mut outside_nodes = vec![area.min];
+        let mut outside_count = 0;
         while let Some(outside_node) = outside_nodes.pop() {
-            let (row, col) = outside_node;
-            self.grid[row][col] = GridSpace::Outside;
-            if row > 0 {
-                // evaluate top
-                if self.grid[row - 1][col] == GridSpace::Empty {
-                    outside_nodes.push((row - 1, col));
-                }
-            }
-
-            if row < self.grid.len() - 1 {
-                // evaluate below
-                if self.grid[row + 1][col] == GridSpace::Empty {
-                    outside_nodes.push((row + 1, col));
-                }
+            if self.grid.contains(&outside_node) {
+                continue;
             }
 
-            if col > 0 {
-                // evaluate left
-                if self.grid[row][col - 1] == GridSpace::Empty {
-                    outside_nodes.push((row, col - 1));
-                }
-            }
-
-            if col < self.grid[row].len() - 1 {
-                // evaluate right
-                if self.grid[row][col + 1] == GridSpace::Empty {
-                    outside_nodes.push((row, col + 1));
+            self.grid.insert(outside_node, GridSpace::Outside);
+            outside_count += weight(outside_node);
+            for adjacent in self.grid.get_adjacents(&outside_node) {
+                if area.contains(adjacent) && !self.grid.contains(&adjacent) {
+                    outside_nodes.push(adjacent);
                 }
             }
         }
 
-        let mut dug_nodes = 0;
-        for row in self.grid.iter() {
-            for node in row {
-                if node == &GridSpace::Empty || node == &GridSpace::Wall {
-                    dug_nodes += 1;
-                }
-            }
-        }
-
-        self.pretty_print();
-
-        dug_nodes
+        let area_count: u64 = match &self.compression {
+            Some(compression) => area.points().map(|cell| compression.weight(cell)).sum(),
+            None => area.area() as u64
+        };
+        area_count - outside_count
     }
 
-    #[allow(dead_code)]
+    /// One tile per grid cell, for checking the flood fill against small plans in tests; the puzzle
+    /// itself goes through `build_compressed_grid_from_plan`.
+    #[cfg(test)]
     pub fn build_grid_from_plan(plan: &DigPlan) -> DigGrid {
-        let max_left = plan.get_max_left();
-        let max_down = plan.get_max_down();
-        let max_right = plan.get_max_right();
-        let max_top = plan.get_max_up();
-
-        let mut grid = vec![vec![GridSpace::Empty; (max_left + max_right + 2) as usize]; (max_down + max_top + 2) as usize];
+        let mut grid = SparseGrid::new();
 
         // build the grid
-        let mut cursor_row = (max_top + 1) as usize;
-        let mut cursor_col = (max_left + 1) as usize;
+        let mut cursor = (0, 0);
         for instruction in plan.instructions.iter() {
-            let (vel_row, vel_col) = instruction.direction.as_vector();
             for _ in 0..instruction.length {
-                if cursor_row == 0 && vel_row < 0 {
-                    panic!();
-                }
+                cursor = instruction.direction.step_signed(cursor);
+                grid.insert(cursor, GridSpace::Wall);
+            }
+        }
 
-                if cursor_col == 0 && vel_col < 0 {
-                    panic!();
-                }
-                cursor_row = (cursor_row as i64 + vel_row) as usize;
-                cursor_col = (cursor_col as i64 + vel_col) as usize;
-                grid[cursor_row][cursor_col] = GridSpace::Wall;
+        DigGrid {
+            grid,
+            compression: None
+        }
+    }
+
+    /// Like `build_grid_from_plan`, but with the trench squeezed onto a grid of cells around its corners,
+    /// so plans with trenches millions of tiles long stay small.
+    pub fn build_compressed_grid_from_plan(plan: &DigPlan) -> DigGrid {
+        let mut segments = vec![];
+        let mut cursor = (0, 0);
+        for instruction in plan.instructions.iter() {
+            let (row, col) = instruction.direction.step_signed((0, 0));
+            let next = (cursor.0 + row * instruction.length as i64, cursor.1 + col * instruction.length as i64);
+            segments.push((cursor, next));
+            cursor = next;
+        }
+
+        let compression = CoordinateCompression::from_segments(segments.iter().copied());
+        let mut grid = SparseGrid::new();
+        for (from, to) in segments {
+            for cell in compression.segment_cells(from, to) {
+                grid.insert(cell, GridSpace::Wall);
             }
         }
 
         DigGrid {
-            grid
+            grid,
+            compression: Some(compression)
+        }
+    }
+}
+
+impl Render for DigGrid {
+    fn render_size(&self) -> (usize, usize) {
+        self.grid.bounds().map_or((0, 0), |bounds| ((bounds.max.0 - bounds.min.0 + 1) as usize, (bounds.max.1 - bounds.min.1 + 1) as usize))
+    }
+
+    fn render_cell(&self, point: GridPoint2D) -> Cell {
+        let origin = self.grid.bounds().map_or((0, 0), |bounds| bounds.min);
+        match self.grid.get(&(origin.0 + point.x as i64, origin.1 + point.y as i64)) {
+            Some(GridSpace::Wall) => Cell::new('#', Rgb::new(150, 100, 60)),
+            Some(GridSpace::Outside) => Cell::new('O', Rgb::new(40, 40, 40)),
+            None => Cell::new('.', Rgb::new(90, 70, 50))
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::day18::dig_grid::DigGrid;
+    use crate::problems::day18::dig_plan::DigPlan;
+    use crate::problems::shared::render::Render;
+
+    #[test]
+    fn counts_by_flooding_the_outside() {
+        // starts by heading up and left, away from the positive quadrant
+        let plan = DigPlan::parse("U 2 (#000000)\nL 3 (#000000)\nD 4 (#000000)\nR 3 (#000000)\nU 2 (#000000)", false).unwrap();
+        let mut grid = DigGrid::build_grid_from_plan(&plan);
+        assert_eq!("####\n#..#\n#..#\n#..#\n####\n", grid.canvas().to_text());
+        assert_eq!(20, plan.count_dug_depth());
+        assert_eq!(20, grid.count_inside());
+    }
+
+    #[test]
+    fn counts_compressed_part2_trenches() {
+        let instructions = "
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+        assert_eq!(62, DigGrid::build_compressed_grid_from_plan(&DigPlan::parse(instructions, false).unwrap()).count_inside());
+        assert_eq!(952408144115, DigGrid::build_compressed_grid_from_plan(&DigPlan::parse(instructions, true).unwrap()).count_inside());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+use crate::problems::shared::parse::{Line, ParseError};
 
 pub struct DigInstruction {
     pub direction: Grid2dDirection,
@@ -7,34 +8,38 @@ pub struct DigInstruction {
 }
 
 impl DigInstruction {
-    pub fn parse(instruction: &str, is_reversed: bool) -> Option<DigInstruction> {
-        let mut split = instruction.split(' ');
-        let direction = Grid2dDirection::parse_str(split.next()?)?;
-        let length = split.next()?.parse::<u64>().unwrap();
-        let hex = split.next()?;
+    pub fn parse(instruction: Line, is_reversed: bool) -> Result<DigInstruction, ParseError> {
+        // R 6 (#70c710)
+        let [direction, length, colour] = instruction.fields().collect::<Vec<&str>>()[..] else {
+            return Err(instruction.error("expected a direction, a length and a colour"));
+        };
+        let direction = Grid2dDirection::parse_str(direction).ok_or_else(|| instruction.error(format!("unknown direction {:?}", direction)))?;
+        let length = instruction.with_text(length).parse::<u64>()?;
+        let hex = colour
+            .strip_prefix("(#")
+            .and_then(|c| c.strip_suffix(')'))
+            .filter(|c| c.len() == 6 && c.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| instruction.error(format!("{:?} is not a colour like (#70c710)", colour)))?;
+        let hex_value = |range: std::ops::Range<usize>| u64::from_str_radix(&hex[range], 16).expect("Colour was checked to be hex");
+
         if is_reversed {
-            let hex_length = u64::from_str_radix(&hex[2..=6], 16).ok()?;
-            let hex_direction = u8::from_str_radix(&hex[7..=7], 16).ok()?;
-            let hex_direction = match hex_direction {
+            let hex_direction = match hex_value(5..6) {
                 0 => Grid2dDirection::Right,
                 1 => Grid2dDirection::Down,
                 2 => Grid2dDirection::Left,
                 3 => Grid2dDirection::Up,
-                _ => return None
+                _ => return Err(instruction.error(format!("{:?} does not end in a direction from 0 to 3", colour)))
             };
-            Some(DigInstruction {
+            Ok(DigInstruction {
                 direction: hex_direction,
-                length: hex_length,
+                length: hex_value(0..5),
                 hex_code: [0,0,0]
             })
         } else {
-            let r = u8::from_str_radix(&hex[2..=3], 16).ok()?;
-            let g = u8::from_str_radix(&hex[4..=5], 16).ok()?;
-            let b = u8::from_str_radix(&hex[6..=7], 16).ok()?;
-            Some(DigInstruction {
+            Ok(DigInstruction {
                 direction,
                 length,
-                hex_code: [r,g,b]
+                hex_code: [hex_value(0..2) as u8, hex_value(2..4) as u8, hex_value(4..6) as u8]
             })
         }
 
@@ -45,10 +50,11 @@ impl DigInstruction {
 mod tests {
     use crate::problems::day18::dig_instruction::{DigInstruction};
     use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+    use crate::problems::shared::parse::Line;
 
     #[test]
     fn parses() {
-        let instruction = DigInstruction::parse("D 10 (#218503)", false).unwrap();
+        let instruction = DigInstruction::parse(Line::new(1, "D 10 (#218503)"), false).unwrap();
         assert_eq!(10, instruction.length);
         assert_eq!(Grid2dDirection::Down, instruction.direction);
         assert_eq!([33, 133, 3], instruction.hex_code);
@@ -57,7 +63,7 @@ mod tests {
 
     #[test]
     fn parses_reversed() {
-        let instruction = DigInstruction::parse("D 10 (#70c710)", true).unwrap();
+        let instruction = DigInstruction::parse(Line::new(1, "D 10 (#70c710)"), true).unwrap();
         assert_eq!(461937, instruction.length);
         assert_eq!(Grid2dDirection::Right, instruction.direction);
         assert_eq!([0,0,0], instruction.hex_code);
@@ -1,5 +1,6 @@
 pub mod part1;
 pub mod part2;
+pub mod render;
 mod dig_plan;
 mod dig_instruction;
 mod dig_grid;
\ No newline at end of file
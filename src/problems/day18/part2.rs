@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day18::dig_plan::DigPlan;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
     let plan = DigPlan::parse(&contents, true).unwrap();
-    println!("Part 2: {}", plan.count_dug_depth());
+    plan.count_dug_depth().to_string()
 }
\ No newline at end of file
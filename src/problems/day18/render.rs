@@ -0,0 +1,14 @@
+use std::fs;
+use crate::problems::day18::dig_grid::DigGrid;
+use crate::problems::day18::dig_plan::DigPlan;
+use crate::problems::shared::render::{Canvas, Render};
+
+/// Draws the part 2 lagoon on its compressed grid, with the flooded outside marked, so each cell stands
+/// for a whole band of tiles rather than one.
+pub fn execute(input_path: &std::path::PathBuf) -> Canvas {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let plan = DigPlan::parse(&contents, true).unwrap();
+    let mut grid = DigGrid::build_compressed_grid_from_plan(&plan);
+    grid.count_inside();
+    grid.canvas()
+}
\ No newline at end of file
@@ -1,3 +1,6 @@
+use crate::problems::shared::interval::IntervalBox;
+use crate::problems::shared::parse::{Line, ParseError};
+
 pub struct Part {
     pub x: u64,
     pub m: u64,
@@ -5,52 +8,26 @@ pub struct Part {
     pub s: u64
 }
 
-#[derive(Clone)]
-pub struct PartRange {
-    pub x_min: u64,
-    pub x_max: u64,
-    pub m_min: u64,
-    pub m_max: u64,
-    pub a_min: u64,
-    pub a_max: u64,
-    pub s_min: u64,
-    pub s_max: u64
-}
+/// Every x/m/a/s combination within a hyper-rectangle, one dimension per category in that order.
+pub type PartRange = IntervalBox<4>;
 
-impl PartRange {
-    pub fn new(min: u64, max: u64) -> PartRange {
-        PartRange {
-            x_min: min,
-            x_max: max,
-            m_min: min,
-            m_max: max,
-            a_min: min,
-            a_max: max,
-            s_min: min,
-            s_max: max,
-        }
-    }
+/// A hyper-rectangle holding every category value from `min` to `max` inclusive.
+pub fn full_part_range(min: u64, max: u64) -> PartRange {
+    IntervalBox::new([min..max + 1, min..max + 1, min..max + 1, min..max + 1])
 }
 
 impl Part {
-    pub fn parse(line: &str) -> Option<Part> {
-        //
-        let mut nums = vec![];
-        for value in line.split([',', '=', '}']) {
-            if let Ok(num) = value.parse::<u64>() {
-                nums.push(num);
-            }
-        }
-
-        if nums.len() != 4 {
-            return None;
-        }
+    pub fn parse(line: Line) -> Result<Part, ParseError> {
+        // {x=787,m=2655,a=1222,s=2876}
+        let [x, m, a, s] = line.integers::<u64>()?[..] else {
+            return Err(line.error("a part should have x, m, a and s ratings"));
+        };
 
-        Some(Part {
-            x: nums[0],
-            m: nums[1],
-            a: nums[2],
-            s: nums[3],
+        Ok(Part {
+            x,
+            m,
+            a,
+            s,
         })
     }
 }
@@ -58,10 +35,11 @@ impl Part {
 #[cfg(test)]
 mod tests {
     use crate::problems::day19::part::Part;
+    use crate::problems::shared::parse::Line;
 
     #[test]
     fn parses() {
-        let part = Part::parse("{x=787,m=2655,a=1222,s=2876}").unwrap();
+        let part = Part::parse(Line::new(1, "{x=787,m=2655,a=1222,s=2876}")).unwrap();
         assert_eq!(787, part.x);
         assert_eq!(2655, part.m);
         assert_eq!(1222, part.a);
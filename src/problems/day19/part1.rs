@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day19::rules_engine::RulesEngine;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
     let engine = RulesEngine::parse(&contents).unwrap();
-	println!("Part 1: {}", engine.apply_rules());
+    engine.apply_rules().to_string()
 }
\ No newline at end of file
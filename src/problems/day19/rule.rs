@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use crate::problems::day19::part::{Part, PartRange};
+use crate::problems::shared::parse::{Line, ParseError};
 
 pub enum RuleApplication {
     X(u64, Ordering),
@@ -24,29 +25,14 @@ impl RuleApplication {
         };
     }
 
-    pub fn marshall_part_range(&self, previous_part: &PartRange, range: ((u64, u64), bool)) -> (PartRange, bool) {
-        let ((min, max), result) = range;
-        let mut part = previous_part.clone();
+    /// Which `PartRange` dimension this rule inspects.
+    fn dimension(&self) -> usize {
         match self {
-            RuleApplication::X(_, _) => {
-                part.x_min = min;
-                part.x_max = max;
-            },
-            RuleApplication::M(_, _) => {
-                part.m_min = min;
-                part.m_max = max;
-            },
-            RuleApplication::A(_, _) => {
-                part.a_min = min;
-                part.a_max = max;
-            },
-            RuleApplication::S(_, _) => {
-                part.s_min = min;
-                part.s_max = max;
-            }
+            RuleApplication::X(_, _) => 0,
+            RuleApplication::M(_, _) => 1,
+            RuleApplication::A(_, _) => 2,
+            RuleApplication::S(_, _) => 3
         }
-
-        return (part, result);
     }
 }
 
@@ -56,24 +42,23 @@ pub struct Rule {
 }
 
 impl Rule {
-    pub fn parse(def: &str) -> Option<Rule> {
-        let token = &def[0..=0];
-        if def.len() > 2 && (&def[1..=1] == "<" || &def[1..=1] == ">") {
-            let mut split = def.split([':', def.chars().nth(1)?]);
-            split.next()?; // remove token, we already have it
-            let value = split.next()?.parse::<u64>().ok()?;
-            let label: String = split.next()?.into();
-            let comparator = &def[1..=1];
-            return Some(Rule {
-                applies: Some(RuleApplication::new(token, value, comparator)?),
+    pub fn parse(def: Line) -> Result<Rule, ParseError> {
+        // a<2006:qkq
+        if let Some((condition, label)) = def.text.split_once(':') {
+            let comparator_index = condition.find(['<', '>']).ok_or_else(|| def.error(format!("{:?} has no '<' or '>'", condition)))?;
+            let (token, comparison) = condition.split_at(comparator_index);
+            let (comparator, value) = comparison.split_at(1);
+            let value = def.with_text(value).parse::<u64>()?;
+            Ok(Rule {
+                applies: Some(RuleApplication::new(token, value, comparator).ok_or_else(|| def.error(format!("unknown category {:?}", token)))?),
                 destination: label.into()
-            });
+            })
         } else {
             // this is just a basic label
-            return Some(Rule {
+            Ok(Rule {
                 applies: None,
-                destination: def.into()
-            });
+                destination: def.text.into()
+            })
         }
     }
 
@@ -98,57 +83,26 @@ impl Rule {
         }
     }
 
-    pub fn compare_xmas(val: u64, range_min: u64, range_max: u64, comp: &Ordering) -> Vec<((u64, u64), bool)> {
-        if val < range_min {
-            // entire set is greater than the comparator
-            return vec![((range_min, range_max), comp == &Ordering::Greater)]
-        } else if val > range_max {
-            // entire set is less than the comparator
-            return vec![((range_min, range_max), comp == &Ordering::Less)]
-        } else {
-            // we know we straddle a line
-            // so part of the range is getting true, part is getting false
-            if comp == &Ordering::Less {
-                // < 100
-                // 0..99, 100..=
-                return vec![((range_min, val - 1), true), ((val, range_max), false)];
-            } else {
-                // > 100
-                // 0.100, 101..=
-                return vec![((range_min, val), false), ((val + 1, range_max), true)];
-            }
-        }
-    }
-
     pub fn map_part_range(&self, part_range: &PartRange) -> Vec<(PartRange, bool)> {
-        if let Some(applies) = &self.applies {
-            let result = match applies {
-                RuleApplication::X(val, comp) => {
-                    Rule::compare_xmas(*val, part_range.x_min, part_range.x_max, comp)
-                        .into_iter().map(|r| applies.marshall_part_range(part_range, r))
-                        .collect()
-                },
-                RuleApplication::M(val, comp) => {
-                    Rule::compare_xmas(*val, part_range.m_min, part_range.m_max, comp)
-                        .into_iter().map(|r| applies.marshall_part_range(part_range, r))
-                        .collect()
-                },
-                RuleApplication::A(val, comp) => {
-                    Rule::compare_xmas(*val, part_range.a_min, part_range.a_max, comp)
-                        .into_iter().map(|r| applies.marshall_part_range(part_range, r))
-                        .collect()
-                },
-                RuleApplication::S(val, comp) => {
-                    Rule::compare_xmas(*val, part_range.s_min, part_range.s_max, comp)
-                        .into_iter().map(|r| applies.marshall_part_range(part_range, r))
-                        .collect()
-                }
-            };
-
-            return result;
-        } else {
+        let Some(applies) = &self.applies else {
             return vec![(part_range.clone(), true)];
-        }
+        };
+
+        let (RuleApplication::X(val, comp) | RuleApplication::M(val, comp) | RuleApplication::A(val, comp) | RuleApplication::S(val, comp)) = applies;
+        let split = match comp {
+            // < 100 splits into ..100 and 100..
+            Ordering::Less => {
+                let (below, above) = part_range.split_at(applies.dimension(), *val);
+                [(below, true), (above, false)]
+            },
+            // > 100 splits into ..101 and 101..
+            _ => {
+                let (below, above) = part_range.split_at(applies.dimension(), *val + 1);
+                [(below, false), (above, true)]
+            }
+        };
+
+        split.into_iter().filter_map(|(range, result)| range.map(|r| (r, result))).collect()
     }
 }
 
@@ -158,20 +112,28 @@ pub struct Workflow {
 }
 
 impl Workflow {
-    pub fn parse(line: &str) -> Option<Workflow> {
+    pub fn parse(line: Line) -> Result<Workflow, ParseError> {
         // px{a<2006:qkq,m>2090:A,rfg}
-        let mut line_parts = line.split(['{', '}']);
-        let label = line_parts.next()?;
-        let rules = line_parts.next()?.split(',');
+        let (label, rules) = line.split_once("{")?;
+        let rules = rules.text.strip_suffix('}').ok_or_else(|| line.error("expected the rules to end with '}'"))?;
 
-        let rules = rules.map(|r| Rule::parse(r).unwrap()).collect();
+        let rules = line.with_text(rules).split(",").map(Rule::parse).collect::<Result<Vec<Rule>, ParseError>>()?;
 
-        Some(Workflow {
-            label: label.into(),
+        Ok(Workflow {
+            label: label.text.into(),
             rules
         })
     }
 
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Where each rule sends the parts it matches, in rule order.
+    pub fn destinations(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|r| r.destination.as_str())
+    }
+
     pub fn is_start(&self) -> bool {
         return self.label == "in";
     }
@@ -1,5 +1,7 @@
-use crate::problems::day19::part::{Part, PartRange};
+use crate::problems::day19::part::{full_part_range, Part, PartRange};
 use crate::problems::day19::rule::Workflow;
+use crate::problems::shared::graph::DirectedGraph;
+use crate::problems::shared::parse::{self, ParseError};
 
 pub struct RulesEngine {
     workflows: Vec<Workflow>,
@@ -7,32 +9,28 @@ pub struct RulesEngine {
 }
 
 impl RulesEngine {
-    pub fn parse(contents: &str) -> Option<RulesEngine> {
-        let mut workflows = vec![];
-        let mut parts = vec![];
-
-        let mut found_parts = false;
-        for line in contents.lines() {
-            if line.is_empty() {
-                found_parts = true;
-                continue;
-            }
-
-            if found_parts {
-                let part = Part::parse(line)?;
-                parts.push(part);
-            } else {
-                let workflow = Workflow::parse(line)?;
-                workflows.push(workflow)
-            }
-        }
-
-        Some(RulesEngine {
-            workflows,
-            parts
+    pub fn parse(contents: &str) -> Result<RulesEngine, ParseError> {
+        let blocks = parse::blocks(contents);
+        let (workflows, parts) = match &blocks[..] {
+            [workflows, parts] => (workflows, parts),
+            [workflows] => (workflows, &vec![]),
+            [] => return Err(parse::unexpected_end(contents, "the workflows")),
+            [_, _, extra, ..] => return Err(extra[0].error("expected only a block of workflows and a block of parts"))
+        };
+
+        Ok(RulesEngine {
+            workflows: workflows.iter().map(|line| Workflow::parse(*line)).collect::<Result<Vec<Workflow>, ParseError>>()?,
+            parts: parts.iter().map(|line| Part::parse(*line)).collect::<Result<Vec<Part>, ParseError>>()?
         })
     }
 
+    /// The workflows as a graph, with an edge for every rule that sends parts to another workflow, `A` or `R`.
+    pub fn workflow_graph(&self) -> DirectedGraph<String> {
+        DirectedGraph::from_adjacency(self.workflows.iter().map(|w| {
+            (w.label().to_string(), w.destinations().map(|d| (d.to_string(), ())).collect::<Vec<(String, ())>>())
+        }))
+    }
+
     pub fn apply_rules(&self) -> u64 {
         let mut accepted_parts = vec![];
         let mut rejected_parts = vec![];
@@ -58,36 +56,38 @@ impl RulesEngine {
     }
 
     pub fn apply_rules_for_ranges_and_count_uniques(&self, range_min: u64, range_max: u64) -> u64 {
-        let mut ranges_to_acceptance = vec![];
+        // workflows never loop, so visiting them in topological order means every range
+        // that can reach a workflow has arrived by the time it is processed
+        let graph = self.workflow_graph();
+        let order = graph.topological_order().expect("Workflows should not send parts in a loop");
 
-        let starting_range = PartRange::new(range_min, range_max);
-        let mut ranges_to_consider: Vec<(PartRange, String)> = vec![(starting_range, "in".into())];
+        let mut ranges_at_node: Vec<Vec<PartRange>> = vec![vec![]; graph.node_count()];
+        ranges_at_node[graph.index_of(&"in".to_string()).unwrap()].push(full_part_range(range_min, range_max));
 
-        while let Some((range, destination)) = ranges_to_consider.pop() {
+        let mut ranges_to_acceptance = vec![];
+        for node in order {
+            let ranges = std::mem::take(&mut ranges_at_node[node]);
+            let destination = graph.node(node);
             if destination == "R" {
-                // we can ignore this range
-                // because it only leads us to rejection
+                // we can ignore these ranges
+                // because they only lead us to rejection
                 continue;
             } else if destination == "A" {
-                ranges_to_acceptance.push(range);
-            } else {
-                let workflow = self.workflows.iter().find(|w| w.is_workflow(&destination)).unwrap();
-                for workflow_range in workflow.apply_rules_to_range(&range) {
-                    ranges_to_consider.push(workflow_range);
+                ranges_to_acceptance.extend(ranges);
+                continue;
+            }
+
+            let workflow = self.workflows.iter().find(|w| w.is_workflow(destination)).unwrap();
+            for range in ranges {
+                for (workflow_range, next_destination) in workflow.apply_rules_to_range(&range) {
+                    ranges_at_node[graph.index_of(&next_destination).unwrap()].push(workflow_range);
                 }
             }
         }
 
-        let range_possibilities = ranges_to_acceptance.iter().map(|r| {
-            let x_possible = r.x_max - r.x_min + 1;
-            let m_possible = r.m_max - r.m_min + 1;
-            let a_possible = r.a_max - r.a_min + 1;
-            let s_possible = r.s_max - r.s_min + 1;
-
-            return x_possible * m_possible * a_possible * s_possible;
-        }).sum();
+        let range_possibilities: u128 = ranges_to_acceptance.iter().map(|r| r.volume()).sum();
 
-        return range_possibilities;
+        range_possibilities as u64
     }
 }
 
@@ -140,4 +140,26 @@ hdj{m>838:A,pv}
         let engine = RulesEngine::parse(input).unwrap();
         assert_eq!(167409079868000, engine.apply_rules_for_ranges_and_count_uniques(1, 4000));
     }
+
+    #[test]
+    fn workflows_form_a_dag_from_in() {
+        let input = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}";
+        let graph = RulesEngine::parse(input).unwrap().workflow_graph();
+        assert_eq!(13, graph.node_count());
+
+        let order = graph.topological_order().unwrap();
+        assert_eq!("in", graph.node(order[0]));
+    }
 }
\ No newline at end of file
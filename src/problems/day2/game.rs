@@ -1,87 +1,267 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use crate::problems::shared::parse::{self, Line, ParseError};
+
+/// How many cubes of each colour, whether shown in a round or held in a bag. Colours not listed count as zero.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct CubeSet {
+    counts: BTreeMap<String, usize>
+}
+
+impl CubeSet {
+    /// Reads a list like `3 blue, 4 red`. A colour listed twice keeps its last count.
+    pub fn parse(line: Line) -> Result<CubeSet, ParseError> {
+        let mut counts = BTreeMap::new();
+        for entry in line.split(",") {
+            let (count, colour) = entry.split_once(" ")?;
+            if colour.text.is_empty() || colour.text.contains(char::is_whitespace) {
+                return Err(entry.error(format!("expected a count and one colour, found {:?}", entry.text)));
+            }
+            counts.insert(colour.text.to_string(), count.parse::<usize>()?);
+        }
+
+        Ok(CubeSet {
+            counts
+        })
+    }
+
+    pub fn count(&self, colour: &str) -> usize {
+        self.counts.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, colour: &str, count: usize) {
+        self.counts.insert(colour.to_string(), count);
+    }
+
+    /// The colours listed, even those with no cubes.
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.counts.keys().map(String::as_str)
+    }
+
+    /// Whether there are at least as many cubes of every colour as in `other`.
+    pub fn contains(&self, other: &CubeSet) -> bool {
+        other.counts.iter().all(|(colour, count)| self.count(colour) >= *count)
+    }
+
+    /// The most cubes of each colour in either set.
+    pub fn union(&self, other: &CubeSet) -> CubeSet {
+        let mut union = self.clone();
+        for (colour, count) in other.counts.iter() {
+            union.set(colour, self.count(colour).max(*count));
+        }
+
+        union
+    }
+
+    /// How many cubes there are altogether.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// The counts of `colours` multiplied together, so a colour with no cubes makes the power zero.
+    pub fn power<'a, I>(&self, colours: I) -> usize where I: IntoIterator<Item = &'a str> {
+        colours.into_iter().map(|colour| self.count(colour)).product()
+    }
+}
+
+impl fmt::Display for CubeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts: Vec<String> = self.counts.iter().map(|(colour, count)| format!("{} {}", count, colour)).collect();
+        write!(f, "{}", counts.join(", "))
+    }
+}
+
 pub struct Game {
     pub identifier: usize,
-    pub rounds: Vec<Round>
+    pub rounds: Vec<CubeSet>
 }
 
 impl Game {
-    pub fn parse_game(lines: &str) -> Game {
-        let mut game_iter = lines.split(":");
-        let game_part = game_iter.next().unwrap();
-        let rounds_part = game_iter.next().unwrap();
-
-        Game {
-            identifier: game_part.chars().skip(5).collect::<String>().parse::<usize>().unwrap(),
-            rounds: rounds_part.split(';').map(|r| Round::parse_round(r)).collect::<Vec<Round>>()
-        }
+    pub fn parse_game(line: Line) -> Result<Game, ParseError> {
+        let (identifier, rounds) = line.strip_label("Game")?;
+
+        Ok(Game {
+            identifier: line.with_text(identifier).parse::<usize>()?,
+            rounds: rounds.split(";").map(CubeSet::parse).collect::<Result<Vec<CubeSet>, ParseError>>()?
+        })
     }
 
+    /// The fewest cubes of each colour the bag could have held for this game to be played.
+    pub fn minimum_bag(&self) -> CubeSet {
+        self.rounds.iter().fold(CubeSet::default(), |bag, round| bag.union(round))
+    }
+
+    /// Whether every round could have been drawn from `bag`.
+    pub fn is_feasible(&self, bag: &CubeSet) -> bool {
+        self.rounds.iter().all(|round| bag.contains(round))
+    }
 }
 
-pub struct Round {
-    pub blue_count: usize,
-    pub green_count: usize,
-    pub red_count: usize
+/// Every game in a record, for asking what the bag could have held.
+pub struct Games {
+    games: Vec<Game>
 }
 
-impl Round {
-    pub fn parse_round(round_def: &str) -> Round {
-        let mut red_cubes = 0usize;
-        let mut blue_cubes = 0usize;
-        let mut green_cubes = 0usize;
-        for entry in round_def.split(',') {
-            if entry.ends_with("green") {
-                green_cubes = entry.split(" ").nth(1).unwrap().parse::<usize>().unwrap();
-            } else if entry.ends_with("red") {
-                red_cubes = entry.split(" ").nth(1).unwrap().parse::<usize>().unwrap();
-            } else if entry.ends_with("blue") {
-                blue_cubes = entry.split(" ").nth(1).unwrap().parse::<usize>().unwrap();
-            } else {
-                panic!();
+impl Games {
+    pub fn parse(contents: &str) -> Result<Games, ParseError> {
+        Ok(Games {
+            games: parse::lines(contents).map(Game::parse_game).collect::<Result<Vec<Game>, ParseError>>()?
+        })
+    }
+
+    /// Every colour shown in any game.
+    pub fn colours(&self) -> BTreeSet<&str> {
+        self.games.iter().flat_map(|game| game.rounds.iter().flat_map(CubeSet::colours)).collect()
+    }
+
+    /// The games that could have been played with `bag`.
+    pub fn feasible<'a>(&'a self, bag: &'a CubeSet) -> impl Iterator<Item = &'a Game> + 'a {
+        self.games.iter().filter(|game| game.is_feasible(bag))
+    }
+
+    /// The fewest cubes each game needs, by game identifier.
+    pub fn minimum_bags(&self) -> Vec<(usize, CubeSet)> {
+        self.games.iter().map(|game| (game.identifier, game.minimum_bag())).collect()
+    }
+
+    /// The power of each game's minimum bag, over every colour in the record, so a game that never shows
+    /// one of them has no power.
+    pub fn powers(&self) -> Vec<(usize, usize)> {
+        let colours = self.colours();
+        self.minimum_bags().into_iter().map(|(identifier, bag)| (identifier, bag.power(colours.iter().copied()))).collect()
+    }
+
+    /// The bag with the fewest cubes in total that makes at least `games` games feasible, or `None` if there
+    /// aren't that many games. Every colour but the last is tried at each count some game needs, so this is
+    /// exponential in the number of colours; the last colour is then the `games`th smallest need among the
+    /// games that fit. Ties go to the bag found first, with fewer cubes of the earlier colours.
+    pub fn smallest_bag_for(&self, games: usize) -> Option<CubeSet> {
+        if games > self.games.len() {
+            return None;
+        }
+
+        let colours: Vec<&str> = self.colours().into_iter().collect();
+        if games == 0 {
+            let mut empty = CubeSet::default();
+            colours.iter().for_each(|colour| empty.set(colour, 0));
+            return Some(empty);
+        }
+
+        let needs: Vec<CubeSet> = self.games.iter().map(Game::minimum_bag).collect();
+        let mut best = None;
+        Games::search_bags(&colours, games, needs.iter().collect(), CubeSet::default(), &mut best);
+        best
+    }
+
+    /// Picks a count for the first of `colours`, keeping only the `candidates` whose need fits it.
+    fn search_bags(colours: &[&str], games: usize, candidates: Vec<&CubeSet>, bag: CubeSet, best: &mut Option<CubeSet>) {
+        if candidates.len() < games {
+            return;
+        }
+
+        let Some((colour, rest)) = colours.split_first() else {
+            if best.as_ref().is_none_or(|best| bag.total() < best.total()) {
+                *best = Some(bag);
             }
+            return;
+        };
+
+        let mut counts: Vec<usize> = candidates.iter().map(|need| need.count(colour)).collect();
+        counts.sort_unstable();
+        if rest.is_empty() {
+            // only the last colour left, so take just enough for the cheapest `games` candidates
+            let mut bag = bag;
+            bag.set(colour, counts[games - 1]);
+            Games::search_bags(rest, games, candidates, bag, best);
+            return;
         }
 
-        Round {
-            red_count: red_cubes,
-            blue_count: blue_cubes,
-            green_count: green_cubes
+        counts.dedup();
+        for count in counts {
+            let mut next = bag.clone();
+            next.set(colour, count);
+            let fitting = candidates.iter().copied().filter(|need| need.count(colour) <= count).collect();
+            Games::search_bags(rest, games, fitting, next, best);
         }
     }
 }
 
-
 #[cfg(test)]
 mod tests {
-    use crate::problems::day2::game::{Game};
+    use crate::problems::day2::game::{CubeSet, Game, Games};
+    use crate::problems::shared::parse::Line;
+
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn bag(text: &str) -> CubeSet {
+        CubeSet::parse(Line::new(1, text)).unwrap()
+    }
 
     #[test]
     fn input_parses() {
-        let game = Game::parse_game("Game 1: 1 green, 6 red, 4 blue");
+        let game = Game::parse_game(Line::new(1, "Game 1: 1 green, 6 red, 4 blue")).unwrap();
         assert_eq!(1, game.identifier);
         assert_eq!(1, game.rounds.len());
-        assert_eq!(1, game.rounds[0].green_count);
-        assert_eq!(6, game.rounds[0].red_count);
-        assert_eq!(4, game.rounds[0].blue_count);
+        assert_eq!(1, game.rounds[0].count("green"));
+        assert_eq!(6, game.rounds[0].count("red"));
+        assert_eq!(4, game.rounds[0].count("blue"));
     }
 
     #[test]
     fn input_parse_compexs() {
-        let game = Game::parse_game("Game 1: 1 green, 6 red, 4 blue; 2 blue, 6 green, 7 red; 3 red, 4 blue, 6 green; 3 green; 3 blue, 2 green, 1 red");
+        let game = Game::parse_game(Line::new(1, "Game 1: 1 green, 6 red, 4 blue; 2 blue, 6 green, 7 red; 3 red, 4 blue, 6 green; 3 green; 3 blue, 2 green, 1 red")).unwrap();
         assert_eq!(1, game.identifier);
         assert_eq!(5, game.rounds.len());
-        assert_eq!(1, game.rounds[0].green_count);
-        assert_eq!(6, game.rounds[0].red_count);
-        assert_eq!(4, game.rounds[0].blue_count);
-        assert_eq!(6, game.rounds[1].green_count);
-        assert_eq!(7, game.rounds[1].red_count);
-        assert_eq!(2, game.rounds[1].blue_count);
-        assert_eq!(6, game.rounds[2].green_count);
-        assert_eq!(3, game.rounds[2].red_count);
-        assert_eq!(4, game.rounds[2].blue_count);
-        assert_eq!(3, game.rounds[3].green_count);
-        assert_eq!(0, game.rounds[3].red_count);
-        assert_eq!(0, game.rounds[3].blue_count);
-        assert_eq!(2, game.rounds[4].green_count);
-        assert_eq!(1, game.rounds[4].red_count);
-        assert_eq!(3, game.rounds[4].blue_count);
-    }
-}
\ No newline at end of file
+        assert_eq!(1, game.rounds[0].count("green"));
+        assert_eq!(6, game.rounds[0].count("red"));
+        assert_eq!(4, game.rounds[0].count("blue"));
+        assert_eq!(6, game.rounds[1].count("green"));
+        assert_eq!(7, game.rounds[1].count("red"));
+        assert_eq!(2, game.rounds[1].count("blue"));
+        assert_eq!(6, game.rounds[2].count("green"));
+        assert_eq!(3, game.rounds[2].count("red"));
+        assert_eq!(4, game.rounds[2].count("blue"));
+        assert_eq!(3, game.rounds[3].count("green"));
+        assert_eq!(0, game.rounds[3].count("red"));
+        assert_eq!(0, game.rounds[3].count("blue"));
+        assert_eq!(2, game.rounds[4].count("green"));
+        assert_eq!(1, game.rounds[4].count("red"));
+        assert_eq!(3, game.rounds[4].count("blue"));
+    }
+
+    #[test]
+    fn reports_the_line_of_a_bad_round() {
+        let error = Game::parse_game(Line::new(3, "Game 3: 1 green; 2 pale purple")).err().unwrap();
+        assert_eq!("line 3: expected a count and one colour, found \"2 pale purple\"", error.to_string());
+        let error = Game::parse_game(Line::new(4, "Game 4: some green")).err().unwrap();
+        assert_eq!(4, error.line);
+    }
+
+    #[test]
+    fn accepts_any_colour() {
+        let game = Game::parse_game(Line::new(1, "Game 7: 2 purple, 1 red; 5 purple")).unwrap();
+        assert_eq!(bag("5 purple, 1 red"), game.minimum_bag());
+        assert!(game.is_feasible(&bag("5 purple, 1 red, 9 blue")));
+        assert!(!game.is_feasible(&bag("12 red, 13 green, 14 blue")));
+    }
+
+    #[test]
+    fn answers_bag_queries() {
+        let games = Games::parse(EXAMPLE).unwrap();
+        let standard = bag("12 red, 13 green, 14 blue");
+        assert_eq!(vec![1, 2, 5], games.feasible(&standard).map(|game| game.identifier).collect::<Vec<usize>>());
+        assert_eq!(bag("4 red, 2 green, 6 blue"), games.minimum_bags()[0].1);
+        assert_eq!(2286, games.powers().iter().map(|(_, power)| power).sum::<usize>());
+
+        // games 1, 2 and 5 need at most 6 red, 3 green and 6 blue between them; no three others need fewer
+        assert_eq!(Some(bag("6 red, 3 green, 6 blue")), games.smallest_bag_for(3));
+        assert_eq!(Some(bag("0 red, 0 green, 0 blue")), games.smallest_bag_for(0));
+        assert_eq!(Some(bag("1 red, 3 green, 4 blue")), games.smallest_bag_for(1));
+        assert_eq!(None, games.smallest_bag_for(6));
+        assert_eq!("6 blue, 3 green, 6 red", games.smallest_bag_for(3).unwrap().to_string());
+    }
+}
@@ -0,0 +1,27 @@
+use std::fs;
+use crate::problems::aocday::{DayOptions, OptionsOutput};
+use crate::problems::day2::game::{CubeSet, Games};
+use crate::problems::day2::part1::STANDARD_BAG;
+use crate::problems::shared::parse::Line;
+
+/// Solves part 1 with the `--bag` given instead of the puzzle's, and part 2 as usual. Also suggests the
+/// smallest bag that would make as many games feasible.
+pub fn execute(input_path: &std::path::PathBuf, options: &DayOptions) -> Result<OptionsOutput, String> {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let games = Games::parse(&contents).map_err(|e| e.to_string())?;
+    let bag_text = options.bag.as_deref().unwrap_or(STANDARD_BAG);
+    let bag = CubeSet::parse(Line::new(1, bag_text)).map_err(|e| format!("bad bag {:?}: {}", bag_text, e.message))?;
+
+    let feasible: Vec<usize> = games.feasible(&bag).map(|game| game.identifier).collect();
+    let power: usize = games.powers().iter().map(|(_, power)| power).sum();
+    let mut notes = format!("{} feasible games\n", feasible.len());
+    if let Some(smallest) = games.smallest_bag_for(feasible.len()) {
+        notes.push_str(&format!("Smallest bag for {} games: {}\n", feasible.len(), smallest));
+    }
+
+    Ok(OptionsOutput {
+        part1: feasible.iter().sum::<usize>().to_string(),
+        part2: power.to_string(),
+        notes
+    })
+}
\ No newline at end of file
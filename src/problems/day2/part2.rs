@@ -1,23 +1,8 @@
 use std::fs;
-use crate::problems::day2::game::Game;
+use crate::problems::day2::game::Games;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let mut result = 0;
-    for line in contents.lines().filter(|l| !l.is_empty()) {
-        let game = Game::parse_game(line);
-
-        let mut green_max = 0;
-        let mut red_max = 0;
-        let mut blue_max = 0;
-        for round in game.rounds {
-            green_max = std::cmp::max(round.green_count, green_max);
-            blue_max = std::cmp::max(round.blue_count, blue_max);
-            red_max = std::cmp::max(round.red_count, red_max);
-        }
-
-        let power = green_max * red_max * blue_max;
-        result += power;
-    }
-    println!("Part 2: {}", result);
-}
+    let games = Games::parse(&contents).unwrap();
+    games.powers().iter().map(|(_, power)| power).sum::<usize>().to_string()
+}
\ No newline at end of file
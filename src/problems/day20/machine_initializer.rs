@@ -1,15 +1,17 @@
 use std::collections::VecDeque;
 use crate::problems::day20::processing_module::{ProcessingModule, Pulse};
-use crate::problems::shared::math::lcm;
+use crate::problems::shared::graph::DirectedGraph;
+use crate::problems::shared::math::checked_lcm_all;
+use crate::problems::shared::parse::{self, ParseError};
 
 pub struct MachineInitializer {
     processing_modules: Vec<ProcessingModule>
 }
 
 impl MachineInitializer {
-    pub fn parse(contents: &str) -> Option<MachineInitializer> {
+    pub fn parse(contents: &str) -> Result<MachineInitializer, ParseError> {
         let mut processing_modules = vec![ProcessingModule::build_button()];
-        for line in contents.lines().filter(|l| !l.is_empty()) {
+        for line in parse::lines(contents) {
             let mapped = ProcessingModule::parse(line)?;
             processing_modules.push(mapped);
         }
@@ -25,7 +27,7 @@ impl MachineInitializer {
             }
         }
 
-        Some(MachineInitializer {
+        Ok(MachineInitializer {
             processing_modules
         })
     }
@@ -60,9 +62,20 @@ impl MachineInitializer {
         low_calls * high_calls
     }
 
+    /// The network of modules, with an edge from each module to every destination it pulses.
+    pub fn module_graph(&self) -> DirectedGraph<String> {
+        DirectedGraph::from_adjacency(self.processing_modules.iter().map(|m| {
+            (m.label.clone(), m.get_destinations().iter().map(|d| (d.clone(), ())).collect::<Vec<(String, ())>>())
+        }))
+    }
+
     pub fn get_components_that_point_to(&self, label: &str) -> Vec<String> {
-        let precursor_to_rx: Vec<String> = self.processing_modules.iter().filter(|m| m.get_destinations().contains(&label.to_owned())).map(|m| m.label.clone()).collect();
-        return precursor_to_rx;
+        let graph = self.module_graph();
+        let Some(index) = graph.index_of(&label.to_owned()) else {
+            return vec![];
+        };
+
+        graph.predecessors(index).iter().map(|p| graph.node(*p).clone()).collect()
     }
 
     pub fn get_presses_to_enable_rx(&mut self) -> u64 {
@@ -80,12 +93,8 @@ impl MachineInitializer {
                 let (consumer_label, emitter, signal) = process;
 
                 if components_with_press.iter().all(|(_, p)| p.is_some()) {
-                    let mut result = 1;
-                    for (_, p) in components_with_press {
-                        result = lcm(result, p.unwrap());
-                    }
-
-                    return result as u64;
+                    let presses_per_component = components_with_press.iter().map(|(_, p)| p.unwrap() as u128);
+                    return checked_lcm_all(presses_per_component).expect("Press count should fit in a u128") as u64;
                 }
 
                 if let Some(node) = self.processing_modules.iter_mut().find(|n| n.label == consumer_label) {
@@ -1,3 +1,5 @@
+use crate::problems::shared::parse::{Line, ParseError};
+
 pub enum ProcessingModuleType {
     Button,
 
@@ -38,9 +40,10 @@ impl ProcessingModule {
             inputs: vec![]
         }
     }
-    pub fn parse(line: &str) -> Option<ProcessingModule> {
-        let mut split = line.split(" -> ");
-        let definition: &str = split.next()?;
+    pub fn parse(line: Line) -> Result<ProcessingModule, ParseError> {
+        // %a -> inv, con
+        let (definition, destination_labels) = line.split_once("->")?;
+        let definition: &str = definition.text;
 
         let module_type: ProcessingModuleType;
         let module_label: String;
@@ -48,18 +51,17 @@ impl ProcessingModule {
             module_type = ProcessingModuleType::Broadcast;
             module_label = "broadcaster".into();
         } else {
-            module_type = match &definition[0..=0]  {
-                "%" => ProcessingModuleType::FlipFlop(false),
-                "&" => ProcessingModuleType::Conjunction,
-                _ => { return None; }
+            module_type = match definition.chars().next() {
+                Some('%') => ProcessingModuleType::FlipFlop(false),
+                Some('&') => ProcessingModuleType::Conjunction,
+                _ => { return Err(line.error(format!("unknown module {:?}", definition))); }
             };
 
             module_label = definition[1..].into();
         }
 
-        let destination_labels: &str = split.next()?;
-        let destination_labels = destination_labels.split(',').map(|s| s.trim().into()).collect();
-        Some(ProcessingModule {
+        let destination_labels = destination_labels.split(",").map(|s| s.text.into()).collect();
+        Ok(ProcessingModule {
             label: module_label,
             processing_module_type: module_type,
             destinations: destination_labels,
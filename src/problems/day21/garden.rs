@@ -1,6 +1,13 @@
-use std::collections::{BinaryHeap};
-use crate::problems::shared::grid_2d::{GridPointWithCost, GridTile};
+use std::collections::HashMap;
+use crate::problems::shared::animation::Recorder;
+use crate::problems::shared::box_3d::Rectangle;
+use crate::problems::shared::grid_2d::Grid2D;
 use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::image::Rgb;
+use crate::problems::shared::parse::{self, ParseError};
+use crate::problems::shared::render::{Cell, Overlay, Render};
+use crate::problems::shared::search::dijkstra_distances;
+use crate::problems::shared::tiled_grid::TiledGrid;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum GardenTile {
@@ -10,126 +17,143 @@ enum GardenTile {
 }
 
 impl GardenTile {
-    fn parse(c: char) -> GardenTile {
+    fn parse(c: char) -> Option<GardenTile> {
         match c {
-            '#' => GardenTile::Rock,
-            '.' => GardenTile::GardenPlot,
-            'S' => GardenTile::GardenPlotStartingPosition,
-            _ => panic!()
+            '#' => Some(GardenTile::Rock),
+            '.' => Some(GardenTile::GardenPlot),
+            'S' => Some(GardenTile::GardenPlotStartingPosition),
+            _ => None
         }
     }
 }
 
 pub struct Garden {
-    grid: Vec<Vec<GardenTile>>
+    grid: TiledGrid<GardenTile>
 }
 
+/// Walks longer than this on the tiled garden are extrapolated rather than walked step by step.
+const LONGEST_DIRECT_WALK: u64 = 1000;
+
 impl Garden {
-    pub fn parse(contents: &str) -> Garden {
-        Garden {
-            grid: contents.lines().filter(|l| !l.is_empty()).map(|l| l.chars().map(|c| GardenTile::parse(c)).collect()).collect()
+    pub fn parse(contents: &str) -> Result<Garden, ParseError> {
+        let grid = Grid2D::parse(contents, GardenTile::parse)?;
+        if grid.height() == 0 {
+            return Err(parse::unexpected_end(contents, "the garden"));
         }
+
+        Ok(Garden {
+            grid: TiledGrid::new(grid)
+        })
+    }
+
+    fn get_start_tile(&self) -> (i64, i64) {
+        let start = self.grid.base().find(|tile| *tile == GardenTile::GardenPlotStartingPosition).unwrap();
+        (start.x as i64, start.y as i64)
+    }
+
+    /// The fewest steps to every plot within `max_steps` of the start. Without tiling the walk stays on the base garden.
+    fn walk(&self, max_steps: u64, is_infinite_tiling: bool) -> HashMap<(i64, i64), u64> {
+        self.walk_recorded(max_steps, is_infinite_tiling, &mut Recorder::disabled())
     }
 
-    fn get_start_tile(&self) -> GridPoint2D {
-        for row in 0..self.grid.len() {
-            for col in 0..self.grid[row].len() {
-                if self.grid[row][col] == GardenTile::GardenPlotStartingPosition {
-                    return GridPoint2D::new(row, col);
-                }
-            }
+    /// Like `walk`, offering `recorder` a frame of the plots reachable on the base garden after each step.
+    fn walk_recorded(&self, max_steps: u64, is_infinite_tiling: bool, recorder: &mut Recorder) -> HashMap<(i64, i64), u64> {
+        let base = Rectangle::new((0, 0), (self.grid.base().height() as i64 - 1, self.grid.base().width() as i64 - 1));
+        let distances = dijkstra_distances(self.get_start_tile(), max_steps, |point| {
+            self.grid
+                .get_adjacents(point)
+                .into_iter()
+                .filter(|neighbor| (is_infinite_tiling || base.contains(*neighbor)) && self.grid[*neighbor] != GardenTile::Rock)
+                .map(|neighbor| (neighbor, 1))
+                .collect::<Vec<((i64, i64), u64)>>()
+        });
+
+        let furthest = distances.values().copied().max().unwrap_or(0);
+        for steps in 0..=furthest {
+            recorder.record(|| self.canvas().with_overlay(&Garden::reachable_plots_overlay(&distances, steps)));
         }
 
-        panic!()
+        distances
+    }
+
+    /// Plots the elf can be standing on after exactly `steps`: any plot reached in fewer steps with the same
+    /// parity can be returned to by stepping back and forth.
+    fn count_reachable(distances: &HashMap<(i64, i64), u64>, steps: u64) -> u64 {
+        distances
+            .values()
+            .filter(|distance| **distance <= steps && **distance % 2 == steps % 2)
+            .count() as u64
     }
 
     pub fn count_garden_plots_reachable_in_steps(&self, steps: u64, is_infinite_tiling: bool) -> u64 {
-        let start = self.get_start_tile();
-
-        let mut vertices: BinaryHeap<GridPointWithCost> = BinaryHeap::new();
-        vertices.push(GridPointWithCost::new(start, 0));
-
-        let mut path_records: Vec<Vec<Option<GridPointWithCost>>> = vec![vec![None; self.grid[0].len()]; self.grid.len()];
-        while let Some(vertex) = vertices.pop() {
-            let eligible_neighbors = vertex.point.get_adjacents(self.grid.len(), self.grid[vertex.point.x].len());
-            for neighbor in eligible_neighbors {
-                if self.grid[neighbor.x][neighbor.y] == GardenTile::Rock {
-                    continue; // impassable, don't record it
-                }
-
-                let potential_cost = vertex.cost - 1;
-                if let Some(record) = &mut path_records[neighbor.x][neighbor.y] {
-                    if potential_cost > record.cost {
-                        record.cost = potential_cost;
-                        record.point = vertex.point;
-                    }
-                } else {
-                    path_records[neighbor.x][neighbor.y] = Some(GridPointWithCost::new(neighbor, potential_cost));
-                    vertices.push(GridPointWithCost::new(neighbor, potential_cost));
-                }
-            }
+        if !is_infinite_tiling || steps <= LONGEST_DIRECT_WALK {
+            return Garden::count_reachable(&self.walk(steps, is_infinite_tiling), steps);
         }
 
-        if !is_infinite_tiling {
-            let mut visitable_gardens = 0;
-            for row in 0..path_records.len() {
-                for col in 0..path_records[row].len() {
-                    if let Some(record) = path_records[row][col] {
-                        if (record.cost.abs() as u64) <= steps
-                            && (record.cost.abs() as u64 % 2 == steps % 2) {
-                            visitable_gardens += 1;
-                        }
-                    }
-                }
-            }
-
-            return visitable_gardens;
-        }
+        self.extrapolate_garden_plots_reachable(steps)
+    }
 
-        // I don't like problems that require input analysis and the examples don't line up with the problem
-        // I get it, but that sucked
-        // I just used someone else's (Excellent!) solution.
-        // https://github.com/villuna/aoc23/wiki/A-Geometric-solution-to-advent-of-code-2023,-day-21
-        let even_corners: usize = path_records.iter()
-            .map(|rows|
-                rows
-                    .iter()
-                    .filter(|record| record.is_some_and(|record| record.cost.abs() % 2 == 0 && record.cost.abs() > 65)).count()).sum();
-
-        let odd_corners: usize = path_records.iter()
-            .map(|rows|
-                rows
-                    .iter()
-                    .filter(|record| record.is_some_and(|record| record.cost.abs() % 2 == 1 && record.cost.abs() > 65)).count()).sum();
-
-        let even_full: usize = path_records.iter()
-            .map(|rows|
-                rows
-                    .iter()
-                    .filter(|record| record.is_some_and(|record| record.cost.abs() % 2 == 0)).count()).sum();
-
-        let odd_full: usize = path_records.iter()
-            .map(|rows|
-                rows
-                    .iter()
-                    .filter(|record| record.is_some_and(|record| record.cost.abs() % 2 == 1)).count()).sum();
-
-        let n = 202300;
-        assert_eq!(n, 202300);
-
-        let p2 = ((n+1)*(n+1)) * odd_full + (n*n) * even_full - (n+1) * odd_corners + n * even_corners;
-
-        p2 as u64
+    /// Once the walk spans a few copies of the garden, every further copy crossed adds plots in the same
+    /// pattern, so the count is a quadratic in the number of copies crossed. Walk the first three and extend
+    /// the quadratic through them.
+    ///
+    /// This relies on the garden being square with clear rows and columns through the start and along its
+    /// edges, so the reachable area grows as a diamond. The puzzle input is like this; the example isn't.
+    fn extrapolate_garden_plots_reachable(&self, steps: u64) -> u64 {
+        let size = self.grid.base().height() as u64;
+        assert_eq!(size, self.grid.base().width() as u64, "Only a square garden grows evenly enough to extrapolate");
+
+        let (copies, remainder) = (steps / size, steps % size);
+        let distances = self.walk(remainder + 2 * size, true);
+        let [first, second, third] = [0, 1, 2].map(|n| Garden::count_reachable(&distances, remainder + n * size) as i128);
+
+        // Newton's forward differences through the three samples
+        let n = copies as i128;
+        let reachable = first + n * (second - first) + n * (n - 1) / 2 * (third - 2 * second + first);
+        reachable as u64
+    }
+
+    /// Highlights the plots of the base garden the elf can be standing on after exactly `steps`.
+    pub fn reachable_overlay(&self, steps: u64) -> Overlay {
+        Garden::reachable_plots_overlay(&self.walk(steps, false), steps)
+    }
+
+    /// Offers `recorder` a frame of the plots reachable after each of the first `steps` steps.
+    pub fn record_walk(&self, steps: u64, recorder: &mut Recorder) {
+        self.walk_recorded(steps, false, recorder);
+    }
+
+    fn reachable_plots_overlay(distances: &HashMap<(i64, i64), u64>, steps: u64) -> Overlay {
+        let reachable = distances
+            .iter()
+            .filter(|(point, distance)| point.0 >= 0 && point.1 >= 0 && **distance <= steps && **distance % 2 == steps % 2)
+            .map(|(point, _)| GridPoint2D::new(point.0 as usize, point.1 as usize));
+        Overlay::new(reachable, Rgb::new(120, 220, 80))
+    }
+}
+
+impl Render for Garden {
+    fn render_size(&self) -> (usize, usize) {
+        (self.grid.base().height(), self.grid.base().width())
+    }
+
+    fn render_cell(&self, point: GridPoint2D) -> Cell {
+        match self.grid.base()[point] {
+            GardenTile::GardenPlot => Cell::new('.', Rgb::new(40, 90, 30)),
+            GardenTile::GardenPlotStartingPosition => Cell::new('S', Rgb::new(255, 255, 255)),
+            GardenTile::Rock => Cell::new('#', Rgb::new(120, 120, 120))
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::problems::day21::garden::Garden;
+    use crate::problems::day21::part2::{self, PART2_STEPS};
+    use crate::problems::shared::animation::Recorder;
+    use crate::problems::shared::render::Render;
 
-    #[test]
-    fn part1() {
-        let input = "...........
+    const EXAMPLE: &str = "...........
 .....###.#.
 .###.##..#.
 ..#.#...#..
@@ -140,7 +164,77 @@ mod tests {
 .##.#.####.
 .##..##.##.
 ...........";
-        let garden = Garden::parse(input);
+
+    #[test]
+    fn part1() {
+        let garden = Garden::parse(EXAMPLE).unwrap();
         assert_eq!(16, garden.count_garden_plots_reachable_in_steps(6, false));
+
+        let reachable = "...........
+.....###.#.
+.###.##.O#.
+.O#O#O.O#..
+O.O.#.#.O..
+.##O.O####.
+.##.O#O..#.
+.O.O.O.##..
+.##.#.####.
+.##O.##.##.
+...........
+";
+        assert_eq!(reachable, garden.canvas().with_overlay(&garden.reachable_overlay(6).with_glyph('O')).to_text());
+
+        let mut frames = vec![];
+        garden.record_walk(6, &mut Recorder::new(1, |frame| frames.push(frame)));
+        assert_eq!(7, frames.len());
+        let last = frames[6].clone().with_overlay(&garden.reachable_overlay(6).with_glyph('O'));
+        assert_eq!(reachable, last.to_text());
+    }
+
+    #[test]
+    fn part2_walks_the_tiled_example() {
+        let garden = Garden::parse(EXAMPLE).unwrap();
+        assert_eq!(16, garden.count_garden_plots_reachable_in_steps(6, true));
+        assert_eq!(50, garden.count_garden_plots_reachable_in_steps(10, true));
+        assert_eq!(1594, garden.count_garden_plots_reachable_in_steps(50, true));
+        assert_eq!(6536, garden.count_garden_plots_reachable_in_steps(100, true));
+    }
+
+    const CLEAR_LANES: &str = "...........
+.#.......#.
+...#...#...
+.#.....#.#.
+..#.....#..
+.....S.....
+..#.....#..
+.#.#.....#.
+...#...#...
+.#.......#.
+...........";
+
+    #[test]
+    fn part2_extrapolates_gardens_with_clear_lanes() {
+        let garden = Garden::parse(CLEAR_LANES).unwrap();
+        let steps = 5 + 11 * 7;
+        assert_eq!(
+            Garden::count_reachable(&garden.walk(steps, true), steps),
+            garden.extrapolate_garden_plots_reachable(steps)
+        );
+    }
+
+    #[test]
+    fn part2_walks_the_puzzle_distance() {
+        assert_eq!(26501365, PART2_STEPS);
+        let garden = Garden::parse(CLEAR_LANES).unwrap();
+        assert_eq!(
+            garden.extrapolate_garden_plots_reachable(PART2_STEPS),
+            garden.count_garden_plots_reachable_in_steps(PART2_STEPS, true)
+        );
+
+        // part 2 itself walks that far, rather than part 1's 64 steps
+        let input = std::env::temp_dir().join("aoc_2023_day21_clear_lanes.txt");
+        std::fs::write(&input, CLEAR_LANES).unwrap();
+        assert_eq!(garden.extrapolate_garden_plots_reachable(26501365).to_string(), part2::execute(&input));
+        assert_ne!(garden.count_garden_plots_reachable_in_steps(64, true).to_string(), part2::execute(&input));
     }
 }
\ No newline at end of file
@@ -1,3 +1,5 @@
 pub mod part1;
 pub mod part2;
+pub mod visualize;
+pub mod render;
 mod garden;
\ No newline at end of file
@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day21::garden::Garden;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let garden = Garden::parse(&contents);
-	println!("Part 1: {}", garden.count_garden_plots_reachable_in_steps(64, false));
+    let garden = Garden::parse(&contents).unwrap();
+    garden.count_garden_plots_reachable_in_steps(64, false).to_string()
 }
\ No newline at end of file
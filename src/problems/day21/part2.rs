@@ -1,9 +1,11 @@
 use std::fs;
 use crate::problems::day21::garden::Garden;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+/// How far the elf walks across the infinitely tiled garden in part 2.
+pub const PART2_STEPS: u64 = 26501365;
+
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let garden = Garden::parse(&contents);
-    // compute most plausible steps
-    println!("Part 2: {}", garden.count_garden_plots_reachable_in_steps(64, true));
+    let garden = Garden::parse(&contents).unwrap();
+    garden.count_garden_plots_reachable_in_steps(PART2_STEPS, true).to_string()
 }
\ No newline at end of file
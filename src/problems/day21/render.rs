@@ -0,0 +1,9 @@
+use std::fs;
+use crate::problems::day21::garden::Garden;
+use crate::problems::shared::render::{Canvas, Render};
+
+pub fn execute(input_path: &std::path::PathBuf) -> Canvas {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let garden = Garden::parse(&contents).unwrap();
+    garden.canvas().with_overlay(&garden.reachable_overlay(64))
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+use std::fs;
+use crate::problems::day21::garden::Garden;
+use crate::problems::shared::animation::Recorder;
+
+pub fn execute(input_path: &std::path::PathBuf, recorder: &mut Recorder) {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let garden = Garden::parse(&contents).unwrap();
+    garden.record_walk(64, recorder);
+}
\ No newline at end of file
@@ -1,120 +1,143 @@
-#[derive(Copy, Clone, PartialEq, Eq)]
-struct Block {
-    x1: usize,
-    y1: usize,
-    z1: usize,
-    x2: usize,
-    y2: usize,
-    z2: usize
-}
+use crate::problems::shared::animation::Recorder;
+use crate::problems::shared::box_3d::{Axis, Box3D};
+use crate::problems::shared::graph::DirectedGraph;
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::height_map::HeightMap;
+use crate::problems::shared::image::Rgb;
+use crate::problems::shared::parse::{self, Line, ParseError};
+use crate::problems::shared::render::{Cell, Render};
+use crate::problems::shared::vector_3d::Vector3D;
 
 #[derive(Clone)]
 pub struct BlockTower {
-    blocks: Vec<Block>
+    blocks: Vec<Box3D>,
+    // for each block, the indices of the blocks directly underneath it holding it up
+    supporters: Vec<Vec<usize>>
+}
+
+fn parse_block(line: Line) -> Result<Box3D, ParseError> {
+    // 1,0,1~1,2,1
+    let [x1, y1, z1, x2, y2, z2] = line.integers::<i64>()?[..] else {
+        return Err(line.error("a block should be two points of three coordinates"));
+    };
+    Ok(Box3D::new(Vector3D::new(x1, y1, z1), Vector3D::new(x2, y2, z2)))
+}
+
+/// What a cell of the side view shows, looking along the y axis.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SideViewCell {
+    Air,
+    Block(usize),
+    // more than one block, one behind the other
+    Blocks
+}
+
+/// The tower seen from the side, with x across and z up, drawn the way the puzzle draws it.
+struct SideView {
+    cells: Grid2D<SideViewCell>
 }
 
-impl Block {
-    fn parse(line: &str) -> Block {
-        let mut splitter = line.split([',', '~']);
-        let x1 = splitter.next().unwrap().parse::<usize>().unwrap();
-        let y1 = splitter.next().unwrap().parse::<usize>().unwrap();
-        let z1 = splitter.next().unwrap().parse::<usize>().unwrap();
-        let x2 = splitter.next().unwrap().parse::<usize>().unwrap();
-        let y2 = splitter.next().unwrap().parse::<usize>().unwrap();
-        let z2 = splitter.next().unwrap().parse::<usize>().unwrap();
-
-        assert!(x1 <= x2);
-        assert!(y1 <= y2);
-        assert!(z1 <= z2);
-
-        Block {
-            x1,
-            y1,
-            z1,
-            x2,
-            y2,
-            z2
+impl SideView {
+    /// Draws `blocks` in a view `height` rows by `width` columns, with the ground along the bottom row.
+    fn new(blocks: &[Box3D], height: usize, width: usize) -> SideView {
+        let mut cells = Grid2D::new(height, width, SideViewCell::Air);
+        for (idx, block) in blocks.iter().enumerate() {
+            for (x, z) in block.project(Axis::Y).points() {
+                let cell = &mut cells[GridPoint2D::new(height - 1 - z as usize, x as usize)];
+                *cell = match *cell {
+                    SideViewCell::Air => SideViewCell::Block(idx),
+                    SideViewCell::Block(other) if other == idx => SideViewCell::Block(idx),
+                    _ => SideViewCell::Blocks
+                };
+            }
+        }
+
+        SideView {
+            cells
         }
     }
+}
 
-    fn intersects_z_independent(&self, other: &Block) -> bool {
-        self.x1 <= other.x2 && self.x2 >= other.x1 && self.y1 <= other.y2 && self.y2 >= other.y1
+impl Render for SideView {
+    fn render_size(&self) -> (usize, usize) {
+        (self.cells.height(), self.cells.width())
     }
 
-    fn does_support(&self, other: &Block) -> bool {
-        if self.z2 + 1 == other.z1 {
-            return self.intersects_z_independent(other);
+    fn render_cell(&self, point: GridPoint2D) -> Cell {
+        const BLOCK_COLOURS: [Rgb; 6] = [
+            Rgb::new(230, 90, 70),
+            Rgb::new(240, 180, 60),
+            Rgb::new(120, 200, 90),
+            Rgb::new(70, 170, 220),
+            Rgb::new(150, 110, 220),
+            Rgb::new(220, 110, 180)
+        ];
+
+        if point.x == self.cells.height() - 1 {
+            return Cell::new('-', Rgb::new(120, 100, 80));
         }
 
-        return false;
+        match self.cells[point] {
+            SideViewCell::Air => Cell::new('.', Rgb::new(20, 20, 30)),
+            SideViewCell::Block(idx) => Cell::new((b'A' + (idx % 26) as u8) as char, BLOCK_COLOURS[idx % BLOCK_COLOURS.len()]),
+            SideViewCell::Blocks => Cell::new('?', Rgb::new(200, 200, 200))
+        }
     }
 }
 
 impl BlockTower {
-    pub fn parse(contents: &str) -> BlockTower {
+    pub fn parse(contents: &str) -> Result<BlockTower, ParseError> {
+        BlockTower::parse_recorded(contents, &mut Recorder::disabled())
+    }
+
+    /// Like `parse`, offering `recorder` a side view of the tower each time a block settles.
+    pub fn parse_recorded(contents: &str, recorder: &mut Recorder) -> Result<BlockTower, ParseError> {
         // parse bricks and move as low as possible
         let mut blocks = vec![];
 
-        for line in contents.lines().filter(|l| !l.is_empty()) {
-            blocks.push(Block::parse(line));
+        for line in parse::lines(contents) {
+            blocks.push(parse_block(line)?);
         }
 
-        // now move all blocks down as far as they can
-        blocks.sort_by(|b1, b2| b1.z2.cmp(&b2.z2));
+        // settle the lowest blocks first so everything under a block is already in place
+        blocks.sort_by_key(|b| b.min().z);
 
-        BlockTower::pull_blocks_by_gravity(&mut blocks);
-        BlockTower {
-            blocks
-        }
-    }
+        // blocks only ever fall, so the view that fits them before they settle fits every frame
+        let view_height = blocks.iter().map(|b| b.max().z + 1).max().unwrap_or(1) as usize;
+        let view_width = blocks.iter().map(|b| b.max().x + 1).max().unwrap_or(0) as usize;
 
-    fn pull_blocks_by_gravity(blocks: &mut Vec<Block>) -> u64 {
-        let mut blocks_that_fell = 0;
+        let mut heights = HeightMap::new(0);
+        let mut supporters = vec![];
         for idx in 0..blocks.len() {
-            let mut top_z_collision = 0;
-            for down_idx in (0..idx).rev() {
-                if blocks[idx].intersects_z_independent(&blocks[down_idx]) {
-                    top_z_collision = std::cmp::max(top_z_collision, blocks[down_idx].z2);
-                } // else it falls past
-            }
-
-            if blocks[idx].z1 != top_z_collision + 1 {
-                blocks_that_fell += 1;
-            }
-
-            let height = blocks[idx].z2 - blocks[idx].z1;
-            blocks[idx].z1 = top_z_collision + 1;
-            blocks[idx].z2 = top_z_collision + 1 + height;
+            let block = blocks[idx];
+            let footprint = block.project(Axis::Z);
+            let resting_height = heights.highest_under(&footprint);
+            let settled = block.translate(Vector3D::new(0, 0, resting_height + 1 - block.min().z));
+
+            supporters.push(heights.occupants_at(&footprint, resting_height));
+            heights.place(&settled, idx);
+            blocks[idx] = settled;
+            recorder.record(|| SideView::new(&blocks, view_height, view_width).canvas());
         }
 
-        blocks_that_fell
+        Ok(BlockTower {
+            blocks,
+            supporters
+        })
     }
 
-    fn count_blocks_supporting(&self, block: &Block) -> u64 {
-        let mut supporting = 0;
-        for other_block in self.blocks.iter() {
-            if block != other_block && other_block.does_support(block) {
-                supporting += 1;
-            }
-        }
-
-        supporting
+    fn count_blocks_supporting(&self, block: usize) -> usize {
+        self.supporters[block].len()
     }
 
-    fn get_blocks_supported_by<'a>(&'a self, block: &'a Block) -> Vec<&'a Block> {
-        let mut result = vec![];
-        for other_block in self.blocks.iter() {
-            if block.does_support(other_block) {
-                result.push(other_block);
-            }
-        }
-
-        result
+    fn get_blocks_supported_by(&self, block: usize) -> Vec<usize> {
+        (0..self.blocks.len()).filter(|other| self.supporters[*other].contains(&block)).collect()
     }
 
     pub fn count_bricks_that_can_disintegrate(&self) -> u64 {
         let mut result = 0;
-        for block in self.blocks.iter() {
+        for block in 0..self.blocks.len() {
             let mut can_disintegrate = true;
             for block_supported_by_me in self.get_blocks_supported_by(block) {
                 can_disintegrate &= self.count_blocks_supporting(block_supported_by_me) != 1;
@@ -128,22 +151,41 @@ impl BlockTower {
         result
     }
 
-    pub fn determine_max_fall(&self) -> u64 {
-        let mut fallen = 0;
+    /// The graph of which block rests on which. Node 0 is the ground, and block `n` is node `n + 1`.
+    pub fn support_graph(&self) -> DirectedGraph<Option<usize>> {
+        let mut graph = DirectedGraph::new();
+        let ground = graph.add_node(None);
         for idx in 0..self.blocks.len() {
-            println!("{}/{}", idx, self.blocks.len());
-            let mut cloned = self.blocks.to_vec();
-            cloned.remove(idx);
-            fallen += BlockTower::pull_blocks_by_gravity(&mut cloned);
+            graph.add_node(Some(idx));
+        }
+
+        for (idx, supporters) in self.supporters.iter().enumerate() {
+            if supporters.is_empty() {
+                graph.add_edge(ground, idx + 1, ());
+            }
+
+            for supporter in supporters {
+                graph.add_edge(supporter + 1, idx + 1, ());
+            }
         }
 
-        fallen
+        graph
+    }
+
+    pub fn determine_max_fall(&self) -> u64 {
+        // removing a block drops exactly the blocks it dominates: every other path to the ground goes through it
+        let graph = self.support_graph();
+        let dominators = graph.dominators(0);
+        (1..graph.node_count())
+            .map(|block| dominators.strictly_dominated_count(block) as u64)
+            .sum()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::problems::day22::block_tower::BlockTower;
+    use crate::problems::shared::animation::Recorder;
 
     #[test]
     fn part1() {
@@ -155,7 +197,7 @@ mod tests {
 0,1,6~2,1,6
 1,1,8~1,1,9";
 
-        let block_tower = BlockTower::parse(input);
+        let block_tower = BlockTower::parse(input).unwrap();
 
         assert_eq!(5, block_tower.count_bricks_that_can_disintegrate());
     }
@@ -170,8 +212,36 @@ mod tests {
 0,1,6~2,1,6
 1,1,8~1,1,9";
 
-        let block_tower = BlockTower::parse(input);
+        let block_tower = BlockTower::parse(input).unwrap();
 
         assert_eq!(7, block_tower.determine_max_fall());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn records_blocks_settling() {
+        let input = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+        let mut frames = vec![];
+        BlockTower::parse_recorded(input, &mut Recorder::new(1, |frame| frames.push(frame))).unwrap();
+        assert_eq!(7, frames.len());
+
+        let settled = "...
+...
+...
+.G.
+.G.
+FFF
+D.E
+???
+.A.
+---
+";
+        assert_eq!(settled, frames[6].to_text());
+    }
+}
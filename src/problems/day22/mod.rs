@@ -1,3 +1,4 @@
 pub mod part1;
 pub mod part2;
+pub mod visualize;
 mod block_tower;
\ No newline at end of file
@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day22::block_tower::BlockTower;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let block_tower = BlockTower::parse(&contents);
-    println!("Part 2: {}", block_tower.determine_max_fall());
+    let block_tower = BlockTower::parse(&contents).unwrap();
+    block_tower.determine_max_fall().to_string()
 }
\ No newline at end of file
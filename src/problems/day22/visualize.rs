@@ -0,0 +1,8 @@
+use std::fs;
+use crate::problems::day22::block_tower::BlockTower;
+use crate::problems::shared::animation::Recorder;
+
+pub fn execute(input_path: &std::path::PathBuf, recorder: &mut Recorder) {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    BlockTower::parse_recorded(&contents, recorder).unwrap();
+}
\ No newline at end of file
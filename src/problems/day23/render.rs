@@ -0,0 +1,9 @@
+use std::fs;
+use crate::problems::day23::scenery::ScenicPark;
+use crate::problems::shared::render::{Canvas, Render};
+
+pub fn execute(input_path: &std::path::PathBuf) -> Canvas {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let scenic_park = ScenicPark::parse(&contents, false).unwrap();
+    scenic_park.canvas().with_overlay(&scenic_park.junction_overlay())
+}
\ No newline at end of file
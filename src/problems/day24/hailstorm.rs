@@ -0,0 +1,194 @@
+use crate::problems::shared::parse::{self, Line, ParseError};
+use crate::problems::shared::rational::Rational;
+use crate::problems::shared::vector_3d::Vector3D;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Hailstone {
+    pub position: Vector3D,
+    pub velocity: Vector3D
+}
+
+impl Hailstone {
+    fn parse(line: Line) -> Result<Hailstone, ParseError> {
+        // 19, 13, 30 @ -2,  1, -2
+        let (position, velocity) = line.split_once("@")?;
+        Ok(Hailstone {
+            position: Hailstone::parse_vector(position)?,
+            velocity: Hailstone::parse_vector(velocity)?
+        })
+    }
+
+    fn parse_vector(values: Line) -> Result<Vector3D, ParseError> {
+        let [x, y, z] = values.split(",").map(|v| v.parse::<i64>()).collect::<Result<Vec<i64>, ParseError>>()?[..] else {
+            return Err(values.error(format!("{:?} should have three coordinates", values.text)));
+        };
+
+        Ok(Vector3D::new(x, y, z))
+    }
+
+    /// Where the paths of the two hailstones cross in the X/Y plane, ignoring Z and time.
+    /// `None` if the paths are parallel or only cross in the past of either hailstone.
+    fn find_future_crossing_xy(&self, other: &Hailstone) -> Option<(Rational, Rational)> {
+        let (p1, v1) = (self.position.map(i128::from), self.velocity.map(i128::from));
+        let (p2, v2) = (other.position.map(i128::from), other.velocity.map(i128::from));
+
+        // p1 + t * v1 == p2 + s * v2, solved for t and s by Cramer's rule
+        let determinant = v2.x * v1.y - v1.x * v2.y;
+        if determinant == 0 {
+            return None;
+        }
+
+        let offset = p2 - p1;
+        let t = Rational::new(v2.x * offset.y - v2.y * offset.x, determinant);
+        let s = Rational::new(v1.x * offset.y - v1.y * offset.x, determinant);
+        if t < Rational::ZERO || s < Rational::ZERO {
+            return None;
+        }
+
+        let crossing = p1.map(Rational::from_integer) + v1.map(Rational::from_integer) * t;
+        Some((crossing.x, crossing.y))
+    }
+}
+
+pub struct Hailstorm {
+    hailstones: Vec<Hailstone>
+}
+
+impl Hailstorm {
+    pub fn parse(contents: &str) -> Result<Hailstorm, ParseError> {
+        let mut hailstones = vec![];
+        for line in parse::lines(contents) {
+            hailstones.push(Hailstone::parse(line)?);
+        }
+
+        Ok(Hailstorm {
+            hailstones
+        })
+    }
+
+    /// Counts the pairs of hailstones whose future X/Y paths cross inside the square `min..=max`.
+    pub fn count_future_crossings_within(&self, min: i64, max: i64) -> usize {
+        let area = Rational::from(min)..=Rational::from(max);
+        let mut crossings = 0;
+        for (index, first) in self.hailstones.iter().enumerate() {
+            for second in &self.hailstones[index + 1..] {
+                if let Some((x, y)) = first.find_future_crossing_xy(second) {
+                    if area.contains(&x) && area.contains(&y) {
+                        crossings += 1;
+                    }
+                }
+            }
+        }
+
+        crossings
+    }
+
+    /// Finds the single throw that collides with every hailstone, using exact arithmetic throughout.
+    ///
+    /// Seen from the first hailstone (subtracting its position and velocity from everything) that hailstone
+    /// sits still at the origin, so the rock's path passes through the origin. It also meets a second
+    /// hailstone, so it lies in the plane through the origin that contains that hailstone's path. Any
+    /// two other hailstones hit that plane exactly where and when the rock hits them, which pins down
+    /// the rock's velocity and then its starting position.
+    pub fn find_rock_that_hits_everything(&self) -> Option<Hailstone> {
+        let origin = self.hailstones.first()?;
+        let relative: Vec<(Vector3D<i128>, Vector3D<i128>)> = self.hailstones[1..]
+            .iter()
+            .map(|h| ((h.position - origin.position).map(i128::from), (h.velocity - origin.velocity).map(i128::from)))
+            .collect();
+
+        for (plane_index, (plane_position, plane_velocity)) in relative.iter().enumerate() {
+            let normal = plane_position.cross(plane_velocity);
+            if normal == Vector3D::default() {
+                continue; // this path runs through the origin, so it doesn't define a plane
+            }
+
+            // each other hailstone's collision with the plane, as (time, position)
+            let collisions: Vec<(Rational, Vector3D<Rational>)> = relative
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index != plane_index)
+                .filter_map(|(_, (position, velocity))| {
+                    let approach = velocity.dot(&normal);
+                    if approach == 0 {
+                        return None; // parallel to the plane
+                    }
+
+                    let time = Rational::new(-position.dot(&normal), approach);
+                    Some((time, position.map(Rational::from_integer) + velocity.map(Rational::from_integer) * time))
+                })
+                .collect();
+
+            let Some((first_time, first_hit)) = collisions.first() else {
+                continue;
+            };
+            let Some((second_time, second_hit)) = collisions.iter().find(|(time, _)| time != first_time) else {
+                continue;
+            };
+
+            let velocity = (*second_hit - *first_hit) * (Rational::from_integer(1) / (*second_time - *first_time));
+            let position = *first_hit - velocity * *first_time;
+
+            let to_integer = |value: Rational| value.to_integer().and_then(|v| i64::try_from(v).ok());
+            let (position, velocity) = (position.map(to_integer), velocity.map(to_integer));
+            return Some(Hailstone {
+                position: Vector3D::new(position.x?, position.y?, position.z?) + origin.position,
+                velocity: Vector3D::new(velocity.x?, velocity.y?, velocity.z?) + origin.velocity
+            });
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::day24::hailstorm::{Hailstone, Hailstorm};
+    use crate::problems::shared::vector_3d::Vector3D;
+
+    const INPUT: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn part1() {
+        let hailstorm = Hailstorm::parse(INPUT).unwrap();
+        assert_eq!(2, hailstorm.count_future_crossings_within(7, 27));
+    }
+
+    #[test]
+    fn part2() {
+        let hailstorm = Hailstorm::parse(INPUT).unwrap();
+        let expected = Hailstone {
+            position: Vector3D::new(24, 13, 10),
+            velocity: Vector3D::new(-3, 1, 2)
+        };
+        assert_eq!(Some(expected), hailstorm.find_rock_that_hits_everything());
+    }
+
+    #[test]
+    fn part2_stays_exact_at_puzzle_scale() {
+        let rock = Hailstone {
+            position: Vector3D::new(240_000_000_000_007, 270_000_000_000_003, 190_000_000_000_011),
+            velocity: Vector3D::new(-137, 45, 220)
+        };
+
+        // hailstones that each meet the rock somewhere in its first trillion nanoseconds
+        let hits = [(91_000_000_007, Vector3D::new(21, -300, 17)), (512_345_678_901, Vector3D::new(-75, 64, -180)),
+            (333_333_333_333, Vector3D::new(140, 2, 99)), (780_000_000_123, Vector3D::new(-5, -66, 301))];
+        let hailstones = hits
+            .iter()
+            .map(|(time, velocity)| Hailstone {
+                position: rock.position + rock.velocity * *time - *velocity * *time,
+                velocity: *velocity
+            })
+            .collect();
+
+        let hailstorm = Hailstorm {
+            hailstones
+        };
+        assert_eq!(Some(rock), hailstorm.find_rock_that_hits_everything());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub mod part1;
+pub mod part2;
+mod hailstorm;
\ No newline at end of file
@@ -0,0 +1,8 @@
+use std::fs;
+use crate::problems::day24::hailstorm::Hailstorm;
+
+pub fn execute(input_path: &std::path::PathBuf) -> String {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let hailstorm = Hailstorm::parse(&contents).unwrap();
+    hailstorm.count_future_crossings_within(200000000000000, 400000000000000).to_string()
+}
\ No newline at end of file
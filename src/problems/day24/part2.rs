@@ -0,0 +1,9 @@
+use std::fs;
+use crate::problems::day24::hailstorm::Hailstorm;
+
+pub fn execute(input_path: &std::path::PathBuf) -> String {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let hailstorm = Hailstorm::parse(&contents).unwrap();
+    let rock = hailstorm.find_rock_that_hits_everything().expect("Some throw should hit every hailstone");
+    (rock.position.x + rock.position.y + rock.position.z).to_string()
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub mod part1;
+pub mod part2;
+mod wiring_diagram;
\ No newline at end of file
@@ -0,0 +1,8 @@
+use std::fs;
+use crate::problems::day25::wiring_diagram::WiringDiagram;
+
+pub fn execute(input_path: &std::path::PathBuf) -> String {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let diagram = WiringDiagram::parse(&contents).unwrap();
+    diagram.multiply_group_sizes_after_cutting(3).to_string()
+}
\ No newline at end of file
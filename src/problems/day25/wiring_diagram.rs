@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use crate::problems::shared::parse::{self, ParseError};
+use crate::problems::shared::undirected_graph::UndirectedGraph;
+
+pub struct WiringDiagram {
+    components: UndirectedGraph<String>
+}
+
+impl WiringDiagram {
+    pub fn parse(contents: &str) -> Result<WiringDiagram, ParseError> {
+        // jqt: rhn xhk nvd
+        let mut components = UndirectedGraph::new();
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        for line in parse::lines(contents) {
+            let (component, connections) = line.split_once(":")?;
+            let mut index_of = |label: &str| *indices.entry(label.to_string()).or_insert_with(|| components.add_node(label.to_string()));
+
+            let from = index_of(component.text);
+            let connected: Vec<usize> = connections.fields().map(index_of).collect();
+            for to in connected {
+                components.add_edge(from, to, 1);
+            }
+        }
+
+        Ok(WiringDiagram {
+            components
+        })
+    }
+
+    /// Cuts the `wires_to_cut` wires that split the components into two groups and multiplies the group sizes.
+    pub fn multiply_group_sizes_after_cutting(&self, wires_to_cut: usize) -> usize {
+        let cut = self.components.stoer_wagner().expect("There should be at least two components");
+        assert_eq!(wires_to_cut, cut.cut_edges.len(), "Cutting {} wires should split the components", wires_to_cut);
+
+        let (first, second) = cut.partition_sizes();
+        first * second
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::day25::wiring_diagram::WiringDiagram;
+
+    const INPUT: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    #[test]
+    fn part1() {
+        let diagram = WiringDiagram::parse(INPUT).unwrap();
+        assert_eq!(54, diagram.multiply_group_sizes_after_cutting(3));
+    }
+
+    #[test]
+    fn cuts_the_expected_wires() {
+        let diagram = WiringDiagram::parse(INPUT).unwrap();
+        for cut in [diagram.components.stoer_wagner().unwrap(), diagram.components.karger_stein(20, 25).unwrap()] {
+            let mut wires: Vec<Vec<&str>> = cut.cut_edges
+                .iter()
+                .map(|(a, b)| {
+                    let mut wire = vec![diagram.components.node(*a).as_str(), diagram.components.node(*b).as_str()];
+                    wire.sort();
+                    wire
+                })
+                .collect();
+            wires.sort();
+            assert_eq!(vec![vec!["bvb", "cmg"], vec!["hfx", "pzl"], vec!["jqt", "nvd"]], wires);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::fs;
+use crate::problems::aocday::{DayOptions, OptionsOutput};
+use crate::problems::day3::schematic::EngineSchematic;
+use crate::problems::shared::render::Render;
+
+/// Solves both parts and draws the schematic with `--annotate ansi` or `--annotate html`, adding row and
+/// column numbers with `--rulers`.
+pub fn execute(input_path: &std::path::PathBuf, options: &DayOptions) -> Result<OptionsOutput, String> {
+    let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
+    let schematic = EngineSchematic::parse(&contents).map_err(|e| e.to_string())?;
+
+    let mut canvas = schematic.canvas();
+    if options.rulers {
+        canvas = canvas.with_rulers();
+    }
+
+    let drawing = match options.annotate.as_deref() {
+        None | Some("ansi") => canvas.to_ansi(),
+        Some("html") => canvas.to_html(),
+        Some(other) => return Err(format!("unknown format {:?}, expected ansi or html", other))
+    };
+
+    let part1: u32 = schematic.part_numbers().map(|number| number.value).sum();
+    let part2: u64 = schematic.gear_ratios(Some('*'), 2).iter().map(|(_, ratio)| ratio).sum();
+    Ok(OptionsOutput {
+        part1: part1.to_string(),
+        part2: part2.to_string(),
+        notes: drawing
+    })
+}
\ No newline at end of file
@@ -0,0 +1,261 @@
+use std::ops::Range;
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::image::Rgb;
+use crate::problems::shared::parse::{self, ParseError};
+use crate::problems::shared::render::{Cell, Render};
+
+/// A number written along one row of the schematic.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NumberSpan {
+    pub value: u32,
+    pub row: usize,
+    pub columns: Range<usize>
+}
+
+/// Any character that is neither a digit nor a `.`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Symbol {
+    pub glyph: char,
+    pub position: GridPoint2D
+}
+
+/// What a cell of the schematic holds, by index into the numbers or symbols.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Item {
+    Number(usize),
+    Symbol(usize)
+}
+
+/// The engine schematic, read once into its numbers and symbols and which of them touch, including
+/// diagonally.
+pub struct EngineSchematic {
+    items: Grid2D<Option<Item>>,
+    numbers: Vec<NumberSpan>,
+    symbols: Vec<Symbol>,
+    // for each number, the indices of the symbols touching it, and the same the other way round
+    number_symbols: Vec<Vec<usize>>,
+    symbol_numbers: Vec<Vec<usize>>
+}
+
+impl EngineSchematic {
+    pub fn parse(contents: &str) -> Result<EngineSchematic, ParseError> {
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        let (mut height, mut width) = (0, 0);
+        for (row, line) in parse::lines(contents).enumerate() {
+            height = row + 1;
+            let mut current: Option<(usize, u32)> = None;
+            let chars: Vec<char> = line.text.chars().collect();
+            width = width.max(chars.len());
+            for (col, c) in chars.iter().copied().chain(['.']).enumerate() {
+                if let Some(digit) = c.to_digit(10) {
+                    let (start, value) = current.unwrap_or((col, 0));
+                    let value = value.checked_mul(10).and_then(|v| v.checked_add(digit))
+                        .ok_or_else(|| line.error(format!("the number at column {} is too large", start + 1)))?;
+                    current = Some((start, value));
+                    continue;
+                }
+
+                if let Some((start, value)) = current.take() {
+                    numbers.push(NumberSpan {
+                        value,
+                        row,
+                        columns: start..col
+                    });
+                }
+
+                if c != '.' && col < chars.len() {
+                    symbols.push(Symbol {
+                        glyph: c,
+                        position: GridPoint2D::new(row, col)
+                    });
+                }
+            }
+        }
+
+        let mut items = Grid2D::new(height, width, None);
+        for (idx, number) in numbers.iter().enumerate() {
+            for col in number.columns.clone() {
+                items[GridPoint2D::new(number.row, col)] = Some(Item::Number(idx));
+            }
+        }
+        for (idx, symbol) in symbols.iter().enumerate() {
+            items[symbol.position] = Some(Item::Symbol(idx));
+        }
+
+        let mut number_symbols = vec![vec![]; numbers.len()];
+        let mut symbol_numbers = vec![vec![]; symbols.len()];
+        for (idx, number) in numbers.iter().enumerate() {
+            for row in number.row.saturating_sub(1)..=number.row + 1 {
+                for col in number.columns.start.saturating_sub(1)..=number.columns.end {
+                    if let Some(Some(Item::Symbol(symbol))) = items.get(&GridPoint2D::new(row, col)) {
+                        number_symbols[idx].push(*symbol);
+                        symbol_numbers[*symbol].push(idx);
+                    }
+                }
+            }
+        }
+
+        Ok(EngineSchematic {
+            items,
+            numbers,
+            symbols,
+            number_symbols,
+            symbol_numbers
+        })
+    }
+
+    /// How many rows and columns the schematic has.
+    pub fn size(&self) -> (usize, usize) {
+        (self.items.height(), self.items.width())
+    }
+
+    #[allow(dead_code)]
+    pub fn numbers(&self) -> &[NumberSpan] {
+        &self.numbers
+    }
+
+    #[allow(dead_code)]
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// The symbols touching the number at `number`, by index.
+    #[allow(dead_code)]
+    pub fn symbols_next_to(&self, number: usize) -> &[usize] {
+        &self.number_symbols[number]
+    }
+
+    /// The numbers touching the symbol at `symbol`, by index.
+    #[allow(dead_code)]
+    pub fn numbers_next_to(&self, symbol: usize) -> &[usize] {
+        &self.symbol_numbers[symbol]
+    }
+
+    /// Whether the number at `number` touches a symbol.
+    pub fn is_part_number(&self, number: usize) -> bool {
+        !self.number_symbols[number].is_empty()
+    }
+
+    /// Every number touching at least one symbol, each counted once however many it touches.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &NumberSpan> {
+        self.numbers.iter().enumerate().filter(|(idx, _)| self.is_part_number(*idx)).map(|(_, number)| number)
+    }
+
+    /// Every number touching no symbol at all.
+    #[allow(dead_code)]
+    pub fn loose_numbers(&self) -> impl Iterator<Item = &NumberSpan> {
+        self.numbers.iter().enumerate().filter(|(idx, _)| !self.is_part_number(*idx)).map(|(_, number)| number)
+    }
+
+    /// Whether the symbol at `symbol` is a gear: drawn with `glyph` (or any glyph, if `None`) and touching
+    /// exactly `neighbours` numbers.
+    pub fn is_gear(&self, symbol: usize, glyph: Option<char>, neighbours: usize) -> bool {
+        glyph.is_none_or(|glyph| self.symbols[symbol].glyph == glyph) && self.symbol_numbers[symbol].len() == neighbours
+    }
+
+    /// Every gear, by symbol index, with its ratio: the product of the numbers touching it.
+    pub fn gear_ratios(&self, glyph: Option<char>, neighbours: usize) -> Vec<(usize, u64)> {
+        (0..self.symbols.len())
+            .filter(|symbol| self.is_gear(*symbol, glyph, neighbours))
+            .map(|symbol| (symbol, self.symbol_numbers[symbol].iter().map(|number| self.numbers[*number].value as u64).product()))
+            .collect()
+    }
+}
+
+/// Part numbers, numbers touching no symbol, gears (a `*` touching exactly two numbers) and other symbols
+/// are each drawn in their own colour.
+impl Render for EngineSchematic {
+    fn render_size(&self) -> (usize, usize) {
+        self.size()
+    }
+
+    fn render_cell(&self, point: GridPoint2D) -> Cell {
+        match self.items[point] {
+            None => Cell::new('.', Rgb::new(70, 70, 70)),
+            Some(Item::Number(idx)) => {
+                let number = &self.numbers[idx];
+                // pad back any leading zeros the value lost
+                let digits = format!("{:0width$}", number.value, width = number.columns.len());
+                let digit = digits.chars().nth(point.y - number.columns.start).expect("Every column of a span has a digit");
+                let colour = if self.is_part_number(idx) { Rgb::new(90, 210, 90) } else { Rgb::new(230, 70, 70) };
+                Cell::new(digit, colour)
+            }
+            Some(Item::Symbol(idx)) => {
+                let colour = if self.is_gear(idx, Some('*'), 2) { Rgb::new(250, 210, 50) } else { Rgb::new(80, 170, 240) };
+                Cell::new(self.symbols[idx].glyph, colour)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::problems::day3::schematic::{EngineSchematic, NumberSpan};
+    use crate::problems::shared::grid_point_2d::GridPoint2D;
+    use crate::problems::shared::render::Render;
+
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_input_runs() {
+        let schematic = EngineSchematic::parse(EXAMPLE).unwrap();
+        assert_eq!(4361, schematic.part_numbers().map(|number| number.value).sum::<u32>());
+        assert_eq!(vec![114, 58], schematic.loose_numbers().map(|number| number.value).collect::<Vec<u32>>());
+        assert_eq!((10, 10), schematic.size());
+    }
+
+    #[test]
+    fn finds_gears() {
+        let schematic = EngineSchematic::parse(EXAMPLE).unwrap();
+        assert_eq!(467835, schematic.gear_ratios(Some('*'), 2).iter().map(|(_, ratio)| ratio).sum::<u64>());
+
+        // the `*` next to 617 touches one number, like every symbol but the two gears
+        let lonely: Vec<char> = schematic.gear_ratios(None, 1).iter().map(|(symbol, _)| schematic.symbols()[*symbol].glyph).collect();
+        assert_eq!(vec!['#', '*', '+', '$'], lonely);
+    }
+
+    #[test]
+    fn reads_number_spans() {
+        let schematic = EngineSchematic::parse("..467\n#").unwrap();
+        assert_eq!(&[NumberSpan { value: 467, row: 0, columns: 2..5 }], schematic.numbers());
+        assert_eq!(GridPoint2D::new(1, 0), schematic.symbols()[0].position);
+        assert!(schematic.symbols_next_to(0).is_empty());
+
+        let touching = EngineSchematic::parse("12.\n..$").unwrap();
+        assert_eq!(&[0], touching.symbols_next_to(0));
+        assert_eq!(&[0], touching.numbers_next_to(0));
+
+        assert_eq!(2, EngineSchematic::parse("1\n99999999999").err().unwrap().line);
+    }
+
+    #[test]
+    fn highlights_parts_and_gears() {
+        let schematic = EngineSchematic::parse(EXAMPLE).unwrap();
+        let canvas = schematic.canvas();
+        assert_eq!(format!("{}\n", EXAMPLE), canvas.to_text());
+
+        let colour = |row, col| canvas.cell(&GridPoint2D::new(row, col)).unwrap().colour;
+        let (part, loose, gear, symbol) = (colour(0, 0), colour(0, 5), colour(1, 3), colour(3, 6));
+        assert_eq!(part, colour(9, 1));
+        assert_eq!(loose, colour(5, 7));
+        assert_eq!(gear, colour(8, 5));
+        assert_eq!(symbol, colour(4, 3));
+        let dot = colour(0, 3);
+        assert_eq!(5, HashSet::from([part, loose, gear, symbol, dot]).len());
+
+        let padded = EngineSchematic::parse("007*").unwrap();
+        assert_eq!("007*\n", padded.canvas().to_text());
+    }
+}
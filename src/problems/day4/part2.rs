@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day4::scratchoff::Game;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let game = Game::parse_input(&contents);
-    println!("Part 2: {}", game.compute_total_scorecards_after_winning());
+    let game = Game::parse_input(&contents).unwrap();
+    game.compute_total_scorecards_after_winning().to_string()
 }
\ No newline at end of file
@@ -1,45 +1,28 @@
 use std::collections::HashMap;
+use crate::problems::shared::parse::{self, ParseError};
 
 pub struct Game {
     cards: Vec<Scratchoff>
 }
 
 impl Game {
-    pub fn parse_input(contents: &String) -> Game {
+    pub fn parse_input(contents: &str) -> Result<Game, ParseError> {
         let mut score_cards = vec![];
-        for line in contents.lines().filter(|l| !l.is_empty()) {
-            let mut line_iter = line.split_whitespace();
-            line_iter.next(); // `card`
-            // `1:`
-            let number_def = line_iter.next().unwrap();
-            let card_number = number_def[0..number_def.len() - 1].parse::<u32>().unwrap();
-
-            let mut split_found = false;
-            let mut winning_numbers = vec![];
-            let mut numbers_present = vec![];
-            while let Some(index) = line_iter.next() {
-                if index == "|" {
-                    // now move to matching solution
-                    split_found = true;
-                } else {
-                    if split_found {
-                        numbers_present.push(index.parse::<u32>().unwrap());
-                    } else {
-                        winning_numbers.push(index.parse::<u32>().unwrap());
-                    }
-                }
-            }
+        for line in parse::lines(contents) {
+            // `Card 1: 41 48 83 | 83 86  6`
+            let (card_number, numbers) = line.strip_label("Card")?;
+            let (winning_numbers, numbers_present) = numbers.split_once("|")?;
 
             score_cards.push(Scratchoff {
-                card_number: card_number,
-                winning_numbers: winning_numbers,
-                numbers_present: numbers_present
+                card_number: line.with_text(card_number).parse::<u32>()?,
+                winning_numbers: winning_numbers.integers::<u32>()?,
+                numbers_present: numbers_present.integers::<u32>()?
             })
         }
 
-        Game {
+        Ok(Game {
             cards: score_cards
-        }
+        })
     }
 
     pub fn score_game(&self) -> u32 {
@@ -107,7 +90,7 @@ Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11".into();
-        let game = Game::parse_input(&input);
+        let game = Game::parse_input(&input).unwrap();
         assert_eq!(13, game.score_game())
     }
 
@@ -119,7 +102,7 @@ Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11".into();
-        let game = Game::parse_input(&input);
+        let game = Game::parse_input(&input).unwrap();
         assert_eq!(30, game.compute_total_scorecards_after_winning())
     }
 }
\ No newline at end of file
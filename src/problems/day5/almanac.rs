@@ -1,4 +1,6 @@
 use std::ops::Range;
+use crate::problems::shared::interval::IntervalSet;
+use crate::problems::shared::parse::{self, ParseError};
 use super::almanac_mapping::AlmanacMapping;
 use super::almanac_type::AlmanacType;
 use super::map_range::MapRange;
@@ -10,11 +12,15 @@ pub struct Almanac {
 }
 
 impl Almanac {
-    pub fn parse_input(data: &str) -> Almanac {
-        let mut data_iter = data.lines();
-
-        let seeds: Vec<u64> = data_iter.next().unwrap().split_whitespace().skip(1).map(|seed| seed.parse::<u64>().unwrap()).collect();
-        data_iter.next(); // clear empty line from input...
+    pub fn parse_input(data: &str) -> Result<Almanac, ParseError> {
+        let blocks = parse::blocks(data);
+        let mut blocks_iter = blocks.iter();
+        let seeds_line = blocks_iter.next().and_then(|block| block.first()).ok_or_else(|| parse::unexpected_end(data, "the seeds"))?;
+        let (_, seeds) = seeds_line.strip_label("seeds")?;
+        let seeds: Vec<u64> = seeds.integers()?;
+        if !seeds.len().is_multiple_of(2) {
+            return Err(seeds_line.error("seeds should come in start and length pairs"));
+        }
 
         let mut seed_ranges: Vec<Range<u64>> = vec![];
         for index in (0..seeds.len()).step_by(2) {
@@ -25,59 +31,40 @@ impl Almanac {
         }
 
         let mut ranges = vec![];
-        let mut start_almanac: Option<AlmanacType> = None;
-        let mut end_almanac: Option<AlmanacType> = None;
-        let mut almanac_ranges = vec![];
-        for line in data_iter {
-            if line.is_empty() {
-                if start_almanac.is_some() {
-                    // start a new almanac
-                    let start = start_almanac.unwrap();
-                    let end = end_almanac.unwrap();
-                    ranges.push(AlmanacMapping {
-                        source_type: start.clone(),
-                        destination_type: end.clone(),
-                        ranges: almanac_ranges.clone()
-                    });
-
-                    start_almanac = None;
-                    end_almanac = None;
-                    almanac_ranges.clear();
-                }
-            }
-            else if start_almanac.is_none() {
-                // next line is the start/stop range
-                let mut word_iter = line.split(|c| c == '-' || c == ' ');
-                start_almanac = Some(AlmanacType::parse_from_string(word_iter.next().unwrap()));
-                word_iter.next(); // ignore 'to'
-                end_almanac = Some(AlmanacType::parse_from_string(word_iter.next().unwrap()));
-            } else {
-                // we're finding ranges
-                let data: Vec<u64> = line.split(' ').map(|entry| entry.parse::<u64>().unwrap()).collect();
+        for block in blocks_iter {
+            // `seed-to-soil map:` followed by one `destination source length` line per range
+            let (header, range_lines) = block.split_first().expect("Blocks are never empty");
+            let (source, destination) = header
+                .text
+                .strip_suffix(" map:")
+                .and_then(|names| names.split_once("-to-"))
+                .ok_or_else(|| header.error("expected a `source-to-destination map:` header"))?;
+            let parse_type = |name: &str| AlmanacType::parse_from_string(name).ok_or_else(|| header.error(format!("unknown almanac type {:?}", name)));
+
+            let mut almanac_ranges = vec![];
+            for line in range_lines {
+                let [destination, source, range_length] = line.integers::<u64>()?[..] else {
+                    return Err(line.error("expected a destination, source and length"));
+                };
                 almanac_ranges.push(MapRange {
-                    destination: data[0],
-                    source: data[1],
-                    range_length: data[2]
+                    destination,
+                    source,
+                    range_length
                 })
             }
-        }
 
-        if start_almanac.is_some() {
-            // start a new almanac
-            let start = start_almanac.unwrap();
-            let end = end_almanac.unwrap();
             ranges.push(AlmanacMapping {
-                source_type: start.clone(),
-                destination_type: end.clone(),
-                ranges: almanac_ranges.clone()
+                source_type: parse_type(source)?,
+                destination_type: parse_type(destination)?,
+                ranges: almanac_ranges
             });
         }
 
-        Almanac {
+        Ok(Almanac {
             seeds: seeds,
             seed_ranges: seed_ranges,
             maps: ranges
-        }
+        })
     }
 
     pub fn map_source_to_destination(&self, source: AlmanacType, destination: AlmanacType, source_ids_to_map: &Vec<u64>) -> Vec<u64> {
@@ -89,13 +76,13 @@ impl Almanac {
         return source_ids_to_map.iter().map(|id| map.map_types(id)).collect();
     }
 
-    pub fn map_source_ranges_to_destination(&self, source: AlmanacType, destination: AlmanacType, source_ranges: &Vec<Range<u64>>) -> Vec<Range<u64>> {
+    pub fn map_source_ranges_to_destination(&self, source: AlmanacType, destination: AlmanacType, source_ids: &IntervalSet) -> IntervalSet {
         let map = self.maps
             .iter()
             .find(|m| m.source_type == source && m.destination_type == destination)
             .unwrap_or_else(|| panic!("No map found for {:?}/{:?} combo", source, destination));
 
-        return map.map_ranges_to_destination(source_ranges);
+        map.map_ranges_to_destination(source_ids)
     }
 
     pub fn get_lowest_seed_location_from_seed_list(&self) -> u64 {
@@ -126,39 +113,30 @@ impl Almanac {
     }
 
     pub fn get_lowest_seed_location_from_seed_ranges(&self) -> u64 {
-        let seeds = &self.seed_ranges;
-
-        let possible_values: u64 = seeds.iter().map(|dr| dr.end - dr.start).sum();
+        let seeds = IntervalSet::from_ranges(self.seed_ranges.iter().cloned());
 
         let soil = self
-            .map_source_ranges_to_destination(AlmanacType::Seeds, AlmanacType::Soil, seeds);
-        assert_eq!(possible_values, soil.iter().map(|dr| dr.end - dr.start).sum());
+            .map_source_ranges_to_destination(AlmanacType::Seeds, AlmanacType::Soil, &seeds);
 
         let fertilizer = self
             .map_source_ranges_to_destination(AlmanacType::Soil, AlmanacType::Fertilizer, &soil);
-        assert_eq!(possible_values, fertilizer.iter().map(|dr| dr.end - dr.start).sum());
 
         let water = self
             .map_source_ranges_to_destination(AlmanacType::Fertilizer, AlmanacType::Water, &fertilizer);
-        assert_eq!(possible_values, water.iter().map(|dr| dr.end - dr.start).sum());
 
         let light = self
             .map_source_ranges_to_destination(AlmanacType::Water, AlmanacType::Light, &water);
-        assert_eq!(possible_values, light.iter().map(|dr| dr.end - dr.start).sum());
 
         let temperature = self
             .map_source_ranges_to_destination(AlmanacType::Light, AlmanacType::Temperature, &light);
-        assert_eq!(possible_values, temperature.iter().map(|dr| dr.end - dr.start).sum());
 
         let humidity = self
             .map_source_ranges_to_destination(AlmanacType::Temperature, AlmanacType::Humidity, &temperature);
-        assert_eq!(possible_values, humidity.iter().map(|dr| dr.end - dr.start).sum());
 
         let location = self
             .map_source_ranges_to_destination(AlmanacType::Humidity, AlmanacType::Location, &humidity);
-        assert_eq!(possible_values, location.iter().map(|dr| dr.end - dr.start).sum());
 
-        return location.iter().map(|r| r.start).min().unwrap().clone();
+        location.min().unwrap()
     }
 }
 
@@ -202,7 +180,7 @@ humidity-to-location map:
 60 56 37
 56 93 4";
 
-        let almanac = Almanac::parse_input(&input);
+        let almanac = Almanac::parse_input(&input).unwrap();
         assert_eq!(vec![79, 14, 55, 13], almanac.seeds);
         assert_eq!(2, almanac.seed_ranges.len());
         assert_eq!(79, almanac.seed_ranges[0].start);
@@ -238,7 +216,7 @@ temperature-to-humidity map:
 humidity-to-location map:
 20 10 10";
 
-        let almanac = Almanac::parse_input(&input);
+        let almanac = Almanac::parse_input(&input).unwrap();
         assert_eq!(20, almanac.get_lowest_seed_location_from_seed_ranges());
     }
 }
\ No newline at end of file
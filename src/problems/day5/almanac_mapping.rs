@@ -1,4 +1,4 @@
-use std::ops::Range;
+use crate::problems::shared::interval::IntervalSet;
 use super::almanac_type::AlmanacType;
 use super::map_range::MapRange;
 
@@ -10,51 +10,25 @@ pub struct AlmanacMapping {
 
 impl AlmanacMapping {
     pub fn map_types(&self, id: &u64) -> u64 {
-        if let Some(range) = self.ranges.iter().find(|range| range.does_apply(id)) {
-            return range.apply_mapping(id);
+        match self.ranges.iter().find(|range| range.source_range().contains(id)) {
+            Some(range) => id.checked_add_signed(range.offset()).expect("Mapped ids should stay within u64"),
+            // anything no range covers keeps its id
+            None => *id
         }
-
-        id.clone()
     }
 
-    pub fn map_range_to_destination(&self, range: &Range<u64>) -> Vec<Range<u64>> {
-        let mut source_intersecting_ranges: Vec<&MapRange> = self.ranges.iter().filter(|map_range| map_range.does_source_intersect(range)).collect();
-        source_intersecting_ranges.sort();
-
-        let mut destination_ranges = vec![];
-        let mut remaining_range = Range { start: range.start, end: range.end };
-        for source_intersection in source_intersecting_ranges {
-            assert!(range.end > source_intersection.source);
-            assert!(range.start < source_intersection.source + source_intersection.range_length);
-
-            let intersection_end_bound = source_intersection.source + source_intersection.range_length;
-            if remaining_range.start < source_intersection.source {
-                // this is in destination format already
-                // because there was no intersection before this, source == destination
-                destination_ranges.push(Range { start: remaining_range.start, end: source_intersection.source });
-                remaining_range.start = source_intersection.source;
-            }
-
-            if remaining_range.end <= intersection_end_bound {
-                // the entire remaining range is within this sequence, we are done
-                destination_ranges.push(source_intersection.build_range(remaining_range.start, remaining_range.end));
-                remaining_range.start = remaining_range.end;
-            } else {
-                // we have leftover
-                destination_ranges.push(source_intersection.build_range(remaining_range.start, intersection_end_bound));
-                remaining_range.start = intersection_end_bound;
-            }
+    pub fn map_ranges_to_destination(&self, source_ids: &IntervalSet) -> IntervalSet {
+        let mut unmapped = source_ids.clone();
+        let mut destination_ids = IntervalSet::new();
+        for map_range in &self.ranges {
+            let source_range = IntervalSet::from_range(map_range.source_range());
+            let mapped = unmapped.intersection(&source_range);
+            destination_ids = destination_ids.union(&mapped.shift(map_range.offset()).expect("Mapped ids should stay within u64"));
+            unmapped = unmapped.difference(&source_range);
         }
 
-        if !remaining_range.is_empty() {
-            destination_ranges.push(remaining_range);
-        }
-
-        destination_ranges
-    }
-
-    pub fn map_ranges_to_destination(&self, ranges: &Vec<Range<u64>>) -> Vec<Range<u64>> {
-        ranges.iter().map(|range| self.map_range_to_destination(range)).flatten().collect()
+        // anything no range covers keeps its id
+        destination_ids.union(&unmapped)
     }
 }
 
@@ -63,9 +37,34 @@ mod tests {
     use crate::problems::day5::almanac_mapping::AlmanacMapping;
     use crate::problems::day5::almanac_type::AlmanacType;
     use crate::problems::day5::map_range::MapRange;
+    use crate::problems::shared::interval::IntervalSet;
+
+    #[test]
+    fn map_types_maps() {
+        let mapping = AlmanacMapping {
+            source_type: AlmanacType::Seeds,
+            destination_type: AlmanacType::Soil,
+            ranges: vec![MapRange {
+                source: 98,
+                destination: 50,
+                range_length: 2
+            }, MapRange {
+                source: 50,
+                destination: 52,
+                range_length: 48
+            }]
+        };
+
+        assert_eq!(50, mapping.map_types(&98));
+        assert_eq!(51, mapping.map_types(&99));
+        assert_eq!(52, mapping.map_types(&50));
+        assert_eq!(99, mapping.map_types(&97));
+        assert_eq!(49, mapping.map_types(&49));
+        assert_eq!(100, mapping.map_types(&100));
+    }
 
     #[test]
-    fn map_range_to_destination_maps() {
+    fn map_ranges_to_destination_maps() {
         let mapping = AlmanacMapping {
             source_type: AlmanacType::Fertilizer,
             destination_type: AlmanacType::Fertilizer,
@@ -76,11 +75,11 @@ mod tests {
             }]
         };
 
-        assert_eq!(vec![10..15], mapping.map_range_to_destination(&(5..10)));
-        assert_eq!(vec![0..5], mapping.map_range_to_destination(&(0..5)));
-        assert_eq!(vec![10..15], mapping.map_range_to_destination(&(10..15)));
-        assert_eq!(vec![0..5, 10..12], mapping.map_range_to_destination(&(0..7)));
-        assert_eq!(vec![10..15, 10..15], mapping.map_range_to_destination(&(5..15)));
+        assert_eq!(IntervalSet::from_range(10..15), mapping.map_ranges_to_destination(&IntervalSet::from_range(5..10)));
+        assert_eq!(IntervalSet::from_range(0..5), mapping.map_ranges_to_destination(&IntervalSet::from_range(0..5)));
+        assert_eq!(IntervalSet::from_range(10..15), mapping.map_ranges_to_destination(&IntervalSet::from_range(10..15)));
+        assert_eq!(IntervalSet::from_ranges([0..5, 10..12]), mapping.map_ranges_to_destination(&IntervalSet::from_range(0..7)));
+        assert_eq!(IntervalSet::from_range(10..15), mapping.map_ranges_to_destination(&IntervalSet::from_range(5..15)));
     }
 
     #[test]
@@ -99,9 +98,9 @@ mod tests {
             }]
         };
 
-        assert_eq!(vec![10..15], mapping.map_range_to_destination(&(10..15)));
-        assert_eq!(vec![0..5], mapping.map_range_to_destination(&(5..10)));
-        assert_eq!(vec![10..15], mapping.map_range_to_destination(&(15..20)));
-        assert_eq!(vec![0..5, 0..5, 10..15, 10..15, 20..25], mapping.map_range_to_destination(&(0..25)));
+        assert_eq!(IntervalSet::from_range(10..15), mapping.map_ranges_to_destination(&IntervalSet::from_range(10..15)));
+        assert_eq!(IntervalSet::from_range(0..5), mapping.map_ranges_to_destination(&IntervalSet::from_range(5..10)));
+        assert_eq!(IntervalSet::from_range(10..15), mapping.map_ranges_to_destination(&IntervalSet::from_range(15..20)));
+        assert_eq!(IntervalSet::from_ranges([0..5, 10..15, 20..25]), mapping.map_ranges_to_destination(&IntervalSet::from_range(0..25)));
     }
 }
\ No newline at end of file
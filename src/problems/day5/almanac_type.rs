@@ -12,17 +12,17 @@ pub enum AlmanacType {
 }
 
 impl AlmanacType {
-    pub fn parse_from_string(almanac: &str) -> AlmanacType {
+    pub fn parse_from_string(almanac: &str) -> Option<AlmanacType> {
         match almanac {
-            "seed" => AlmanacType::Seeds,
-            "soil" => AlmanacType::Soil,
-            "fertilizer" => AlmanacType::Fertilizer,
-            "water" => AlmanacType::Water,
-            "light" => AlmanacType::Light,
-            "temperature" => AlmanacType::Temperature,
-            "humidity" => AlmanacType::Humidity,
-            "location" => AlmanacType::Location,
-            val => panic!("Unsupported Value for Almanac: {}", val)
+            "seed" => Some(AlmanacType::Seeds),
+            "soil" => Some(AlmanacType::Soil),
+            "fertilizer" => Some(AlmanacType::Fertilizer),
+            "water" => Some(AlmanacType::Water),
+            "light" => Some(AlmanacType::Light),
+            "temperature" => Some(AlmanacType::Temperature),
+            "humidity" => Some(AlmanacType::Humidity),
+            "location" => Some(AlmanacType::Location),
+            _ => None
         }
     }
 }
\ No newline at end of file
@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day5::almanac::Almanac;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let almanac = Almanac::parse_input(&contents);
-    println!("Part 2: {}", almanac.get_lowest_seed_location_from_seed_ranges());
+    let almanac = Almanac::parse_input(&contents).unwrap();
+    almanac.get_lowest_seed_location_from_seed_ranges().to_string()
 }
@@ -1,3 +1,5 @@
+use crate::problems::shared::parse::{self, Line, ParseError};
+
 pub struct Race {
     series: Vec<Series>
 }
@@ -8,33 +10,42 @@ struct Series {
 }
 
 impl Race {
-    pub fn parse(sheet: &str) -> Race {
-        let mut lines = sheet.lines().filter(|l| !l.is_empty());
-        let mut times = lines.next().unwrap().split_whitespace();
-        let mut distances = lines.next().unwrap().split_whitespace();
-        times.next().unwrap();
-        distances.next().unwrap();
-
-        let series = times.zip(distances).map(|(time, distance)| Series {
-            series_duration_ms: time.parse().unwrap(),
-            current_distance_record_ms: distance.parse().unwrap()
+    pub fn parse(sheet: &str) -> Result<Race, ParseError> {
+        let (times_row, distances_row) = Race::parse_rows(sheet)?;
+        let times = times_row.integers::<u64>()?;
+        let distances = distances_row.integers::<u64>()?;
+        if times.len() != distances.len() {
+            return Err(distances_row.error(format!("expected {} distances to match the times", times.len())));
+        }
+
+        let series = times.into_iter().zip(distances).map(|(time, distance)| Series {
+            series_duration_ms: time,
+            current_distance_record_ms: distance
         }).collect();
-        Race {
+        Ok(Race {
             series: series
-        }
+        })
     }
 
-    pub fn parse_as_single(sheet: &str) -> Race {
-        let mut lines = sheet.lines().filter(|l| !l.is_empty());
-        let time = lines.next().unwrap().split_whitespace().skip(1).collect::<String>().parse::<u64>().unwrap();
-        let distance = lines.next().unwrap().split_whitespace().skip(1).collect::<String>().parse::<u64>().unwrap();
+    pub fn parse_as_single(sheet: &str) -> Result<Race, ParseError> {
+        let (time, distance) = Race::parse_rows(sheet)?;
+        // the spaces between the numbers are only kerning
+        let single = |row: Line| row.with_text(&row.fields().collect::<String>()).parse::<u64>();
 
-        Race {
+        Ok(Race {
             series: vec![Series {
-                series_duration_ms: time,
-                current_distance_record_ms: distance
+                series_duration_ms: single(time)?,
+                current_distance_record_ms: single(distance)?
             }]
-        }
+        })
+    }
+
+    /// The `Time:` and `Distance:` rows with their labels removed.
+    fn parse_rows(sheet: &str) -> Result<(Line<'_>, Line<'_>), ParseError> {
+        let mut lines = parse::lines(sheet);
+        let (_, times) = lines.next().ok_or_else(|| parse::unexpected_end(sheet, "the times"))?.strip_label("Time")?;
+        let (_, distances) = lines.next().ok_or_else(|| parse::unexpected_end(sheet, "the distances"))?.strip_label("Distance")?;
+        Ok((times, distances))
     }
 
     pub fn get_number_of_winning_moves(&self) -> usize {
@@ -78,7 +89,7 @@ mod tests {
         let input = "Time:      7  15   30
 Distance:  9  40  200";
 
-        let race = Race::parse(input);
+        let race = Race::parse(input).unwrap();
         assert_eq!(288, race.get_number_of_winning_moves())
     }
 
@@ -87,7 +98,7 @@ Distance:  9  40  200";
         let input = "Time:      7  15   30
 Distance:  9  40  200";
 
-        let race = Race::parse_as_single(input);
+        let race = Race::parse_as_single(input).unwrap();
         assert_eq!(71503, race.get_number_of_winning_moves())
     }
 }
\ No newline at end of file
@@ -37,22 +37,22 @@ impl CamelCard {
             CamelCard::Joker => 1,
         }
     }
-    pub fn from_char(c: char, jokers_wild: bool) -> CamelCard {
+    pub fn from_char(c: char, jokers_wild: bool) -> Option<CamelCard> {
         match c {
-            'A' => CamelCard::Ace,
-            'K' => CamelCard::King,
-            'Q' => CamelCard::Queen,
-            'J' => if jokers_wild { CamelCard::Joker } else { CamelCard::Jack },
-            'T' => CamelCard::Ten,
-            '9' => CamelCard::Nine,
-            '8' => CamelCard::Eight,
-            '7' => CamelCard::Seven,
-            '6' => CamelCard::Six,
-            '5' => CamelCard::Five,
-            '4' => CamelCard::Four,
-            '3' => CamelCard::Three,
-            '2' => CamelCard::Two,
-            unknown => panic!("Unknown input for card: {}", unknown)
+            'A' => Some(CamelCard::Ace),
+            'K' => Some(CamelCard::King),
+            'Q' => Some(CamelCard::Queen),
+            'J' => Some(if jokers_wild { CamelCard::Joker } else { CamelCard::Jack }),
+            'T' => Some(CamelCard::Ten),
+            '9' => Some(CamelCard::Nine),
+            '8' => Some(CamelCard::Eight),
+            '7' => Some(CamelCard::Seven),
+            '6' => Some(CamelCard::Six),
+            '5' => Some(CamelCard::Five),
+            '4' => Some(CamelCard::Four),
+            '3' => Some(CamelCard::Three),
+            '2' => Some(CamelCard::Two),
+            _ => None
         }
     }
 }
@@ -1,14 +1,15 @@
 use crate::problems::day7::camel_hand::Hand;
+use crate::problems::shared::parse::{self, ParseError};
 
 pub struct CamelGame {
     hands: Vec<Hand>
 }
 
 impl CamelGame {
-    pub fn parse(input: &str, jokers_wild: bool) -> CamelGame {
-        CamelGame {
-            hands: input.lines().filter(|l| !l.is_empty()).map(|l| Hand::parse_input(l, jokers_wild)).collect()
-        }
+    pub fn parse(input: &str, jokers_wild: bool) -> Result<CamelGame, ParseError> {
+        Ok(CamelGame {
+            hands: parse::lines(input).map(|l| Hand::parse_input(l, jokers_wild)).collect::<Result<Vec<Hand>, ParseError>>()?
+        })
     }
 
     pub fn compute_game_score(&self) -> u64 {
@@ -31,7 +32,7 @@ KK677 28
 KTJJT 220
 QQQJA 483";
 
-        let game = CamelGame::parse(input, false);
+        let game = CamelGame::parse(input, false).unwrap();
 
         assert_eq!(6440, game.compute_game_score());
     }
@@ -44,7 +45,7 @@ KK677 28
 KTJJT 220
 QQQJA 483";
 
-        let game = CamelGame::parse(input, true);
+        let game = CamelGame::parse(input, true).unwrap();
         assert_eq!(5905, game.compute_game_score());
     }
 }
\ No newline at end of file
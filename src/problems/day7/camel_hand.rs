@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 use crate::problems::day7::camel_cards::CamelCard;
 use crate::problems::day7::hand_type::HandType;
+use crate::problems::shared::parse::{Line, ParseError};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Hand {
@@ -10,16 +11,20 @@ pub struct Hand {
 }
 
 impl Hand {
-    pub fn parse_input(hand: &str, jokers_wild: bool) -> Hand {
-        let mut lines = hand.split(' ');
-        let mut cards = lines.next().unwrap().chars().map(|c| CamelCard::from_char(c, jokers_wild));
-        let cards = [cards.next().unwrap(), cards.next().unwrap(), cards.next().unwrap(), cards.next().unwrap(), cards.next().unwrap()];
+    pub fn parse_input(line: Line, jokers_wild: bool) -> Result<Hand, ParseError> {
+        let (cards, bid) = line.split_once(" ")?;
+        let cards = cards
+            .text
+            .chars()
+            .map(|c| CamelCard::from_char(c, jokers_wild).ok_or_else(|| line.error(format!("unknown card {:?}", c))))
+            .collect::<Result<Vec<CamelCard>, ParseError>>()?;
+        let cards: [CamelCard; 5] = cards.try_into().map_err(|_| line.error("a hand should have five cards"))?;
         let hand_type = HandType::parse_type(cards);
-        Hand {
+        Ok(Hand {
             cards: cards,
             parsed_hand_type: hand_type,
-            bid: lines.next().unwrap().parse().unwrap()
-        }
+            bid: bid.parse()?
+        })
     }
 }
 
@@ -66,12 +71,23 @@ impl Eq for Hand {}
 #[cfg(test)]
 mod tests {
     use crate::problems::day7::camel_hand::Hand;
+    use crate::problems::shared::parse::Line;
+
+    fn hand(text: &str) -> Hand {
+        Hand::parse_input(Line::new(1, text), false).unwrap()
+    }
 
     #[test]
     fn orders_correctly() {
-        assert!(Hand::parse_input("AAAAA 1", false) == Hand::parse_input("AAAAA 1", false));
-        assert!(Hand::parse_input("AAAAJ 1", false) < Hand::parse_input("AAAAA 1", false));
-        assert!(Hand::parse_input("AAAAA 1", false) > Hand::parse_input("AAAAJ 1", false));
-        assert!(Hand::parse_input("AAAAA 1", false) > Hand::parse_input("AAAJJ 1", false));
+        assert!(hand("AAAAA 1") == hand("AAAAA 1"));
+        assert!(hand("AAAAJ 1") < hand("AAAAA 1"));
+        assert!(hand("AAAAA 1") > hand("AAAAJ 1"));
+        assert!(hand("AAAAA 1") > hand("AAAJJ 1"));
+    }
+
+    #[test]
+    fn rejects_short_hands() {
+        let error = Hand::parse_input(Line::new(4, "AAAA 1"), false).err().unwrap();
+        assert_eq!("line 4: a hand should have five cards", error.to_string());
     }
 }
\ No newline at end of file
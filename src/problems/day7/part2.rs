@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day7::camel_game::CamelGame;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let camel_game = CamelGame::parse(&contents, true);
-    println!("Part 1: {}", camel_game.compute_game_score());
+    let camel_game = CamelGame::parse(&contents, true).unwrap();
+    camel_game.compute_game_score().to_string()
 }
\ No newline at end of file
@@ -4,11 +4,11 @@ pub enum CamelDirection {
 }
 
 impl CamelDirection {
-    pub fn parse(c: char) -> CamelDirection {
+    pub fn parse(c: char) -> Option<CamelDirection> {
         match c {
-            'L' => CamelDirection::Left,
-            'R' => CamelDirection::Right,
-            _ => panic!("invalid direction"),
+            'L' => Some(CamelDirection::Left),
+            'R' => Some(CamelDirection::Right),
+            _ => None,
         }
     }
 }
\ No newline at end of file
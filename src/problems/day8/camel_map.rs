@@ -1,5 +1,7 @@
 use crate::problems::day8::camel_direction::CamelDirection;
 use crate::problems::day8::desert_node::DesertNode;
+use crate::problems::shared::math::crt;
+use crate::problems::shared::parse::{self, ParseError};
 
 pub struct CamelMap {
     directions: Vec<CamelDirection>,
@@ -7,15 +9,20 @@ pub struct CamelMap {
 }
 
 impl CamelMap {
-    pub fn parse(map_data: &str) -> CamelMap {
-        let mut lines = map_data.lines().filter(|l| !l.is_empty());
-        let directions: Vec<CamelDirection> = lines.next().unwrap().chars().map(CamelDirection::parse).collect();
-        let nodes: Vec<DesertNode> = lines.map(DesertNode::parse).collect();
-
-        CamelMap {
+    pub fn parse(map_data: &str) -> Result<CamelMap, ParseError> {
+        let mut lines = parse::lines(map_data);
+        let direction_line = lines.next().ok_or_else(|| parse::unexpected_end(map_data, "the directions"))?;
+        let directions: Vec<CamelDirection> = direction_line
+            .text
+            .chars()
+            .map(|c| CamelDirection::parse(c).ok_or_else(|| direction_line.error(format!("unknown direction {:?}", c))))
+            .collect::<Result<Vec<CamelDirection>, ParseError>>()?;
+        let nodes: Vec<DesertNode> = lines.map(DesertNode::parse).collect::<Result<Vec<DesertNode>, ParseError>>()?;
+
+        Ok(CamelMap {
             directions,
             nodes
-        }
+        })
     }
 
     pub fn get_steps_to_zzz(&self) -> u32 {
@@ -40,49 +47,49 @@ impl CamelMap {
         steps
     }
 
-    pub fn count_steps_to_end_for_node(&self, node: &DesertNode) -> u64 {
+    fn next_node(&self, node: &DesertNode, direction_index: usize) -> &DesertNode {
+        match self.directions[direction_index] {
+            CamelDirection::Left => self.nodes.iter().find(|n| node.left_id == n.id).unwrap(),
+            CamelDirection::Right => self.nodes.iter().find(|n| node.right_id == n.id).unwrap()
+        }
+    }
+
+    /// Returns the step a ghost starting at `node` first reaches a destination, and how many steps
+    /// later it reaches one again. Like the puzzle inputs, this assumes each ghost loops through exactly
+    /// one destination.
+    fn find_ghost_cycle(&self, node: &DesertNode) -> (u64, u64) {
         let mut current_node = node;
         let mut steps = 0;
-        let mut direction_index = 0;
-        while !current_node.is_destination_for_ghosts() {
-            let direction = &self.directions[direction_index];
-            match direction {
-                CamelDirection::Left => {
-                    current_node = self.nodes.iter().find(|n| current_node.left_id == n.id).unwrap();
-                },
-                CamelDirection::Right => {
-                    current_node = self.nodes.iter().find(|n| current_node.right_id == n.id).unwrap();
+        let mut first_arrival = None;
+        loop {
+            current_node = self.next_node(current_node, steps as usize % self.directions.len());
+            steps += 1;
+            if current_node.is_destination_for_ghosts() {
+                match first_arrival {
+                    Some(first) => return (first, steps - first),
+                    None => first_arrival = Some(steps)
                 }
             }
-
-            direction_index = (direction_index + 1) % self.directions.len();
-            steps += 1;
         }
-
-        steps
     }
 
     pub fn get_steps_to_zzz_ghost_mode(&self) -> u64 {
-        // https://en.wikipedia.org/wiki/Chinese_remainder_theorem for alternative approach from reddit
-        // https://en.wikipedia.org/wiki/Least_common_multiple for LCM
-        // https://en.wikipedia.org/wiki/Euclidean_algorithm for GCD
-        let current_nodes: Vec<&DesertNode> = self.nodes.iter().filter(|n| n.is_start_for_ghosts()).collect();
-        let step_counts_for_nodes: Vec<u64> = current_nodes.iter().map(|cn| self.count_steps_to_end_for_node(cn)).collect();
-        println!("{:?}", step_counts_for_nodes);
-        // 277 nodes
-        // upper bound is 16,617,338,012,572,747,975
-        // 16897, 16343, 21883, 13019, 14681, 20221
-
-        // 61, 59, 79, 47, 53, 73
-        //
-
-        // todo -- write algorithm that computes the LCM between a set of numbers
-        // i used wolfram to do it after getting the cycle counts
-        // i computed upper bound... but reddit helped point out that all of these are multiples of the direction input
-            // (Which kinda feels bad, I would not have sus'd that out myself)
-        // math analysis problems are boring (to non math people like me)
-
-        return step_counts_for_nodes.iter().product::<u64>() * self.directions.len() as u64;
+        // https://en.wikipedia.org/wiki/Chinese_remainder_theorem
+        // every ghost is at a destination on the steps t with t ≡ first (mod period).
+        // on the puzzle inputs first == period, so this collapses to the lcm of the periods.
+        let cycles: Vec<(u64, u64)> = self.nodes
+            .iter()
+            .filter(|n| n.is_start_for_ghosts())
+            .map(|n| self.find_ghost_cycle(n))
+            .collect();
+
+        let (solution, modulus) = crt(cycles.iter().map(|(first, period)| (*first as i128, *period as i128)))
+            .expect("Ghosts should all reach a destination on the same step");
+
+        // the congruences only hold once every ghost has entered its loop
+        let latest_first_arrival = cycles.iter().map(|(first, _)| *first as i128).max().unwrap_or(0);
+        let loops_to_wait = (latest_first_arrival - solution).max(0) + modulus - 1;
+        (solution + loops_to_wait / modulus * modulus) as u64
     }
 }
 
@@ -102,7 +109,24 @@ EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)
 ";
-        let map = CamelMap::parse(input);
+        let map = CamelMap::parse(input).unwrap();
         assert_eq!(2, map.get_steps_to_zzz());
     }
+
+    #[test]
+    fn test_part2() {
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+        let map = CamelMap::parse(input).unwrap();
+        assert_eq!(6, map.get_steps_to_zzz_ghost_mode());
+    }
 }
\ No newline at end of file
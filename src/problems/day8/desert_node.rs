@@ -1,3 +1,5 @@
+use crate::problems::shared::parse::{Line, ParseError};
+
 pub struct DesertNode {
     pub id: [char; 3],
 
@@ -6,18 +8,26 @@ pub struct DesertNode {
 }
 
 impl DesertNode {
-    pub fn parse(line: &str) -> DesertNode {
+    pub fn parse(line: Line) -> Result<DesertNode, ParseError> {
         // LST = (PVJ, DPR)
+        let (id, targets) = line.split_once("=")?;
+        let targets = targets
+            .text
+            .strip_prefix('(')
+            .and_then(|t| t.strip_suffix(')'))
+            .ok_or_else(|| line.error("expected the targets in parentheses"))?;
+        let (left_id, right_id) = line.with_text(targets).split_once(",")?;
 
-        let mut id = line[0..3].chars();
-        let mut left_id = line[7..10].chars();
-        let mut right_id = line[12..15].chars();
+        Ok(DesertNode {
+            id: DesertNode::parse_id(id)?,
+            left_id: DesertNode::parse_id(left_id)?,
+            right_id: DesertNode::parse_id(right_id)?,
+        })
+    }
 
-        DesertNode {
-            id: [id.next().unwrap(), id.next().unwrap(), id.next().unwrap()],
-            left_id: [left_id.next().unwrap(), left_id.next().unwrap(), left_id.next().unwrap()],
-            right_id: [right_id.next().unwrap(), right_id.next().unwrap(), right_id.next().unwrap()],
-        }
+    fn parse_id(id: Line) -> Result<[char; 3], ParseError> {
+        let chars: Vec<char> = id.text.chars().collect();
+        chars.try_into().map_err(|_| id.error(format!("{:?} is not a three letter node", id.text)))
     }
 
     pub fn is_start_for_ghosts(&self) -> bool {
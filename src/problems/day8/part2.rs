@@ -1,8 +1,8 @@
 use std::fs;
 use crate::problems::day8::camel_map::CamelMap;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let camel_map = CamelMap::parse(&contents);
-    println!("Part 2: {}", camel_map.get_steps_to_zzz_ghost_mode());
+    let camel_map = CamelMap::parse(&contents).unwrap();
+    camel_map.get_steps_to_zzz_ghost_mode().to_string()
 }
\ No newline at end of file
@@ -1,15 +1,15 @@
 use std::fs;
+use crate::problems::shared::parse;
 
-pub fn execute(input_path: &std::path::PathBuf) {
+pub fn execute(input_path: &std::path::PathBuf) -> String {
     let contents = fs::read_to_string(input_path).expect("Should have been able to read the file");
-    let lines = contents.lines().filter(|l| !l.is_empty());
     let mut result = 0;
-    for line in lines {
-        let top_entries: Vec<i64> = line.split_whitespace().map(|e| e.parse::<i64>().unwrap()).collect();
+    for line in parse::lines(&contents) {
+        let top_entries: Vec<i64> = line.integers::<i64>().unwrap();
         let next_predicted_value = compute_right_value_recursive(top_entries);
         result += next_predicted_value;
     }
-	println!("Part 1: {}", result);
+    result.to_string()
 }
 
 fn compute_right_value_recursive(parent: Vec<i64>) -> i64 {
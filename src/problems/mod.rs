@@ -1,4 +1,4 @@
-use crate::problems::aocday::AoCDay;
+use crate::problems::aocday::{AoCDay, WithOptions};
 
 pub mod aocday;
 mod shared;
@@ -27,130 +27,227 @@ mod day20;
 mod day21;
 mod day22;
 mod day23;
-// pub mod day24;
-// pub mod day25;
+mod day24;
+mod day25;
 
 pub fn get_days() -> Vec<aocday::AoCDay> {
 	vec![
 		AoCDay {
 			day: 0,
 			part1: Box::new(day0::part1::execute),
-			part2: Box::new(day0::part2::execute)
+			part2: Box::new(day0::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 1,
 			part1: Box::new(day1::part1::execute),
-			part2: Box::new(day1::part2::execute)
+			part2: Box::new(day1::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: Some(WithOptions {
+				accepts: &["missing-digits", "report"],
+				run: Box::new(day1::options::execute)
+			})
 		},
 		AoCDay {
 			day: 2,
 			part1: Box::new(day2::part1::execute),
-			part2: Box::new(day2::part2::execute)
+			part2: Box::new(day2::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: Some(WithOptions {
+				accepts: &["bag"],
+				run: Box::new(day2::options::execute)
+			})
 		},
 		AoCDay {
 			day: 3,
 			part1: Box::new(day3::part1::execute),
-			part2: Box::new(day3::part2::execute)
+			part2: Box::new(day3::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: Some(WithOptions {
+				accepts: &["annotate", "rulers"],
+				run: Box::new(day3::options::execute)
+			})
 		},
 		AoCDay {
 			day: 4,
 			part1: Box::new(day4::part1::execute),
-			part2: Box::new(day4::part2::execute)
+			part2: Box::new(day4::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 5,
 			part1: Box::new(day5::part1::execute),
-			part2: Box::new(day5::part2::execute)
+			part2: Box::new(day5::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 6,
 			part1: Box::new(day6::part1::execute),
-			part2: Box::new(day6::part2::execute)
+			part2: Box::new(day6::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 7,
 			part1: Box::new(day7::part1::execute),
-			part2: Box::new(day7::part2::execute)
+			part2: Box::new(day7::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 8,
 			part1: Box::new(day8::part1::execute),
-			part2: Box::new(day8::part2::execute)
+			part2: Box::new(day8::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 9,
 			part1: Box::new(day9::part1::execute),
-			part2: Box::new(day9::part2::execute)
+			part2: Box::new(day9::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 10,
 			part1: Box::new(day10::part1::execute),
-			part2: Box::new(day10::part2::execute)
+			part2: Box::new(day10::part2::execute),
+			visualize: None,
+			render: Some(Box::new(day10::render::execute)),
+			with_options: None
 		},
 		AoCDay {
 			day: 11,
 			part1: Box::new(day11::part1::execute),
-			part2: Box::new(day11::part2::execute)
+			part2: Box::new(day11::part2::execute),
+			visualize: None,
+			render: Some(Box::new(day11::render::execute)),
+			with_options: None
 		},
 		AoCDay {
 			day: 12,
 			part1: Box::new(day12::part1::execute),
-			part2: Box::new(day12::part2::execute)
+			part2: Box::new(day12::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 13,
 			part1: Box::new(day13::part1::execute),
-			part2: Box::new(day13::part2::execute)
+			part2: Box::new(day13::part2::execute),
+			visualize: None,
+			render: Some(Box::new(day13::render::execute)),
+			with_options: None
 		},
 		AoCDay {
 			day: 14,
 			part1: Box::new(day14::part1::execute),
-			part2: Box::new(day14::part2::execute)
+			part2: Box::new(day14::part2::execute),
+			visualize: Some(Box::new(day14::visualize::execute)),
+			render: Some(Box::new(day14::render::execute)),
+			with_options: None
 		},
 		AoCDay {
 			day: 15,
 			part1: Box::new(day15::part1::execute),
-			part2: Box::new(day15::part2::execute)
+			part2: Box::new(day15::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 16,
 			part1: Box::new(day16::part1::execute),
-			part2: Box::new(day16::part2::execute)
+			part2: Box::new(day16::part2::execute),
+			visualize: Some(Box::new(day16::visualize::execute)),
+			render: Some(Box::new(day16::render::execute)),
+			with_options: None
 		},
 		AoCDay {
 			day: 17,
 			part1: Box::new(day17::part1::execute),
-			part2: Box::new(day17::part2::execute)
+			part2: Box::new(day17::part2::execute),
+			visualize: None,
+			render: Some(Box::new(day17::render::execute)),
+			with_options: None
 		},
 		AoCDay {
 			day: 18,
 			part1: Box::new(day18::part1::execute),
-			part2: Box::new(day18::part2::execute)
+			part2: Box::new(day18::part2::execute),
+			visualize: None,
+			render: Some(Box::new(day18::render::execute)),
+			with_options: None
 		},
 		AoCDay {
 			day: 19,
 			part1: Box::new(day19::part1::execute),
-			part2: Box::new(day19::part2::execute)
+			part2: Box::new(day19::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 20,
 			part1: Box::new(day20::part1::execute),
-			part2: Box::new(day20::part2::execute)
+			part2: Box::new(day20::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 21,
 			part1: Box::new(day21::part1::execute),
-			part2: Box::new(day21::part2::execute)
+			part2: Box::new(day21::part2::execute),
+			visualize: Some(Box::new(day21::visualize::execute)),
+			render: Some(Box::new(day21::render::execute)),
+			with_options: None
 		},
 		AoCDay {
 			day: 22,
 			part1: Box::new(day22::part1::execute),
-			part2: Box::new(day22::part2::execute)
+			part2: Box::new(day22::part2::execute),
+			visualize: Some(Box::new(day22::visualize::execute)),
+			render: None,
+			with_options: None
 		},
 		AoCDay {
 			day: 23,
 			part1: Box::new(day23::part1::execute),
-			part2: Box::new(day23::part2::execute)
+			part2: Box::new(day23::part2::execute),
+			visualize: None,
+			render: Some(Box::new(day23::render::execute)),
+			with_options: None
+		},
+		AoCDay {
+			day: 24,
+			part1: Box::new(day24::part1::execute),
+			part2: Box::new(day24::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
+		},
+		AoCDay {
+			day: 25,
+			part1: Box::new(day25::part1::execute),
+			part2: Box::new(day25::part2::execute),
+			visualize: None,
+			render: None,
+			with_options: None
 		},
     ]
 }
\ No newline at end of file
@@ -0,0 +1,155 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Where one pattern was found, as a byte range of the searched text.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PatternMatch {
+    /// The index of the pattern, in the order the patterns were given.
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize
+}
+
+struct Node {
+    children: HashMap<u8, usize>,
+    // the longest proper suffix of this node's prefix that is also a prefix of some pattern
+    fail: usize,
+    // every pattern ending here, including those ending at a suffix
+    outputs: Vec<usize>
+}
+
+impl Node {
+    fn new() -> Node {
+        Node {
+            children: HashMap::new(),
+            fail: 0,
+            outputs: vec![]
+        }
+    }
+}
+
+/// Finds every occurrence of a set of patterns in one pass over the text, however much they overlap.
+/// Each pattern carries a value, such as the digit a word stands for.
+pub struct AhoCorasick<V> {
+    nodes: Vec<Node>,
+    patterns: Vec<(usize, V)>
+}
+
+impl<V> AhoCorasick<V> {
+    /// Builds the automaton for `patterns`, each with its value. Empty patterns never match.
+    pub fn new<I, P>(patterns: I) -> AhoCorasick<V> where I: IntoIterator<Item = (P, V)>, P: AsRef<[u8]> {
+        let mut nodes = vec![Node::new()];
+        let mut values = vec![];
+        for (index, (pattern, value)) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            values.push((pattern.len(), value));
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut node = 0;
+            for byte in pattern {
+                node = match nodes[node].children.get(byte) {
+                    Some(child) => *child,
+                    None => {
+                        nodes.push(Node::new());
+                        let child = nodes.len() - 1;
+                        nodes[node].children.insert(*byte, child);
+                        child
+                    }
+                };
+            }
+            nodes[node].outputs.push(index);
+        }
+
+        // breadth first, so every node's fail link is finished before its children need it
+        let mut queue: VecDeque<usize> = nodes[0].children.values().copied().collect();
+        while let Some(node) = queue.pop_front() {
+            let children: Vec<(u8, usize)> = nodes[node].children.iter().map(|(byte, child)| (*byte, *child)).collect();
+            for (byte, child) in children {
+                let mut fail = nodes[node].fail;
+                let fail = loop {
+                    if let Some(next) = nodes[fail].children.get(&byte) {
+                        break *next;
+                    }
+                    if fail == 0 {
+                        break 0;
+                    }
+                    fail = nodes[fail].fail;
+                };
+
+                nodes[child].fail = fail;
+                let inherited = nodes[fail].outputs.clone();
+                nodes[child].outputs.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        AhoCorasick {
+            nodes,
+            patterns: values
+        }
+    }
+
+    /// The value given with `pattern`.
+    pub fn value(&self, pattern: usize) -> &V {
+        &self.patterns[pattern].1
+    }
+
+    /// Every occurrence of every pattern in `text`, overlapping ones included, ordered by where they end
+    /// and then longest first.
+    pub fn find_overlapping(&self, text: &[u8]) -> Vec<PatternMatch> {
+        let mut matches = vec![];
+        let mut node = 0;
+        for (position, byte) in text.iter().enumerate() {
+            node = loop {
+                if let Some(next) = self.nodes[node].children.get(byte) {
+                    break *next;
+                }
+                if node == 0 {
+                    break 0;
+                }
+                node = self.nodes[node].fail;
+            };
+
+            for pattern in self.nodes[node].outputs.iter() {
+                let length = self.patterns[*pattern].0;
+                matches.push(PatternMatch {
+                    pattern: *pattern,
+                    start: position + 1 - length,
+                    end: position + 1
+                });
+            }
+        }
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::aho_corasick::{AhoCorasick, PatternMatch};
+
+    #[test]
+    fn finds_overlapping_patterns() {
+        let matcher = AhoCorasick::new([("he", 1), ("she", 2), ("his", 3), ("hers", 4)]);
+        let found: Vec<(usize, usize, i32)> = matcher
+            .find_overlapping(b"ushers")
+            .iter()
+            .map(|m| (m.start, m.end, *matcher.value(m.pattern)))
+            .collect();
+        assert_eq!(vec![(1, 4, 2), (2, 4, 1), (2, 6, 4)], found);
+    }
+
+    #[test]
+    fn handles_repeats_and_empty_patterns() {
+        let matcher = AhoCorasick::new(["aa", "", "a"].map(|p| (p, ())));
+        assert_eq!(vec![
+            PatternMatch { pattern: 2, start: 0, end: 1 },
+            PatternMatch { pattern: 0, start: 0, end: 2 },
+            PatternMatch { pattern: 2, start: 1, end: 2 },
+            PatternMatch { pattern: 0, start: 1, end: 3 },
+            PatternMatch { pattern: 2, start: 2, end: 3 }
+        ], matcher.find_overlapping(b"aaa"));
+        assert!(matcher.find_overlapping(b"bcd").is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+use crate::problems::shared::render::Canvas;
+
+/// Passes frames from a running simulation on to be played back, such as to a GIF encoder. Only every
+/// `step`th frame offered is kept, and frames are only drawn when they are kept, so a disabled recorder
+/// costs next to nothing.
+pub struct Recorder<'a> {
+    on_frame: Option<Box<dyn FnMut(Canvas) + 'a>>,
+    step: usize,
+    offered: usize
+}
+
+impl<'a> Recorder<'a> {
+    /// Hands one frame out of every `step` to `on_frame` as soon as it is drawn. Panics if `step` is zero.
+    pub fn new<F>(step: usize, on_frame: F) -> Recorder<'a> where F: FnMut(Canvas) + 'a {
+        assert!(step > 0, "A recorder has to keep some frames");
+        Recorder {
+            on_frame: Some(Box::new(on_frame)),
+            step,
+            offered: 0
+        }
+    }
+
+    /// A recorder that ignores every frame, for running simulations without watching them.
+    pub fn disabled() -> Recorder<'a> {
+        Recorder {
+            on_frame: None,
+            step: 1,
+            offered: 0
+        }
+    }
+
+    /// Offers the next frame. `draw` is only called if the frame is kept.
+    pub fn record<F>(&mut self, draw: F) where F: FnOnce() -> Canvas {
+        let Some(on_frame) = self.on_frame.as_mut() else {
+            return;
+        };
+
+        if self.offered.is_multiple_of(self.step) {
+            on_frame(draw());
+        }
+        self.offered += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::animation::Recorder;
+    use crate::problems::shared::grid_point_2d::GridPoint2D;
+    use crate::problems::shared::image::Rgb;
+    use crate::problems::shared::render::{Canvas, Cell, Render};
+
+    struct Counter(usize);
+
+    impl Render for Counter {
+        fn render_size(&self) -> (usize, usize) {
+            (1, 1)
+        }
+
+        fn render_cell(&self, _: GridPoint2D) -> Cell {
+            Cell::new(char::from_digit(self.0 as u32, 10).unwrap(), Rgb::new(self.0 as u8 * 20, 0, 0))
+        }
+    }
+
+    #[test]
+    fn keeps_every_step_frames() {
+        let mut frames = vec![];
+        let mut recorder = Recorder::new(3, |frame: Canvas| frames.push(frame.to_text()));
+        let mut drawn = 0;
+        for n in 0..8 {
+            recorder.record(|| {
+                drawn += 1;
+                Counter(n).canvas()
+            });
+        }
+
+        drop(recorder);
+        assert_eq!(3, drawn);
+        assert_eq!(vec!["0\n", "3\n", "6\n"], frames);
+    }
+
+    #[test]
+    fn disabled_recorders_draw_nothing() {
+        let mut recorder = Recorder::disabled();
+        recorder.record(|| panic!("A disabled recorder should not draw"));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A grid of on/off tiles packed one bit per tile, a row of words at a time, so whole rows can be
+/// combined, shifted and counted a word at a time.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitGrid {
+    height: usize,
+    width: usize,
+    words_per_row: usize,
+    words: Vec<u64>
+}
+
+impl BitGrid {
+    /// A grid with every tile off.
+    pub fn new(height: usize, width: usize) -> BitGrid {
+        let words_per_row = width.div_ceil(WORD_BITS);
+        BitGrid {
+            height,
+            width,
+            words_per_row,
+            words: vec![0; height * words_per_row]
+        }
+    }
+
+    /// A grid with the tiles of `grid` that match `predicate` turned on.
+    pub fn from_grid<TTile, F>(grid: &Grid2D<TTile>, predicate: F) -> BitGrid where F: Fn(&TTile) -> bool {
+        let mut bits = BitGrid::new(grid.height(), grid.width());
+        for (point, tile) in grid.iter() {
+            if predicate(tile) {
+                bits.set(point, true);
+            }
+        }
+
+        bits
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    fn word_index(&self, point: &GridPoint2D) -> (usize, u64) {
+        assert!(point.x < self.height && point.y < self.width, "({}, {}) is outside a {}x{} bit grid", point.x, point.y, self.height, self.width);
+        (point.x * self.words_per_row + point.y / WORD_BITS, 1 << (point.y % WORD_BITS))
+    }
+
+    /// Panics if `point` is outside the grid.
+    pub fn get(&self, point: &GridPoint2D) -> bool {
+        let (index, mask) = self.word_index(point);
+        self.words[index] & mask != 0
+    }
+
+    /// Turns the tile at `point` on or off, returning whether it was on. Panics if `point` is outside the grid.
+    pub fn set(&mut self, point: GridPoint2D, value: bool) -> bool {
+        let (index, mask) = self.word_index(&point);
+        let was_set = self.words[index] & mask != 0;
+        if value {
+            self.words[index] |= mask;
+        } else {
+            self.words[index] &= !mask;
+        }
+
+        was_set
+    }
+
+    /// How many tiles are on.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// How many tiles in `row` are on.
+    pub fn count_ones_in_row(&self, row: usize) -> usize {
+        self.row_words(row).iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// The tiles that are on, a row at a time.
+    pub fn ones(&self) -> impl Iterator<Item = GridPoint2D> + '_ {
+        (0..self.height).flat_map(move |row| (0..self.width).map(move |col| GridPoint2D::new(row, col)).filter(|p| self.get(p)))
+    }
+
+    fn row_words(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    /// Keeps the unused high bits of each row's last word clear so counts and comparisons stay exact.
+    fn mask_row_ends(&mut self) {
+        let unused = self.words_per_row * WORD_BITS - self.width;
+        if unused == 0 {
+            return;
+        }
+
+        let mask = u64::MAX >> unused;
+        for row in 0..self.height {
+            self.words[(row + 1) * self.words_per_row - 1] &= mask;
+        }
+    }
+
+    /// Every tile moved one step in `direction`. Tiles pushed off the edge are lost and the opposite edge is left off.
+    pub fn shifted(&self, direction: Grid2dDirection) -> BitGrid {
+        let mut shifted = BitGrid::new(self.height, self.width);
+        let words_per_row = self.words_per_row;
+        match direction {
+            Grid2dDirection::Up => {
+                if self.height > 1 {
+                    shifted.words[..(self.height - 1) * words_per_row].copy_from_slice(&self.words[words_per_row..]);
+                }
+            },
+            Grid2dDirection::Down => {
+                if self.height > 1 {
+                    shifted.words[words_per_row..].copy_from_slice(&self.words[..(self.height - 1) * words_per_row]);
+                }
+            },
+            Grid2dDirection::Left => {
+                // column c takes the bit from column c + 1, carrying the lowest bit of the next word down
+                for row in 0..self.height {
+                    let source = self.row_words(row);
+                    for word in 0..words_per_row {
+                        let carry = source.get(word + 1).map_or(0, |next| next << (WORD_BITS - 1));
+                        shifted.words[row * words_per_row + word] = (source[word] >> 1) | carry;
+                    }
+                }
+            },
+            Grid2dDirection::Right => {
+                // column c takes the bit from column c - 1, carrying the highest bit of the previous word up
+                for row in 0..self.height {
+                    let source = self.row_words(row);
+                    for word in 0..words_per_row {
+                        let carry = if word == 0 { 0 } else { source[word - 1] >> (WORD_BITS - 1) };
+                        shifted.words[row * words_per_row + word] = (source[word] << 1) | carry;
+                    }
+                }
+                shifted.mask_row_ends();
+            }
+        }
+
+        shifted
+    }
+
+    fn combine_with<F>(&mut self, other: &BitGrid, combine: F) where F: Fn(u64, u64) -> u64 {
+        assert!(self.height == other.height && self.width == other.width, "Bit grids must be the same size to combine");
+        self.words.iter_mut().zip(&other.words).for_each(|(word, other)| *word = combine(*word, *other));
+    }
+
+    /// Turns on every tile that is on in `other`.
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a | b);
+    }
+
+    /// Turns off every tile that is on in `other`.
+    pub fn difference_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a & !b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::bit_grid::BitGrid;
+    use crate::problems::shared::grid_2d::Grid2D;
+    use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+    use crate::problems::shared::grid_point_2d::GridPoint2D;
+
+    fn bits(rows: &str) -> BitGrid {
+        BitGrid::from_grid(&Grid2D::parse(rows, Some).unwrap(), |c| *c == '#')
+    }
+
+    #[test]
+    fn sets_and_counts() {
+        let mut grid = BitGrid::new(2, 70);
+        assert!(grid.is_empty());
+        assert!(!grid.set(GridPoint2D::new(1, 69), true));
+        assert!(grid.set(GridPoint2D::new(1, 69), true));
+        grid.set(GridPoint2D::new(0, 3), true);
+        assert!(grid.get(&GridPoint2D::new(1, 69)));
+        assert_eq!(2, grid.count_ones());
+        assert_eq!(1, grid.count_ones_in_row(1));
+        assert_eq!(vec![GridPoint2D::new(0, 3), GridPoint2D::new(1, 69)], grid.ones().collect::<Vec<GridPoint2D>>());
+    }
+
+    #[test]
+    fn shifts_in_every_direction() {
+        let grid = bits("#..#\n.#..\n...#");
+        assert_eq!(bits(".#..\n...#\n...."), grid.shifted(Grid2dDirection::Up));
+        assert_eq!(bits("....\n#..#\n.#.."), grid.shifted(Grid2dDirection::Down));
+        assert_eq!(bits("..#.\n#...\n..#."), grid.shifted(Grid2dDirection::Left));
+        assert_eq!(bits(".#..\n..#.\n...."), grid.shifted(Grid2dDirection::Right));
+    }
+
+    #[test]
+    fn shifts_across_words() {
+        let mut grid = BitGrid::new(1, 130);
+        grid.set(GridPoint2D::new(0, 63), true);
+        grid.set(GridPoint2D::new(0, 64), true);
+        grid.set(GridPoint2D::new(0, 129), true);
+
+        let right = grid.shifted(Grid2dDirection::Right);
+        assert_eq!(vec![64, 65], right.ones().map(|p| p.y).collect::<Vec<usize>>());
+        let left = grid.shifted(Grid2dDirection::Left);
+        assert_eq!(vec![62, 63, 128], left.ones().map(|p| p.y).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn combines() {
+        let mut grid = bits("##.\n..#");
+        grid.difference_with(&bits("#..\n..."));
+        assert_eq!(bits(".#.\n..#"), grid);
+        grid.union_with(&bits("#..\n..."));
+        assert_eq!(bits("##.\n..#"), grid);
+    }
+}
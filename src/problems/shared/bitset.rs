@@ -0,0 +1,230 @@
+const WORD_BITS: usize = u64::BITS as usize;
+
+fn set_bits(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    words.iter().enumerate().flat_map(|(word_index, word)| {
+        let mut remaining = *word;
+        std::iter::from_fn(move || {
+            if remaining == 0 {
+                return None;
+            }
+
+            let bit = remaining.trailing_zeros() as usize;
+            remaining &= remaining - 1;
+            Some(word_index * WORD_BITS + bit)
+        })
+    })
+}
+
+/// A set of small integers below `WORDS * 64`, stored inline so it can be copied freely.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FixedBitSet<const WORDS: usize> {
+    words: [u64; WORDS]
+}
+
+impl<const WORDS: usize> Default for FixedBitSet<WORDS> {
+    fn default() -> Self {
+        FixedBitSet::new()
+    }
+}
+
+impl<const WORDS: usize> FixedBitSet<WORDS> {
+    pub const CAPACITY: usize = WORDS * WORD_BITS;
+
+    pub fn new() -> FixedBitSet<WORDS> {
+        FixedBitSet {
+            words: [0; WORDS]
+        }
+    }
+
+    /// Adds `value`, returning whether it was newly added. Panics if `value` is not below `CAPACITY`.
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(value < Self::CAPACITY, "{} does not fit in a bitset of {} bits", value, Self::CAPACITY);
+        let (word, mask) = (value / WORD_BITS, 1 << (value % WORD_BITS));
+        let is_new = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        is_new
+    }
+
+    /// Removes `value`, returning whether it was present.
+    #[allow(dead_code)]
+    pub fn remove(&mut self, value: usize) -> bool {
+        let was_present = self.contains(value);
+        if was_present {
+            self.words[value / WORD_BITS] &= !(1 << (value % WORD_BITS));
+        }
+
+        was_present
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value < Self::CAPACITY && self.words[value / WORD_BITS] & (1 << (value % WORD_BITS)) != 0
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// The values in the set, smallest first.
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        set_bits(&self.words)
+    }
+
+    #[allow(dead_code)]
+    pub fn union_with(&mut self, other: &FixedBitSet<WORDS>) {
+        self.words.iter_mut().zip(other.words).for_each(|(word, other)| *word |= other);
+    }
+
+    #[allow(dead_code)]
+    pub fn intersect_with(&mut self, other: &FixedBitSet<WORDS>) {
+        self.words.iter_mut().zip(other.words).for_each(|(word, other)| *word &= other);
+    }
+
+    #[allow(dead_code)]
+    pub fn clear(&mut self) {
+        self.words = [0; WORDS];
+    }
+}
+
+/// A set of small integers that grows to fit whatever is inserted.
+#[derive(Clone, Default, Debug)]
+pub struct BitSet {
+    words: Vec<u64>
+}
+
+impl BitSet {
+    /// An empty set with room for values below `capacity` before it needs to grow.
+    pub fn with_capacity(capacity: usize) -> BitSet {
+        BitSet {
+            words: vec![0; capacity.div_ceil(WORD_BITS)]
+        }
+    }
+
+    /// Adds `value`, returning whether it was newly added.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, mask) = (value / WORD_BITS, 1 << (value % WORD_BITS));
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+
+        let is_new = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        is_new
+    }
+
+    /// Removes `value`, returning whether it was present.
+    #[allow(dead_code)]
+    pub fn remove(&mut self, value: usize) -> bool {
+        let was_present = self.contains(value);
+        if was_present {
+            self.words[value / WORD_BITS] &= !(1 << (value % WORD_BITS));
+        }
+
+        was_present
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, value: usize) -> bool {
+        self.words.get(value / WORD_BITS).is_some_and(|word| word & (1 << (value % WORD_BITS)) != 0)
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    #[allow(dead_code)]
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = 0);
+    }
+
+    /// The values in the set, smallest first.
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        set_bits(&self.words)
+    }
+
+    #[allow(dead_code)]
+    pub fn union_with(&mut self, other: &BitSet) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        self.words.iter_mut().zip(&other.words).for_each(|(word, other)| *word |= other);
+    }
+
+    #[allow(dead_code)]
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        for (index, word) in self.words.iter_mut().enumerate() {
+            *word &= other.words.get(index).copied().unwrap_or(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::bitset::{BitSet, FixedBitSet};
+
+    #[test]
+    fn fixed_sets_insert_and_iterate() {
+        let mut set: FixedBitSet<2> = FixedBitSet::new();
+        assert_eq!(128, FixedBitSet::<2>::CAPACITY);
+        assert!(set.insert(3));
+        assert!(set.insert(127));
+        assert!(!set.insert(3));
+        assert!(set.contains(127));
+        assert!(!set.contains(200));
+        assert_eq!(vec![3, 127], set.iter().collect::<Vec<usize>>());
+
+        let copy = set;
+        assert!(set.remove(3));
+        assert_eq!(1, set.len());
+        assert_eq!(2, copy.len());
+
+        let mut other = FixedBitSet::new();
+        other.insert(64);
+        other.insert(127);
+        set.union_with(&other);
+        assert_eq!(vec![64, 127], set.iter().collect::<Vec<usize>>());
+        set.intersect_with(&copy);
+        assert_eq!(vec![127], set.iter().collect::<Vec<usize>>());
+        assert!(!set.is_empty());
+        set.clear();
+        assert!(set.is_empty());
+        assert!(!set.remove(127));
+    }
+
+    #[test]
+    fn dynamic_sets_grow() {
+        let mut set = BitSet::with_capacity(10);
+        assert!(set.is_empty());
+        assert!(set.insert(1000));
+        assert!(set.insert(5));
+        assert!(set.contains(1000));
+        assert!(!set.contains(999));
+        assert!(!set.contains(100_000));
+
+        assert!(set.remove(1000));
+        assert!(!set.contains(1000));
+
+        let mut other = BitSet::default();
+        other.insert(5);
+        other.insert(70);
+        set.intersect_with(&other);
+        assert_eq!(vec![5], set.iter().collect::<Vec<usize>>());
+        set.union_with(&other);
+        assert_eq!(2, set.len());
+        set.clear();
+        assert!(set.is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+use crate::problems::shared::vector_3d::Vector3D;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Axis {
+    X,
+    Y,
+    Z
+}
+
+/// An axis-aligned rectangle of integer points, inclusive of both corners.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rectangle {
+    pub min: (i64, i64),
+    pub max: (i64, i64)
+}
+
+impl Rectangle {
+    /// Builds the rectangle spanning two opposite corners given in any order.
+    pub fn new(first: (i64, i64), second: (i64, i64)) -> Rectangle {
+        Rectangle {
+            min: (first.0.min(second.0), first.1.min(second.1)),
+            max: (first.0.max(second.0), first.1.max(second.1))
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn area(&self) -> u128 {
+        (self.max.0 - self.min.0 + 1) as u128 * (self.max.1 - self.min.1 + 1) as u128
+    }
+
+    pub fn contains(&self, point: (i64, i64)) -> bool {
+        (self.min.0..=self.max.0).contains(&point.0) && (self.min.1..=self.max.1).contains(&point.1)
+    }
+
+    /// The smallest rectangle covering this one and `point`.
+    pub fn including(&self, point: (i64, i64)) -> Rectangle {
+        Rectangle::new((self.min.0.min(point.0), self.min.1.min(point.1)), (self.max.0.max(point.0), self.max.1.max(point.1)))
+    }
+
+    /// Pushes every edge out by `margin`.
+    pub fn grow(&self, margin: i64) -> Rectangle {
+        Rectangle::new((self.min.0 - margin, self.min.1 - margin), (self.max.0 + margin, self.max.1 + margin))
+    }
+
+    #[allow(dead_code)]
+    pub fn intersects(&self, other: &Rectangle) -> bool {
+        self.min.0 <= other.max.0 && other.min.0 <= self.max.0 && self.min.1 <= other.max.1 && other.min.1 <= self.max.1
+    }
+
+    /// Every integer point in the rectangle.
+    pub fn points(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        (self.min.0..=self.max.0).flat_map(move |a| (self.min.1..=self.max.1).map(move |b| (a, b)))
+    }
+}
+
+/// An axis-aligned box of integer points, inclusive of both corners.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Box3D {
+    min: Vector3D,
+    max: Vector3D
+}
+
+impl Box3D {
+    /// Builds the box spanning two opposite corners given in any order.
+    pub fn new(first: Vector3D, second: Vector3D) -> Box3D {
+        Box3D {
+            min: Vector3D::new(first.x.min(second.x), first.y.min(second.y), first.z.min(second.z)),
+            max: Vector3D::new(first.x.max(second.x), first.y.max(second.y), first.z.max(second.z))
+        }
+    }
+
+    pub fn min(&self) -> Vector3D {
+        self.min
+    }
+
+    pub fn max(&self) -> Vector3D {
+        self.max
+    }
+
+    /// The number of integer points in the box.
+    #[allow(dead_code)]
+    pub fn volume(&self) -> u128 {
+        let size = self.max - self.min;
+        (size.x + 1) as u128 * (size.y + 1) as u128 * (size.z + 1) as u128
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, point: &Vector3D) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// The points shared by both boxes, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &Box3D) -> Option<Box3D> {
+        let min = Vector3D::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y), self.min.z.max(other.min.z));
+        let max = Vector3D::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y), self.max.z.min(other.max.z));
+        if min.x > max.x || min.y > max.y || min.z > max.z {
+            return None;
+        }
+
+        Some(Box3D {
+            min,
+            max
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn intersects(&self, other: &Box3D) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Flattens the box along `axis`, keeping the other two axes in x, y, z order.
+    /// Projecting along `Axis::Z` gives the box's footprint on the ground.
+    pub fn project(&self, axis: Axis) -> Rectangle {
+        match axis {
+            Axis::X => Rectangle::new((self.min.y, self.min.z), (self.max.y, self.max.z)),
+            Axis::Y => Rectangle::new((self.min.x, self.min.z), (self.max.x, self.max.z)),
+            Axis::Z => Rectangle::new((self.min.x, self.min.y), (self.max.x, self.max.y))
+        }
+    }
+
+    pub fn translate(&self, offset: Vector3D) -> Box3D {
+        Box3D {
+            min: self.min + offset,
+            max: self.max + offset
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::box_3d::{Axis, Box3D, Rectangle};
+    use crate::problems::shared::vector_3d::Vector3D;
+
+    #[test]
+    fn normalizes_and_measures() {
+        let brick = Box3D::new(Vector3D::new(2, 0, 5), Vector3D::new(0, 0, 5));
+        assert_eq!(Vector3D::new(0, 0, 5), brick.min());
+        assert_eq!(Vector3D::new(2, 0, 5), brick.max());
+        assert_eq!(3, brick.volume());
+        assert!(brick.contains(&Vector3D::new(1, 0, 5)));
+        assert!(!brick.contains(&Vector3D::new(1, 1, 5)));
+    }
+
+    #[test]
+    fn intersects_projects_and_translates() {
+        let a = Box3D::new(Vector3D::new(0, 0, 0), Vector3D::new(3, 3, 3));
+        let b = Box3D::new(Vector3D::new(2, 3, -1), Vector3D::new(5, 5, 1));
+        assert_eq!(Some(Box3D::new(Vector3D::new(2, 3, 0), Vector3D::new(3, 3, 1))), a.intersection(&b));
+        assert!(!a.intersects(&b.translate(Vector3D::new(0, 1, 0))));
+
+        assert_eq!(Rectangle::new((2, 3), (5, 5)), b.project(Axis::Z));
+        assert_eq!(Rectangle::new((3, -1), (5, 1)), b.project(Axis::X));
+        assert_eq!(Rectangle::new((2, -1), (5, 1)), b.project(Axis::Y));
+        assert_eq!(12, b.project(Axis::Z).area());
+        assert!(a.project(Axis::Z).intersects(&b.project(Axis::Z)));
+        assert_eq!(vec![(2, 3), (2, 4), (3, 3), (3, 4)], Rectangle::new((3, 4), (2, 3)).points().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::problems::shared::box_3d::Rectangle;
+
+/// One axis of a compressed grid. Every breakpoint gets a cell of its own, and each run of coordinates
+/// between two breakpoints is merged into a single cell.
+#[derive(Clone, Debug)]
+pub struct CompressedAxis {
+    // the first coordinate and the number of coordinates in each cell, in increasing order
+    cells: Vec<(i64, u64)>
+}
+
+impl CompressedAxis {
+    pub fn new<I>(breakpoints: I) -> CompressedAxis where I: IntoIterator<Item = i64> {
+        let mut breakpoints: Vec<i64> = breakpoints.into_iter().collect();
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+
+        let mut cells = Vec::with_capacity(breakpoints.len() * 2);
+        for (index, breakpoint) in breakpoints.iter().enumerate() {
+            cells.push((*breakpoint, 1));
+            if let Some(next) = breakpoints.get(index + 1) {
+                if next - breakpoint > 1 {
+                    cells.push((breakpoint + 1, (next - breakpoint - 1) as u64));
+                }
+            }
+        }
+
+        CompressedAxis {
+            cells
+        }
+    }
+
+    /// How many cells the axis has been compressed into.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cell `coordinate` falls in, or `None` if it is outside every breakpoint.
+    pub fn cell_of(&self, coordinate: i64) -> Option<usize> {
+        let after = self.cells.partition_point(|(start, _)| *start <= coordinate);
+        let cell = after.checked_sub(1)?;
+        let (start, size) = self.cells[cell];
+        (coordinate - start < size as i64).then_some(cell)
+    }
+
+    /// How many coordinates `cell` stands for.
+    pub fn size(&self, cell: usize) -> u64 {
+        self.cells[cell].1
+    }
+
+    /// The first and last coordinate of `cell`.
+    pub fn span(&self, cell: usize) -> (i64, i64) {
+        let (start, size) = self.cells[cell];
+        (start, start + size as i64 - 1)
+    }
+}
+
+/// Squeezes a huge, mostly empty plane into a small grid of weighted cells around the corners of a set of
+/// rectilinear segments. Each segment covers whole cells, so flood fills and drawings on the small grid
+/// match the full plane, and a cell's weight says how many points of the plane it stands for.
+///
+/// Cells are addressed by signed `(row, col)` points, like the uncompressed plane, so they can be stored in
+/// a `SparseGrid` and bounded by a `Rectangle`.
+#[derive(Clone, Debug)]
+pub struct CoordinateCompression {
+    rows: CompressedAxis,
+    cols: CompressedAxis
+}
+
+impl CoordinateCompression {
+    /// Compresses around the ends of `segments`, keeping a one point margin all the way round so there is
+    /// always open space outside them.
+    pub fn from_segments<I>(segments: I) -> CoordinateCompression where I: IntoIterator<Item = ((i64, i64), (i64, i64))> {
+        let mut rows = vec![];
+        let mut cols = vec![];
+        for (from, to) in segments {
+            rows.extend([from.0, to.0]);
+            cols.extend([from.1, to.1]);
+        }
+
+        let with_margin = |breakpoints: &mut Vec<i64>| {
+            let (Some(min), Some(max)) = (breakpoints.iter().min().copied(), breakpoints.iter().max().copied()) else {
+                return;
+            };
+            breakpoints.extend([min - 1, max + 1]);
+        };
+        with_margin(&mut rows);
+        with_margin(&mut cols);
+
+        CoordinateCompression {
+            rows: CompressedAxis::new(rows),
+            cols: CompressedAxis::new(cols)
+        }
+    }
+
+    /// Every cell of the compressed grid.
+    #[allow(dead_code)]
+    pub fn bounds(&self) -> Option<Rectangle> {
+        if self.rows.is_empty() || self.cols.is_empty() {
+            return None;
+        }
+
+        Some(Rectangle::new((0, 0), (self.rows.len() as i64 - 1, self.cols.len() as i64 - 1)))
+    }
+
+    /// The cell `point` falls in, or `None` if it is outside the compressed area.
+    pub fn compress(&self, point: (i64, i64)) -> Option<(i64, i64)> {
+        Some((self.rows.cell_of(point.0)? as i64, self.cols.cell_of(point.1)? as i64))
+    }
+
+    /// The points of the plane that `cell` stands for.
+    #[allow(dead_code)]
+    pub fn expand(&self, cell: (i64, i64)) -> Rectangle {
+        let rows = self.rows.span(cell.0 as usize);
+        let cols = self.cols.span(cell.1 as usize);
+        Rectangle::new((rows.0, cols.0), (rows.1, cols.1))
+    }
+
+    /// How many points of the plane `cell` stands for.
+    pub fn weight(&self, cell: (i64, i64)) -> u64 {
+        self.rows.size(cell.0 as usize) * self.cols.size(cell.1 as usize)
+    }
+
+    /// The cells covered by the segment from `from` to `to`. Panics if the segment is not horizontal or
+    /// vertical, or if its ends were not part of the compression.
+    pub fn segment_cells(&self, from: (i64, i64), to: (i64, i64)) -> Vec<(i64, i64)> {
+        assert!(from.0 == to.0 || from.1 == to.1, "Only rectilinear segments can be compressed");
+        let start = self.compress(from).expect("Segment should start inside the compressed area");
+        let end = self.compress(to).expect("Segment should end inside the compressed area");
+        Rectangle::new(start, end).points().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::box_3d::Rectangle;
+    use crate::problems::shared::compression::{CompressedAxis, CoordinateCompression};
+
+    #[test]
+    fn merges_gaps_between_breakpoints() {
+        let axis = CompressedAxis::new([10, 0, 1, 10]);
+        assert_eq!(4, axis.len());
+        assert_eq!(Some(0), axis.cell_of(0));
+        assert_eq!(Some(2), axis.cell_of(2));
+        assert_eq!(Some(2), axis.cell_of(9));
+        assert_eq!(Some(3), axis.cell_of(10));
+        assert_eq!(None, axis.cell_of(-1));
+        assert_eq!(None, axis.cell_of(11));
+        assert_eq!(8, axis.size(2));
+        assert_eq!((2, 9), axis.span(2));
+    }
+
+    #[test]
+    fn compresses_segments_with_a_margin() {
+        let compression = CoordinateCompression::from_segments([((0, 0), (0, 1_000_000)), ((0, 1_000_000), (-5, 1_000_000))]);
+        assert_eq!(Some(Rectangle::new((0, 0), (4, 4))), compression.bounds());
+        assert_eq!(Some((3, 1)), compression.compress((0, 0)));
+        assert_eq!(Some((2, 2)), compression.compress((-3, 17)));
+        assert_eq!(4 * 999_999, compression.weight((2, 2)));
+        assert_eq!(Rectangle::new((-4, 1), (-1, 999_999)), compression.expand((2, 2)));
+        assert_eq!(vec![(1, 3), (2, 3), (3, 3)], compression.segment_cells((0, 1_000_000), (-5, 1_000_000)));
+
+        let total: u64 = compression.bounds().unwrap().points().map(|cell| compression.weight(cell)).sum();
+        assert_eq!(8 * 1_000_003, total);
+    }
+}
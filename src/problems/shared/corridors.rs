@@ -0,0 +1,120 @@
+use crate::problems::shared::graph::DirectedGraph;
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+
+/// The tiles walked along one edge of a junction graph, from the tile after the starting junction up to and
+/// including the junction it ends at.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Corridor {
+    tiles: Vec<GridPoint2D>
+}
+
+impl Corridor {
+    /// How many steps it takes to walk the corridor.
+    pub fn length(&self) -> usize {
+        self.tiles.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn tiles(&self) -> &[GridPoint2D] {
+        &self.tiles
+    }
+}
+
+/// Collapses the corridors of a maze into a graph between its junctions: every passable tile that does not
+/// have exactly two passable neighbours, which includes dead ends such as entrances and exits.
+///
+/// `can_leave` says whether a tile may be left in a direction, which is how one-way tiles are described.
+/// An edge is only added in a direction the whole corridor can be walked in. Every corridor is walked
+/// once from each end, so this takes time linear in the size of the grid. Loops with no junction on them
+/// are unreachable from any junction and are left out.
+pub fn contract_corridors<TTile, P, S>(grid: &Grid2D<TTile>, is_passable: P, can_leave: S) -> DirectedGraph<GridPoint2D, Corridor>
+where
+    P: Fn(&TTile) -> bool,
+    S: Fn(&TTile, Grid2dDirection) -> bool
+{
+    let passable_neighbours = |point: GridPoint2D| {
+        Grid2dDirection::all()
+            .into_iter()
+            .filter_map(move |direction| grid.step(&point, direction).map(|next| (direction, next)))
+            .filter(|(_, next)| is_passable(&grid[*next]))
+    };
+
+    let mut graph = DirectedGraph::new();
+    let mut junctions: Grid2D<Option<usize>> = grid.map(|_, _| None);
+    for (point, tile) in grid.iter() {
+        if is_passable(tile) && passable_neighbours(point).count() != 2 {
+            junctions[point] = Some(graph.add_node(point));
+        }
+    }
+
+    for junction in 0..graph.node_count() {
+        let start = *graph.node(junction);
+        for (direction, first) in passable_neighbours(start) {
+            let mut is_walkable = can_leave(&grid[start], direction);
+            let mut previous = start;
+            let mut current = first;
+            let mut tiles = vec![first];
+            while junctions[current].is_none() {
+                let (direction, next) = passable_neighbours(current)
+                    .find(|(_, next)| *next != previous)
+                    .expect("A corridor tile has two passable neighbours");
+                is_walkable &= can_leave(&grid[current], direction);
+                previous = current;
+                current = next;
+                tiles.push(next);
+            }
+
+            if is_walkable {
+                graph.add_edge(junction, junctions[current].unwrap(), Corridor { tiles });
+            }
+        }
+    }
+
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::corridors::contract_corridors;
+    use crate::problems::shared::grid_2d::Grid2D;
+    use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+    use crate::problems::shared::grid_point_2d::GridPoint2D;
+
+    fn maze(rows: &str) -> Grid2D<char> {
+        Grid2D::parse(rows, Some).unwrap()
+    }
+
+    #[test]
+    fn contracts_corridors_between_junctions() {
+        let grid = maze("
+#.###
+#...#
+#.#.#
+#...#
+###.#
+");
+        let graph = contract_corridors(&grid, |c| *c != '#', |_, _| true);
+        let points: Vec<GridPoint2D> = graph.nodes().map(|(_, p)| *p).collect();
+        assert_eq!(vec![GridPoint2D::new(0, 1), GridPoint2D::new(1, 1), GridPoint2D::new(3, 3), GridPoint2D::new(4, 3)], points);
+
+        let entrance = graph.index_of(&GridPoint2D::new(0, 1)).unwrap();
+        let fork = graph.index_of(&GridPoint2D::new(1, 1)).unwrap();
+        let join = graph.index_of(&GridPoint2D::new(3, 3)).unwrap();
+        assert_eq!(vec![(fork, 1)], graph.successors(entrance).map(|(n, c)| (n, c.length())).collect::<Vec<(usize, usize)>>());
+        assert_eq!(vec![(entrance, 1), (join, 4), (join, 4)], graph.successors(fork).map(|(n, c)| (n, c.length())).collect::<Vec<(usize, usize)>>());
+
+        let (_, around) = graph.successors(fork).nth(1).unwrap();
+        assert_eq!(&[GridPoint2D::new(1, 2), GridPoint2D::new(1, 3), GridPoint2D::new(2, 3), GridPoint2D::new(3, 3)], around.tiles());
+    }
+
+    #[test]
+    fn respects_one_way_tiles() {
+        let grid = maze(".>..\n####");
+        let graph = contract_corridors(&grid, |c| *c != '#', |c, direction| Grid2dDirection::parse(*c).is_none_or(|slope| slope == direction));
+        assert_eq!(2, graph.node_count());
+        assert_eq!(vec![(1, 3)], graph.successors(0).map(|(n, c)| (n, c.length())).collect::<Vec<(usize, usize)>>());
+        assert_eq!(0, graph.successors(1).count());
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// The states a simulation passes through before it starts repeating, and the loop it then repeats forever.
+pub struct Cycle<S> {
+    start: usize,
+    length: usize,
+    history: Vec<S>
+}
+
+impl<S> Cycle<S> {
+    /// The first step that is part of the loop.
+    #[allow(dead_code)]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// How many steps it takes to come back around the loop.
+    #[allow(dead_code)]
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// The earliest step that is in the same state as `step`.
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.start {
+            return step;
+        }
+
+        self.start + (step - self.start) % self.length
+    }
+
+    /// The state after `step` steps, without simulating them.
+    pub fn state_at(&self, step: usize) -> &S {
+        &self.history[self.equivalent_step(step)]
+    }
+}
+
+fn fingerprint<S>(state: &S) -> u64 where S: Hash {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Repeatedly applies `step` to `initial` until a state repeats. Only a `u64` fingerprint of each state
+/// is used as a key; states are compared in full only when fingerprints collide.
+///
+/// Never returns if the simulation does not repeat, so the state space must be finite.
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Cycle<S> where S: Hash + Eq, F: FnMut(&S) -> S {
+    let mut history: Vec<S> = vec![];
+    let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut current = initial;
+    loop {
+        let candidates = seen.entry(fingerprint(&current)).or_default();
+        if let Some(start) = candidates.iter().copied().find(|index| history[*index] == current) {
+            return Cycle {
+                start,
+                length: history.len() - start,
+                history
+            };
+        }
+
+        candidates.push(history.len());
+        let next = step(&current);
+        history.push(current);
+        current = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::cycle::find_cycle;
+
+    #[test]
+    fn finds_tail_and_loop() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> ...
+        let cycle = find_cycle(0, |state| if *state == 4 { 2 } else { state + 1 });
+        assert_eq!(2, cycle.start());
+        assert_eq!(3, cycle.length());
+        assert_eq!(&1, cycle.state_at(1));
+        assert_eq!(&4, cycle.state_at(4));
+        assert_eq!(&2, cycle.state_at(5));
+        assert_eq!(&4, cycle.state_at(1_000_000_000));
+    }
+
+    #[test]
+    fn matches_direct_simulation() {
+        let step = |state: &u64| (state * state + 7) % 1009;
+        for initial in [0, 1, 42, 1008] {
+            let cycle = find_cycle(initial, step);
+            let mut state = initial;
+            for n in 0..3000 {
+                assert_eq!(&state, cycle.state_at(n));
+                state = step(&state);
+            }
+        }
+    }
+
+    #[test]
+    fn handles_fixed_points() {
+        let cycle = find_cycle("steady", |state| *state);
+        assert_eq!(0, cycle.start());
+        assert_eq!(1, cycle.length());
+        assert_eq!(&"steady", cycle.state_at(12345));
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use crate::problems::shared::image::{Image, Rgb};
+
+/// The most codes an LZW table can hold before it has to be cleared, since codes are at most 12 bits.
+const MAX_CODES: u16 = 4096;
+
+/// Writes an animated GIF that loops forever, a frame at a time as each is added, so the frames never have
+/// to be held together. Every frame carries a table of just the colours it uses; a frame with more than
+/// 256 colours is reduced to a fixed 3-3-2 bit palette.
+pub struct AnimationEncoder<W: Write> {
+    output: W,
+    delay_centiseconds: u16,
+    size: Option<(usize, usize)>,
+    frames: usize
+}
+
+impl<W: Write> AnimationEncoder<W> {
+    pub fn new(output: W, frames_per_second: u16) -> AnimationEncoder<W> {
+        AnimationEncoder {
+            output,
+            // GIF delays are in hundredths of a second; browsers treat anything under 2 as 10, so don't go below it
+            delay_centiseconds: (100 / frames_per_second.max(1)).max(2),
+            size: None,
+            frames: 0
+        }
+    }
+
+    /// Encodes and writes out `frame`. Panics if it isn't the same size as the first frame.
+    pub fn add_frame(&mut self, frame: &Image) -> io::Result<()> {
+        let (height, width) = (frame.height(), frame.width());
+        match self.size {
+            Some(size) => assert_eq!(size, (height, width), "Every frame of an animation must be the same size"),
+            None => {
+                self.write_header(height, width)?;
+                self.size = Some((height, width));
+            }
+        }
+
+        let palette = Palette::for_image(frame);
+        let table_bits = palette.table_bits();
+
+        let mut bytes = vec![0x21, 0xf9, 0x04, 0x00];
+        bytes.extend(self.delay_centiseconds.to_le_bytes());
+        bytes.extend([0x00, 0x00]);
+
+        bytes.push(0x2c);
+        bytes.extend([0, 0, 0, 0]);
+        bytes.extend((width as u16).to_le_bytes());
+        bytes.extend((height as u16).to_le_bytes());
+        // a local colour table of 2^table_bits entries
+        bytes.push(0x80 | (table_bits - 1));
+        for index in 0..1 << table_bits {
+            let colour = palette.colours.get(index).copied().unwrap_or(Rgb::BLACK);
+            bytes.extend([colour.r, colour.g, colour.b]);
+        }
+
+        let indices: Vec<u8> = frame.pixels().iter().map(|pixel| palette.index_of(*pixel)).collect();
+        let minimum_code_size = table_bits.max(2);
+        bytes.push(minimum_code_size);
+        for block in lzw_encode(&indices, minimum_code_size).chunks(u8::MAX as usize) {
+            bytes.push(block.len() as u8);
+            bytes.extend(block);
+        }
+        bytes.push(0);
+
+        self.output.write_all(&bytes)?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Ends the animation, returning how many frames it has.
+    pub fn finish(mut self) -> io::Result<usize> {
+        if self.size.is_none() {
+            self.write_header(0, 0)?;
+        }
+
+        self.output.write_all(&[0x3b])?;
+        self.output.flush()?;
+        Ok(self.frames)
+    }
+
+    fn write_header(&mut self, height: usize, width: usize) -> io::Result<()> {
+        assert!(height <= u16::MAX as usize && width <= u16::MAX as usize, "A GIF can be at most {} pixels across", u16::MAX);
+        let mut header = b"GIF89a".to_vec();
+        header.extend((width as u16).to_le_bytes());
+        header.extend((height as u16).to_le_bytes());
+        // no global colour table, since each frame brings its own
+        header.extend([0x00, 0, 0]);
+
+        // loop forever
+        header.extend([0x21, 0xff, 0x0b]);
+        header.extend(b"NETSCAPE2.0");
+        header.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+        self.output.write_all(&header)
+    }
+}
+
+/// The colours used in a frame, in the order they first appear.
+struct Palette {
+    colours: Vec<Rgb>,
+    indices: HashMap<Rgb, u8>,
+    is_reduced: bool
+}
+
+impl Palette {
+    fn for_image(image: &Image) -> Palette {
+        let mut colours = vec![];
+        let mut indices = HashMap::new();
+        for pixel in image.pixels() {
+            if indices.contains_key(pixel) {
+                continue;
+            }
+
+            if colours.len() == 256 {
+                return Palette::reduced();
+            }
+            indices.insert(*pixel, colours.len() as u8);
+            colours.push(*pixel);
+        }
+
+        Palette {
+            colours,
+            indices,
+            is_reduced: false
+        }
+    }
+
+    /// Three bits of red and green and two of blue, for a frame with too many colours to list.
+    fn reduced() -> Palette {
+        let scale = |level: u8, levels: u8| (level as u16 * 255 / (levels as u16 - 1)) as u8;
+        let colours = (0..=255u8).map(|index| Rgb::new(scale(index >> 5, 8), scale((index >> 2) & 7, 8), scale(index & 3, 4))).collect();
+        Palette {
+            colours,
+            indices: HashMap::new(),
+            is_reduced: true
+        }
+    }
+
+    fn index_of(&self, colour: Rgb) -> u8 {
+        if self.is_reduced {
+            return (colour.r & 0xe0) | ((colour.g >> 3) & 0x1c) | (colour.b >> 6);
+        }
+
+        self.indices[&colour]
+    }
+
+    /// How many bits it takes to index every colour, which is at least one.
+    fn table_bits(&self) -> u8 {
+        let mut bits = 1;
+        while 1 << bits < self.colours.len() {
+            bits += 1;
+        }
+
+        bits
+    }
+}
+
+/// Packs codes of varying widths into bytes, least significant bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += width;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
+
+/// Compresses colour indices with the variable-width LZW scheme GIF uses.
+fn lzw_encode(indices: &[u8], minimum_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << minimum_code_size;
+    let end_of_information = clear + 1;
+
+    let mut writer = BitWriter {
+        bytes: vec![],
+        buffer: 0,
+        bits: 0
+    };
+    let mut code_size = minimum_code_size + 1;
+    let mut last_code = end_of_information;
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    writer.write(clear, code_size);
+
+    let Some((first, rest)) = indices.split_first() else {
+        writer.write(end_of_information, code_size);
+        return writer.finish();
+    };
+
+    let mut current = *first as u16;
+    for index in rest {
+        if let Some(code) = table.get(&(current, *index)) {
+            current = *code;
+            continue;
+        }
+
+        writer.write(current, code_size);
+        last_code += 1;
+        table.insert((current, *index), last_code);
+        if last_code >= 1 << code_size {
+            code_size += 1;
+        }
+
+        if last_code == MAX_CODES - 1 {
+            // the table is full, so start a fresh one
+            writer.write(clear, code_size);
+            table.clear();
+            code_size = minimum_code_size + 1;
+            last_code = end_of_information;
+        }
+
+        current = *index as u16;
+    }
+
+    writer.write(current, code_size);
+    writer.write(end_of_information, code_size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::gif::{lzw_encode, AnimationEncoder, MAX_CODES};
+    use crate::problems::shared::image::{Image, Rgb};
+
+    /// A plain GIF LZW decoder, to check the encoder against.
+    fn lzw_decode(bytes: &[u8], minimum_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << minimum_code_size;
+        let initial_table = || (0..clear).map(|i| vec![i as u8]).chain([vec![], vec![]]).collect::<Vec<Vec<u8>>>();
+        let mut table = initial_table();
+        let mut code_size = minimum_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = vec![];
+
+        let mut position = 0;
+        let mut read = |width: u8| {
+            let mut code = 0;
+            for bit in 0..width as usize {
+                let byte = bytes[(position + bit) / 8];
+                code |= (((byte >> ((position + bit) % 8)) & 1) as usize) << bit;
+            }
+            position += width as usize;
+            code
+        };
+
+        loop {
+            let code = read(code_size);
+            if code == clear {
+                table = initial_table();
+                code_size = minimum_code_size + 1;
+                previous = None;
+                continue;
+            }
+
+            if code == clear + 1 {
+                return output;
+            }
+
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.clone(), vec![previous[0]]].concat(),
+                (None, None) => panic!("Unknown code {} with nothing before it", code)
+            };
+            output.extend(&entry);
+            if let Some(previous) = previous {
+                if table.len() < MAX_CODES as usize {
+                    table.push([previous, vec![entry[0]]].concat());
+                }
+            }
+            previous = Some(entry);
+
+            if table.len() == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn lzw_round_trips() {
+        let repetitive: Vec<u8> = (0..5000).map(|i| ((i / 7) % 4) as u8).collect();
+        assert_eq!(repetitive, lzw_decode(&lzw_encode(&repetitive, 2), 2));
+
+        // enough varied data to fill the code table and force a clear
+        let noisy: Vec<u8> = (0..40_000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8).collect();
+        assert_eq!(noisy, lzw_decode(&lzw_encode(&noisy, 8), 8));
+
+        assert_eq!(Vec::<u8>::new(), lzw_decode(&lzw_encode(&[], 2), 2));
+    }
+
+    #[test]
+    fn writes_a_looping_animation() {
+        let mut first = Image::new(2, 3, Rgb::BLACK);
+        first.set(0, 0, Rgb::WHITE);
+        let second = Image::new(2, 3, Rgb::new(255, 0, 0));
+
+        let mut gif = vec![];
+        let mut encoder = AnimationEncoder::new(&mut gif, 10);
+        encoder.add_frame(&first).unwrap();
+        encoder.add_frame(&second).unwrap();
+        assert_eq!(2, encoder.finish().unwrap());
+
+        assert_eq!(b"GIF89a", &gif[..6]);
+        assert_eq!(&[3, 0, 2, 0], &gif[6..10]);
+        assert_eq!(0x00, gif[10]);
+        assert_eq!(b"NETSCAPE2.0", &gif[16..27]);
+        // the first frame's own two colours, in the order they first appear
+        assert_eq!(&[0x21, 0xf9, 0x04, 0x00, 10, 0], &gif[32..38]);
+        assert_eq!(0x80, gif[49]);
+        assert_eq!(&[255, 255, 255, 0, 0, 0], &gif[50..56]);
+        assert_eq!(2, gif.windows(4).filter(|w| *w == [0x21, 0xf9, 0x04, 0x00]).count());
+        assert_eq!(Some(&0x3b), gif.last());
+    }
+
+    #[test]
+    fn reduces_colourful_frames() {
+        let mut image = Image::new(1, 300, Rgb::BLACK);
+        for col in 0..300 {
+            image.set(0, col, Rgb::new(col as u8, (col / 2) as u8, 7));
+        }
+
+        let mut gif = vec![];
+        let mut encoder = AnimationEncoder::new(&mut gif, 20);
+        encoder.add_frame(&image).unwrap();
+        encoder.finish().unwrap();
+        assert_eq!(0x87, gif[49]);
+        // the minimum code size follows the full table
+        assert_eq!(8, gif[50 + 256 * 3]);
+    }
+
+    #[test]
+    fn writes_an_empty_animation() {
+        let mut gif = vec![];
+        assert_eq!(0, AnimationEncoder::new(&mut gif, 10).finish().unwrap());
+        assert_eq!(33, gif.len());
+        assert_eq!(Some(&0x3b), gif.last());
+    }
+}
@@ -0,0 +1,476 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A directed graph stored as adjacency lists. Nodes are addressed by the index `add_node` returns,
+/// and every edge carries a value of type `E` (a weight, a label, or `()` for none).
+#[derive(Clone, Debug)]
+pub struct DirectedGraph<N, E = ()> {
+    nodes: Vec<N>,
+    successors: Vec<Vec<(usize, E)>>,
+    predecessors: Vec<Vec<usize>>
+}
+
+impl<N, E> Default for DirectedGraph<N, E> {
+    fn default() -> Self {
+        DirectedGraph::new()
+    }
+}
+
+impl<N, E> DirectedGraph<N, E> {
+    pub fn new() -> DirectedGraph<N, E> {
+        DirectedGraph {
+            nodes: vec![],
+            successors: vec![],
+            predecessors: vec![]
+        }
+    }
+
+    /// Builds a graph from each node's outgoing edges. Nodes that only appear as an edge target are added too.
+    pub fn from_adjacency<I, J>(adjacency: I) -> DirectedGraph<N, E>
+    where
+        N: Clone + Eq + Hash,
+        I: IntoIterator<Item = (N, J)>,
+        J: IntoIterator<Item = (N, E)>
+    {
+        let mut graph = DirectedGraph::new();
+        let mut indices: HashMap<N, usize> = HashMap::new();
+        let mut index_of = |graph: &mut DirectedGraph<N, E>, node: N| {
+            *indices.entry(node.clone()).or_insert_with(|| graph.add_node(node))
+        };
+
+        for (node, edges) in adjacency {
+            let from = index_of(&mut graph, node);
+            for (target, edge) in edges {
+                let to = index_of(&mut graph, target);
+                graph.add_edge(from, to, edge);
+            }
+        }
+
+        graph
+    }
+
+    pub fn add_node(&mut self, node: N) -> usize {
+        self.nodes.push(node);
+        self.successors.push(vec![]);
+        self.predecessors.push(vec![]);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, edge: E) {
+        self.successors[from].push((to, edge));
+        self.predecessors[to].push(from);
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn node(&self, index: usize) -> &N {
+        &self.nodes[index]
+    }
+
+    #[allow(dead_code)]
+    pub fn nodes(&self) -> impl Iterator<Item = (usize, &N)> {
+        self.nodes.iter().enumerate()
+    }
+
+    /// The index of the first node equal to `node`.
+    pub fn index_of(&self, node: &N) -> Option<usize> where N: PartialEq {
+        self.nodes.iter().position(|n| n == node)
+    }
+
+    /// Every edge leaving `index`, in the order they were added.
+    pub fn successors(&self, index: usize) -> impl Iterator<Item = (usize, &E)> {
+        self.successors[index].iter().map(|(to, edge)| (*to, edge))
+    }
+
+    /// The source of every edge entering `index`. A node appears once per parallel edge.
+    pub fn predecessors(&self, index: usize) -> &[usize] {
+        &self.predecessors[index]
+    }
+
+    /// Nodes reachable from `start` in breadth-first order.
+    #[allow(dead_code)]
+    pub fn bfs(&self, start: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.node_count()];
+        let mut order = vec![];
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for (next, _) in self.successors(node) {
+                if !visited[next] {
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Nodes reachable from `start` in depth-first preorder, following edges in the order they were added.
+    #[allow(dead_code)]
+    pub fn dfs(&self, start: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.node_count()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+
+            visited[node] = true;
+            order.push(node);
+            // pushed in reverse so the first edge is explored first
+            stack.extend(self.successors[node].iter().rev().map(|(next, _)| *next).filter(|next| !visited[*next]));
+        }
+
+        order
+    }
+
+    /// Every node ordered so each edge points forwards, or `None` if the graph has a cycle.
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        // https://en.wikipedia.org/wiki/Topological_sorting#Kahn's_algorithm
+        let mut in_degrees: Vec<usize> = self.predecessors.iter().map(|p| p.len()).collect();
+        let mut ready: VecDeque<usize> = (0..self.node_count()).filter(|n| in_degrees[*n] == 0).collect();
+        let mut order = vec![];
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for (next, _) in self.successors(node) {
+                in_degrees[next] -= 1;
+                if in_degrees[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        if order.len() != self.node_count() {
+            return None;
+        }
+
+        Some(order)
+    }
+
+    /// Groups nodes that can all reach each other. Components are returned in reverse topological
+    /// order: no edge leads from a component to one listed before it.
+    #[allow(dead_code)]
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        // https://en.wikipedia.org/wiki/Tarjan%27s_strongly_connected_components_algorithm
+        // with an explicit call stack so deep graphs don't overflow the real one
+        let mut discovered_at: Vec<Option<usize>> = vec![None; self.node_count()];
+        let mut low_link = vec![0; self.node_count()];
+        let mut on_stack = vec![false; self.node_count()];
+        let mut stack = vec![];
+        let mut components = vec![];
+        let mut next_index = 0;
+
+        for root in 0..self.node_count() {
+            if discovered_at[root].is_some() {
+                continue;
+            }
+
+            let mut call_stack = vec![(root, 0)];
+            discovered_at[root] = Some(next_index);
+            low_link[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some((node, edge_position)) = call_stack.last_mut() {
+                let node = *node;
+                if let Some((next, _)) = self.successors[node].get(*edge_position) {
+                    let next = *next;
+                    *edge_position += 1;
+                    match discovered_at[next] {
+                        None => {
+                            discovered_at[next] = Some(next_index);
+                            low_link[next] = next_index;
+                            next_index += 1;
+                            stack.push(next);
+                            on_stack[next] = true;
+                            call_stack.push((next, 0));
+                        },
+                        Some(index) if on_stack[next] => low_link[node] = low_link[node].min(index),
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                call_stack.pop();
+                if let Some((parent, _)) = call_stack.last() {
+                    low_link[*parent] = low_link[*parent].min(low_link[node]);
+                }
+
+                if Some(low_link[node]) == discovered_at[node] {
+                    let mut component = vec![];
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Nodes reachable from `root` in reverse postorder, so every node comes before the nodes it leads to
+    /// (ignoring edges that close a cycle).
+    fn reverse_postorder(&self, root: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.node_count()];
+        let mut postorder = vec![];
+        let mut call_stack = vec![(root, 0)];
+        visited[root] = true;
+        while let Some((node, edge_position)) = call_stack.last_mut() {
+            let node = *node;
+            if let Some((next, _)) = self.successors[node].get(*edge_position) {
+                *edge_position += 1;
+                if !visited[*next] {
+                    visited[*next] = true;
+                    call_stack.push((*next, 0));
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            postorder.push(node);
+        }
+
+        postorder.reverse();
+        postorder
+    }
+
+    /// Builds the dominator tree of every node reachable from `root`. A node `a` dominates `b` if
+    /// every path from `root` to `b` passes through `a`.
+    pub fn dominators(&self, root: usize) -> DominatorTree {
+        // "A Simple, Fast Dominance Algorithm" by Cooper, Harvey and Kennedy
+        let order = self.reverse_postorder(root);
+        let mut order_position: Vec<Option<usize>> = vec![None; self.node_count()];
+        for (position, node) in order.iter().enumerate() {
+            order_position[*node] = Some(position);
+        }
+
+        let mut immediate: Vec<Option<usize>> = vec![None; self.node_count()];
+        immediate[root] = Some(root);
+
+        let intersect = |immediate: &[Option<usize>], mut a: usize, mut b: usize| {
+            while a != b {
+                while order_position[a] > order_position[b] {
+                    a = immediate[a].unwrap();
+                }
+                while order_position[b] > order_position[a] {
+                    b = immediate[b].unwrap();
+                }
+            }
+            a
+        };
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for node in order.iter().skip(1) {
+                let mut new_immediate = None;
+                for predecessor in self.predecessors(*node) {
+                    if immediate[*predecessor].is_none() {
+                        continue; // not processed yet, or unreachable from the root
+                    }
+
+                    new_immediate = match new_immediate {
+                        None => Some(*predecessor),
+                        Some(current) => Some(intersect(&immediate, *predecessor, current))
+                    };
+                }
+
+                if immediate[*node] != new_immediate {
+                    immediate[*node] = new_immediate;
+                    changed = true;
+                }
+            }
+        }
+
+        immediate[root] = None;
+        DominatorTree::new(root, immediate)
+    }
+
+    /// Renders the graph in Graphviz DOT format. Edges are unlabelled wherever `edge_label` returns `None`.
+    #[allow(dead_code)]
+    pub fn to_dot<FNode, FEdge>(&self, node_label: FNode, edge_label: FEdge) -> String
+    where
+        FNode: Fn(&N) -> String,
+        FEdge: Fn(&E) -> Option<String>
+    {
+        let escape = |label: String| label.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut dot = String::from("digraph {\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            dot.push_str(&format!("    {} [label=\"{}\"];\n", index, escape(node_label(node))));
+        }
+
+        for (from, edges) in self.successors.iter().enumerate() {
+            for (to, edge) in edges {
+                match edge_label(edge) {
+                    Some(label) => dot.push_str(&format!("    {} -> {} [label=\"{}\"];\n", from, to, escape(label))),
+                    None => dot.push_str(&format!("    {} -> {};\n", from, to))
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// The immediate dominator of every node reachable from a root, as produced by [`DirectedGraph::dominators`].
+pub struct DominatorTree {
+    root: usize,
+    immediate: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>
+}
+
+impl DominatorTree {
+    fn new(root: usize, immediate: Vec<Option<usize>>) -> DominatorTree {
+        let mut children = vec![vec![]; immediate.len()];
+        for (node, dominator) in immediate.iter().enumerate() {
+            if let Some(dominator) = dominator {
+                children[*dominator].push(node);
+            }
+        }
+
+        DominatorTree {
+            root,
+            immediate,
+            children
+        }
+    }
+
+    /// The closest strict dominator of `node`. `None` for the root and for unreachable nodes.
+    #[allow(dead_code)]
+    pub fn immediate_dominator(&self, node: usize) -> Option<usize> {
+        self.immediate[node]
+    }
+
+    pub fn is_reachable(&self, node: usize) -> bool {
+        node == self.root || self.immediate[node].is_some()
+    }
+
+    /// Whether every path from the root to `node` passes through `dominator`. Every node dominates itself.
+    #[allow(dead_code)]
+    pub fn dominates(&self, dominator: usize, node: usize) -> bool {
+        if !self.is_reachable(node) {
+            return false;
+        }
+
+        let mut current = Some(node);
+        while let Some(candidate) = current {
+            if candidate == dominator {
+                return true;
+            }
+            current = self.immediate[candidate];
+        }
+
+        false
+    }
+
+    /// How many nodes `node` strictly dominates, i.e. how many would be cut off from the root without it.
+    pub fn strictly_dominated_count(&self, node: usize) -> usize {
+        let mut count = 0;
+        let mut stack = self.children[node].clone();
+        while let Some(next) = stack.pop() {
+            count += 1;
+            stack.extend(&self.children[next]);
+        }
+
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::graph::DirectedGraph;
+
+    fn graph(edges: &[(&'static str, &[&'static str])]) -> DirectedGraph<&'static str> {
+        DirectedGraph::from_adjacency(edges.iter().map(|(from, to)| (*from, to.iter().map(|t| (*t, ())))))
+    }
+
+    fn labels(graph: &DirectedGraph<&'static str>, indices: &[usize]) -> Vec<&'static str> {
+        indices.iter().map(|i| *graph.node(*i)).collect()
+    }
+
+    #[test]
+    fn traverses() {
+        let graph = graph(&[("a", &["b", "c"]), ("b", &["d"]), ("c", &["d"]), ("e", &["a"])]);
+        assert_eq!(5, graph.node_count());
+        assert_eq!(vec!["a", "b", "c", "d"], labels(&graph, &graph.bfs(0)));
+        assert_eq!(vec!["a", "b", "d", "c"], labels(&graph, &graph.dfs(0)));
+        assert_eq!(vec!["b", "c"], labels(&graph, graph.predecessors(graph.index_of(&"d").unwrap())));
+
+        let order = graph.topological_order().unwrap();
+        let position = |node| order.iter().position(|i| graph.node(*i) == &node).unwrap();
+        assert!(position("e") < position("a"));
+        assert!(position("a") < position("b"));
+        assert!(position("c") < position("d"));
+
+        let cyclic = self::graph(&[("a", &["b"]), ("b", &["a"])]);
+        assert!(cyclic.topological_order().is_none());
+    }
+
+    #[test]
+    fn finds_strongly_connected_components() {
+        let graph = graph(&[("a", &["b"]), ("b", &["c", "d"]), ("c", &["a"]), ("d", &["e"]), ("e", &["d", "f"]), ("f", &[])]);
+        let components: Vec<Vec<&str>> = graph
+            .strongly_connected_components()
+            .iter()
+            .map(|c| {
+                let mut members = labels(&graph, c);
+                members.sort();
+                members
+            })
+            .collect();
+        assert_eq!(vec![vec!["f"], vec!["d", "e"], vec!["a", "b", "c"]], components);
+
+        let long_chain = DirectedGraph::<usize>::from_adjacency((0..100_000).map(|n| (n, vec![(n + 1, ())])));
+        assert_eq!(100_001, long_chain.strongly_connected_components().len());
+    }
+
+    #[test]
+    fn builds_dominator_tree() {
+        // root -> a -> {b, c} -> d -> e, with a shortcut root -> c
+        let graph = graph(&[("root", &["a", "c"]), ("a", &["b", "c"]), ("b", &["d"]), ("c", &["d"]), ("d", &["e"]), ("x", &["e"])]);
+        let index = |node| graph.index_of(&node).unwrap();
+        let tree = graph.dominators(index("root"));
+
+        assert_eq!(None, tree.immediate_dominator(index("root")));
+        assert_eq!(Some(index("root")), tree.immediate_dominator(index("c")));
+        assert_eq!(Some(index("a")), tree.immediate_dominator(index("b")));
+        assert_eq!(Some(index("root")), tree.immediate_dominator(index("d")));
+        assert_eq!(Some(index("d")), tree.immediate_dominator(index("e")));
+        assert!(tree.dominates(index("d"), index("e")));
+        assert!(!tree.dominates(index("a"), index("d")));
+        assert!(!tree.is_reachable(index("x")));
+        assert_eq!(5, tree.strictly_dominated_count(index("root")));
+        assert_eq!(1, tree.strictly_dominated_count(index("a")));
+        assert_eq!(1, tree.strictly_dominated_count(index("d")));
+    }
+
+    #[test]
+    fn exports_dot() {
+        let mut graph: DirectedGraph<&str, u32> = DirectedGraph::new();
+        let a = graph.add_node("say \"hi\"");
+        let b = graph.add_node("b");
+        graph.add_edge(a, b, 3);
+        graph.add_edge(b, a, 0);
+
+        let expected = "digraph {
+    0 [label=\"say \\\"hi\\\"\"];
+    1 [label=\"b\"];
+    0 -> 1 [label=\"3\"];
+    1 -> 0;
+}
+";
+        assert_eq!(expected, graph.to_dot(|n| n.to_string(), |e| Some(e.to_string()).filter(|_| *e > 0)));
+    }
+}
@@ -1,42 +1,308 @@
-use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
 use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::parse::{self, Line, ParseError};
 
-#[allow(dead_code)]
-struct Grid2D<TTile> {
-    grid: Vec<Vec<TTile>>
+/// A rectangular grid of tiles stored row-major.
+/// Points follow the `GridPoint2D` convention: `x` is the row and `y` is the column.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid2D<TTile> {
+    height: usize,
+    width: usize,
+    tiles: Vec<TTile>
 }
 
-pub trait GridTile {
-    fn get_adjacents(&self, x_boundary: usize, y_boundary: usize) -> Vec<GridPoint2D>;
-}
+impl<TTile> Grid2D<TTile> {
+    /// Parses one row per non-empty line, mapping each character to a tile.
+    /// Fails if any character is rejected by the mapper or the rows are not all the same width.
+    pub fn parse<F>(contents: &str, tile_mapper: F) -> Result<Grid2D<TTile>, ParseError> where F: Fn(char) -> Option<TTile> {
+        Grid2D::parse_lines(parse::lines(contents), tile_mapper)
+    }
+
+    /// Like [`Grid2D::parse`], for lines that have already been split out of a larger input.
+    pub fn parse_lines<'a, I, F>(lines: I, tile_mapper: F) -> Result<Grid2D<TTile>, ParseError>
+    where
+        I: IntoIterator<Item = Line<'a>>,
+        F: Fn(char) -> Option<TTile>
+    {
+        let mut rows: Vec<Vec<TTile>> = vec![];
+        for line in lines {
+            let mut row = vec![];
+            for (column, c) in line.text.chars().enumerate() {
+                row.push(tile_mapper(c).ok_or_else(|| line.error(format!("unexpected {:?} in column {}", c, column + 1)))?);
+            }
+
+            if let Some(first) = rows.first().filter(|first| first.len() != row.len()) {
+                return Err(line.error(format!("row is {} wide but the rows above are {} wide", row.len(), first.len())));
+            }
+            rows.push(row);
+        }
+
+        Ok(Grid2D::from_rows(rows).expect("Rows were checked to be the same width"))
+    }
+
+    /// Builds a grid from a list of rows. Returns `None` if the rows are ragged.
+    pub fn from_rows(rows: Vec<Vec<TTile>>) -> Option<Grid2D<TTile>> {
+        let height = rows.len();
+        let width = rows.first().map(|r| r.len()).unwrap_or(0);
+        if rows.iter().any(|r| r.len() != width) {
+            return None;
+        }
+
+        Some(Grid2D {
+            height,
+            width,
+            tiles: rows.into_iter().flatten().collect()
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn contains(&self, point: &GridPoint2D) -> bool {
+        point.x < self.height && point.y < self.width
+    }
+
+    pub fn get(&self, point: &GridPoint2D) -> Option<&TTile> {
+        if self.contains(point) {
+            Some(&self.tiles[point.x * self.width + point.y])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, point: &GridPoint2D) -> Option<&mut TTile> {
+        if self.contains(point) {
+            Some(&mut self.tiles[point.x * self.width + point.y])
+        } else {
+            None
+        }
+    }
+
+    /// Moves one step from `point` in `direction`, or `None` if that would leave the grid.
+    pub fn step(&self, point: &GridPoint2D, direction: Grid2dDirection) -> Option<GridPoint2D> {
+        direction.step(point, self.height, self.width)
+    }
+
+    /// The orthogonal neighbours of `point` that lie within the grid, ordered up, down, left, right.
+    pub fn get_adjacents(&self, point: &GridPoint2D) -> Vec<GridPoint2D> {
+        let mut adjacents = Vec::with_capacity(4);
+        if point.x > 0 {
+            adjacents.push(GridPoint2D::new(point.x - 1, point.y));
+        }
+
+        if point.x + 1 < self.height {
+            adjacents.push(GridPoint2D::new(point.x + 1, point.y));
+        }
+
+        if point.y > 0 {
+            adjacents.push(GridPoint2D::new(point.x, point.y - 1));
+        }
+
+        if point.y + 1 < self.width {
+            adjacents.push(GridPoint2D::new(point.x, point.y + 1));
+        }
+        adjacents
+    }
+
+    /// The orthogonal and diagonal neighbours of `point` that lie within the grid, in row-major order.
+    #[allow(dead_code)]
+    pub fn get_adjacents_with_diagonals(&self, point: &GridPoint2D) -> Vec<GridPoint2D> {
+        let mut adjacents = Vec::with_capacity(8);
+        for row in point.x.saturating_sub(1)..=(point.x + 1).min(self.height.saturating_sub(1)) {
+            for col in point.y.saturating_sub(1)..=(point.y + 1).min(self.width.saturating_sub(1)) {
+                if row != point.x || col != point.y {
+                    adjacents.push(GridPoint2D::new(row, col));
+                }
+            }
+        }
+        adjacents
+    }
+
+    pub fn row(&self, row: usize) -> &[TTile] {
+        &self.tiles[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[TTile]> {
+        // chunks panics on 0, and an empty grid has no rows anyway
+        self.tiles.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &TTile> {
+        self.tiles.iter().skip(col).step_by(self.width.max(1)).take(self.height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &TTile>> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    /// Every point in the grid, in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = GridPoint2D> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| GridPoint2D::new(row, col)))
+    }
+
+    /// Every point in the grid paired with its tile, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (GridPoint2D, &TTile)> {
+        self.points().zip(self.tiles.iter())
+    }
+
+    /// The first point, in row-major order, whose tile matches the predicate.
+    pub fn find<F>(&self, predicate: F) -> Option<GridPoint2D> where F: Fn(&TTile) -> bool {
+        self.tiles.iter().position(predicate).map(|index| GridPoint2D::new(index / self.width, index % self.width))
+    }
+
+    pub fn map<TOther, F>(&self, tile_mapper: F) -> Grid2D<TOther> where F: Fn(GridPoint2D, &TTile) -> TOther {
+        Grid2D {
+            height: self.height,
+            width: self.width,
+            tiles: self.iter().map(|(point, tile)| tile_mapper(point, tile)).collect()
+        }
+    }
+
+    fn rebuild<F>(&self, height: usize, width: usize, source_of: F) -> Grid2D<TTile> where TTile: Clone, F: Fn(usize, usize) -> GridPoint2D {
+        let mut tiles = Vec::with_capacity(self.tiles.len());
+        for row in 0..height {
+            for col in 0..width {
+                tiles.push(self[source_of(row, col)].clone());
+            }
+        }
+
+        Grid2D {
+            height,
+            width,
+            tiles
+        }
+    }
+
+    /// Mirrors the grid across its main diagonal, turning rows into columns.
+    pub fn transpose(&self) -> Grid2D<TTile> where TTile: Clone {
+        self.rebuild(self.width, self.height, |row, col| GridPoint2D::new(col, row))
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate_clockwise(&self) -> Grid2D<TTile> where TTile: Clone {
+        let height = self.height;
+        self.rebuild(self.width, self.height, |row, col| GridPoint2D::new(height - 1 - col, row))
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate_counter_clockwise(&self) -> Grid2D<TTile> where TTile: Clone {
+        let width = self.width;
+        self.rebuild(self.width, self.height, |row, col| GridPoint2D::new(col, width - 1 - row))
+    }
+
+    /// Mirrors the grid left to right.
+    #[allow(dead_code)]
+    pub fn flip_horizontal(&self) -> Grid2D<TTile> where TTile: Clone {
+        let width = self.width;
+        self.rebuild(self.height, self.width, |row, col| GridPoint2D::new(row, width - 1 - col))
+    }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-pub struct GridPointWithCost {
-    pub point: GridPoint2D,
-    pub cost: i64
+    /// Mirrors the grid top to bottom.
+    #[allow(dead_code)]
+    pub fn flip_vertical(&self) -> Grid2D<TTile> where TTile: Clone {
+        let height = self.height;
+        self.rebuild(self.height, self.width, |row, col| GridPoint2D::new(height - 1 - row, col))
+    }
 }
 
-impl GridPointWithCost {
-    pub fn new(point: GridPoint2D, cost: i64) -> GridPointWithCost {
-        GridPointWithCost {
-            point,
-            cost
+impl<TTile: Clone> Grid2D<TTile> {
+    #[allow(dead_code)]
+    pub fn new(height: usize, width: usize, tile: TTile) -> Grid2D<TTile> {
+        Grid2D {
+            height,
+            width,
+            tiles: vec![tile; height * width]
         }
     }
 }
 
-impl PartialOrd for GridPointWithCost {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let cost = self.cost;
-        let other_cost = other.cost;
-        Some(cost.cmp(&other_cost))
+impl<TTile> Index<GridPoint2D> for Grid2D<TTile> {
+    type Output = TTile;
+
+    fn index(&self, point: GridPoint2D) -> &TTile {
+        self.get(&point).expect("Point should be within the grid")
+    }
+}
+
+impl<TTile> IndexMut<GridPoint2D> for Grid2D<TTile> {
+    fn index_mut(&mut self, point: GridPoint2D) -> &mut TTile {
+        self.get_mut(&point).expect("Point should be within the grid")
     }
 }
 
-impl Ord for GridPointWithCost {
-    fn cmp(&self, other: &Self) -> Ordering {
-        let cost = self.cost;
-        let other_cost = other.cost;
-        cost.cmp(&other_cost)
+impl<TTile: fmt::Display> fmt::Display for Grid2D<TTile> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for tile in row {
+                write!(f, "{}", tile)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::grid_2d::Grid2D;
+    use crate::problems::shared::grid_point_2d::GridPoint2D;
+
+    fn parse(contents: &str) -> Grid2D<char> {
+        Grid2D::parse(contents, Some).unwrap()
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = parse("
+abc
+def
+");
+        assert_eq!(2, grid.height());
+        assert_eq!(3, grid.width());
+        assert_eq!('f', grid[GridPoint2D::new(1, 2)]);
+        assert_eq!(None, grid.get(&GridPoint2D::new(2, 0)));
+        assert_eq!("abc\ndef\n", grid.to_string());
+        assert_eq!(Some(GridPoint2D::new(1, 1)), grid.find(|t| *t == 'e'));
+    }
+
+    #[test]
+    fn rejects_ragged_rows_and_unknown_tiles() {
+        assert_eq!("line 2: row is 1 wide but the rows above are 2 wide", Grid2D::parse("ab\nc", Some).unwrap_err().to_string());
+        assert_eq!("line 3: unexpected 'x' in column 2", Grid2D::parse("ab\r\n\r\ncx", |c| if c == 'x' { None } else { Some(c) }).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn finds_adjacents() {
+        let grid = parse("abc\ndef\nghi");
+        assert_eq!(vec![GridPoint2D::new(1, 0), GridPoint2D::new(0, 1)], grid.get_adjacents(&GridPoint2D::new(0, 0)));
+        assert_eq!(4, grid.get_adjacents(&GridPoint2D::new(1, 1)).len());
+        assert_eq!(3, grid.get_adjacents_with_diagonals(&GridPoint2D::new(2, 2)).len());
+        assert_eq!(8, grid.get_adjacents_with_diagonals(&GridPoint2D::new(1, 1)).len());
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = parse("abc\ndef");
+        assert_eq!(vec!["abc", "def"], grid.rows().map(|r| r.iter().collect::<String>()).collect::<Vec<String>>());
+        assert_eq!(vec!["ad", "be", "cf"], grid.columns().map(|c| c.collect::<String>()).collect::<Vec<String>>());
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = parse("abc\ndef");
+        assert_eq!("ad\nbe\ncf\n", grid.transpose().to_string());
+        assert_eq!("da\neb\nfc\n", grid.rotate_clockwise().to_string());
+        assert_eq!("cf\nbe\nad\n", grid.rotate_counter_clockwise().to_string());
+        assert_eq!("cba\nfed\n", grid.flip_horizontal().to_string());
+        assert_eq!("def\nabc\n", grid.flip_vertical().to_string());
+        assert_eq!(grid, grid.rotate_clockwise().rotate_counter_clockwise());
+    }
+}
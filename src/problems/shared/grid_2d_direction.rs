@@ -1,4 +1,6 @@
-#[derive(PartialEq, Debug)]
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Grid2dDirection {
     Right,
     Down,
@@ -7,6 +9,12 @@ pub enum Grid2dDirection {
 }
 
 impl Grid2dDirection {
+    /// Every direction, clockwise starting from `Up`.
+    pub fn all() -> [Grid2dDirection; 4] {
+        [Grid2dDirection::Up, Grid2dDirection::Right, Grid2dDirection::Down, Grid2dDirection::Left]
+    }
+
+    /// The `(row, col)` offset of a single step in this direction.
     pub fn as_vector(&self) -> (i64, i64) {
         match self {
             Grid2dDirection::Down => (1, 0),
@@ -16,23 +24,116 @@ impl Grid2dDirection {
         }
     }
 
-    // pub fn parse(c: char) -> Option<Grid2dDirection> {
-    //     match c {
-    //         'U' => Some(Grid2dDirection::Up),
-    //         'L' => Some(Grid2dDirection::Left),
-    //         'D' => Some(Grid2dDirection::Down),
-    //         'R' => Some(Grid2dDirection::Right),
-    //         _ => None
-    //     }
-    // }
+    /// Moves one step from a signed `(row, col)` point, which can go anywhere.
+    pub fn step_signed(&self, point: (i64, i64)) -> (i64, i64) {
+        let (row_vel, col_vel) = self.as_vector();
+        (point.0 + row_vel, point.1 + col_vel)
+    }
 
-    pub fn parse_str(c: &str) -> Option<Grid2dDirection> {
+    /// Rotates 90 degrees clockwise.
+    pub fn turn_right(&self) -> Grid2dDirection {
+        match self {
+            Grid2dDirection::Up => Grid2dDirection::Right,
+            Grid2dDirection::Right => Grid2dDirection::Down,
+            Grid2dDirection::Down => Grid2dDirection::Left,
+            Grid2dDirection::Left => Grid2dDirection::Up,
+        }
+    }
+
+    /// Rotates 90 degrees counter-clockwise.
+    pub fn turn_left(&self) -> Grid2dDirection {
+        match self {
+            Grid2dDirection::Up => Grid2dDirection::Left,
+            Grid2dDirection::Left => Grid2dDirection::Down,
+            Grid2dDirection::Down => Grid2dDirection::Right,
+            Grid2dDirection::Right => Grid2dDirection::Up,
+        }
+    }
+
+    pub fn opposite(&self) -> Grid2dDirection {
+        match self {
+            Grid2dDirection::Up => Grid2dDirection::Down,
+            Grid2dDirection::Down => Grid2dDirection::Up,
+            Grid2dDirection::Left => Grid2dDirection::Right,
+            Grid2dDirection::Right => Grid2dDirection::Left,
+        }
+    }
+
+    /// Accepts `UDLR`, arrows (`^v<>`) and compass points (`NSEW`), with north being up.
+    pub fn parse(c: char) -> Option<Grid2dDirection> {
         match c {
-            "U" => Some(Grid2dDirection::Up),
-            "L" => Some(Grid2dDirection::Left),
-            "D" => Some(Grid2dDirection::Down),
-            "R" => Some(Grid2dDirection::Right),
+            'U' | '^' | 'N' => Some(Grid2dDirection::Up),
+            'L' | '<' | 'W' => Some(Grid2dDirection::Left),
+            'D' | 'v' | 'S' => Some(Grid2dDirection::Down),
+            'R' | '>' | 'E' => Some(Grid2dDirection::Right),
+            _ => None
+        }
+    }
+
+    /// The arrow (`^v<>`) pointing this way.
+    pub fn arrow(&self) -> char {
+        match self {
+            Grid2dDirection::Up => '^',
+            Grid2dDirection::Down => 'v',
+            Grid2dDirection::Left => '<',
+            Grid2dDirection::Right => '>'
+        }
+    }
+
+    /// Parses a string holding exactly one direction character. See `parse`.
+    pub fn parse_str(c: &str) -> Option<Grid2dDirection> {
+        let mut chars = c.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Grid2dDirection::parse(c),
             _ => None
         }
     }
-}
\ No newline at end of file
+
+    /// Moves one step from `point`, or `None` if that would leave a `height` by `width` grid.
+    pub fn step(&self, point: &GridPoint2D, height: usize, width: usize) -> Option<GridPoint2D> {
+        let (row_vel, col_vel) = self.as_vector();
+        let row = point.x.checked_add_signed(row_vel as isize)?;
+        let col = point.y.checked_add_signed(col_vel as isize)?;
+        if row < height && col < width {
+            Some(GridPoint2D::new(row, col))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+    use crate::problems::shared::grid_point_2d::GridPoint2D;
+
+    #[test]
+    fn turns() {
+        for direction in Grid2dDirection::all() {
+            assert_eq!(direction, direction.turn_right().turn_left());
+            assert_eq!(direction.opposite(), direction.turn_right().turn_right());
+            assert_eq!(direction.opposite(), direction.turn_left().turn_left());
+        }
+
+        assert_eq!(Grid2dDirection::Right, Grid2dDirection::Up.turn_right());
+        assert_eq!(Grid2dDirection::Left, Grid2dDirection::Up.turn_left());
+    }
+
+    #[test]
+    fn parses() {
+        assert_eq!(Some(Grid2dDirection::Up), Grid2dDirection::parse('^'));
+        assert_eq!(Some(Grid2dDirection::Down), Grid2dDirection::parse('S'));
+        assert_eq!(Some(Grid2dDirection::Right), Grid2dDirection::parse_str("R"));
+        assert_eq!(None, Grid2dDirection::parse_str("RR"));
+        assert_eq!(None, Grid2dDirection::parse('x'));
+    }
+
+    #[test]
+    fn steps_within_bounds() {
+        let origin = GridPoint2D::new(0, 0);
+        assert_eq!(None, Grid2dDirection::Up.step(&origin, 2, 2));
+        assert_eq!(None, Grid2dDirection::Left.step(&origin, 2, 2));
+        assert_eq!(Some(GridPoint2D::new(1, 0)), Grid2dDirection::Down.step(&origin, 2, 2));
+        assert_eq!(None, Grid2dDirection::Down.step(&GridPoint2D::new(1, 0), 2, 2));
+    }
+}
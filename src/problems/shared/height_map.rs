@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use crate::problems::shared::box_3d::{Axis, Box3D, Rectangle};
+
+/// Tracks the highest occupied z of every (x, y) column and which box occupies it, so stacking
+/// boxes only touches the columns under them instead of every box placed so far.
+#[derive(Clone, Debug, Default)]
+pub struct HeightMap<TId> {
+    tops: HashMap<(i64, i64), (i64, TId)>,
+    floor: i64
+}
+
+impl<TId: Copy + PartialEq> HeightMap<TId> {
+    /// An empty map where every column is occupied up to `floor`.
+    pub fn new(floor: i64) -> HeightMap<TId> {
+        HeightMap {
+            tops: HashMap::new(),
+            floor
+        }
+    }
+
+    /// The highest occupied z anywhere under `footprint`, or the floor if nothing has been placed there.
+    pub fn highest_under(&self, footprint: &Rectangle) -> i64 {
+        footprint
+            .points()
+            .filter_map(|column| self.tops.get(&column).map(|(z, _)| *z))
+            .fold(self.floor, i64::max)
+    }
+
+    /// The boxes under `footprint` whose tops are exactly at height `z`, each listed once.
+    pub fn occupants_at(&self, footprint: &Rectangle, z: i64) -> Vec<TId> {
+        let mut occupants = vec![];
+        for column in footprint.points() {
+            if let Some((top, id)) = self.tops.get(&column) {
+                if *top == z && !occupants.contains(id) {
+                    occupants.push(*id);
+                }
+            }
+        }
+
+        occupants
+    }
+
+    /// Marks the columns under `block` as occupied up to its top by `id`.
+    pub fn place(&mut self, block: &Box3D, id: TId) {
+        let top = block.max().z;
+        for column in block.project(Axis::Z).points() {
+            let entry = self.tops.entry(column).or_insert((top, id));
+            if top >= entry.0 {
+                *entry = (top, id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::box_3d::{Box3D, Rectangle};
+    use crate::problems::shared::height_map::HeightMap;
+    use crate::problems::shared::vector_3d::Vector3D;
+
+    #[test]
+    fn tracks_tops() {
+        let mut heights = HeightMap::new(0);
+        let footprint = Rectangle::new((0, 0), (2, 0));
+        assert_eq!(0, heights.highest_under(&footprint));
+
+        heights.place(&Box3D::new(Vector3D::new(0, 0, 1), Vector3D::new(0, 0, 3)), 'a');
+        heights.place(&Box3D::new(Vector3D::new(1, 0, 1), Vector3D::new(2, 0, 3)), 'b');
+        heights.place(&Box3D::new(Vector3D::new(2, 0, 1), Vector3D::new(2, 5, 1)), 'c');
+
+        assert_eq!(3, heights.highest_under(&footprint));
+        assert_eq!(vec!['a', 'b'], heights.occupants_at(&footprint, 3));
+        assert_eq!(1, heights.highest_under(&Rectangle::new((2, 1), (2, 5))));
+        assert_eq!(0, heights.highest_under(&Rectangle::new((5, 5), (6, 6))));
+    }
+}
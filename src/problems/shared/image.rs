@@ -0,0 +1,214 @@
+/// A 24-bit colour.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+    #[allow(dead_code)]
+    pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb {
+            r,
+            g,
+            b
+        }
+    }
+
+    /// The colour `fraction` of the way from `self` to `other`, with `fraction` clamped to 0..=1.
+    pub fn blend(&self, other: Rgb, fraction: f64) -> Rgb {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let mix = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * fraction).round() as u8;
+        Rgb::new(mix(self.r, other.r), mix(self.g, other.g), mix(self.b, other.b))
+    }
+}
+
+/// A picture as rows of pixels, which can be written out as a PPM or PNG file.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Image {
+    height: usize,
+    width: usize,
+    pixels: Vec<Rgb>
+}
+
+impl Image {
+    pub fn new(height: usize, width: usize, background: Rgb) -> Image {
+        Image {
+            height,
+            width,
+            pixels: vec![background; height * width]
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Panics if the pixel is outside the image.
+    #[allow(dead_code)]
+    pub fn get(&self, row: usize, col: usize) -> Rgb {
+        assert!(row < self.height && col < self.width, "({}, {}) is outside a {}x{} image", row, col, self.height, self.width);
+        self.pixels[row * self.width + col]
+    }
+
+    /// Panics if the pixel is outside the image.
+    #[allow(dead_code)]
+    pub fn set(&mut self, row: usize, col: usize, colour: Rgb) {
+        assert!(row < self.height && col < self.width, "({}, {}) is outside a {}x{} image", row, col, self.height, self.width);
+        self.pixels[row * self.width + col] = colour;
+    }
+
+    /// Fills the `size` by `size` square whose top left pixel is at (`row`, `col`), clipped to the image.
+    pub fn fill_square(&mut self, row: usize, col: usize, size: usize, colour: Rgb) {
+        for r in row..(row + size).min(self.height) {
+            for c in col..(col + size).min(self.width) {
+                self.pixels[r * self.width + c] = colour;
+            }
+        }
+    }
+
+    /// The pixels, a row at a time.
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    /// A binary (`P6`) PPM file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for pixel in self.pixels.iter() {
+            bytes.extend([pixel.r, pixel.g, pixel.b]);
+        }
+
+        bytes
+    }
+
+    /// An 8-bit RGB PNG file. The image data is stored without compression, which keeps the encoder
+    /// small at the cost of larger files.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits per channel, truecolour, default compression, filtering and no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        // every scanline starts with its filter type, which is always none
+        let mut scanlines = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            scanlines.push(0);
+            for pixel in row {
+                scanlines.extend([pixel.r, pixel.g, pixel.b]);
+            }
+        }
+
+        let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        write_png_chunk(&mut png, b"IHDR", &header);
+        write_png_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+        write_png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        // an empty final block
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        let length = block.len() as u16;
+        stream.push(is_final as u8);
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend(block);
+    }
+
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MODULUS: u32 = 65521;
+    let (mut a, mut b) = (1, 0);
+    for byte in data {
+        a = (a + *byte as u32) % MODULUS;
+        b = (b + a) % MODULUS;
+    }
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::image::{adler32, crc32, zlib_stored, Image, Rgb};
+
+    #[test]
+    fn checksums_match_known_values() {
+        assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+        assert_eq!(0x11e6_0398, adler32(b"Wikipedia"));
+    }
+
+    #[test]
+    fn writes_ppm() {
+        let mut image = Image::new(1, 2, Rgb::BLACK);
+        image.set(0, 1, Rgb::new(1, 2, 3));
+        assert_eq!(b"P6\n2 1\n255\n\x00\x00\x00\x01\x02\x03".to_vec(), image.to_ppm());
+    }
+
+    #[test]
+    fn writes_png_chunks() {
+        let mut image = Image::new(2, 3, Rgb::WHITE);
+        image.fill_square(1, 2, 4, Rgb::new(255, 0, 0));
+        assert_eq!(Rgb::new(255, 0, 0), image.get(1, 2));
+
+        let png = image.to_png();
+        assert_eq!(&[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'], &png[..8]);
+        assert_eq!(b"IHDR", &png[12..16]);
+        assert_eq!(&[0, 0, 0, 3, 0, 0, 0, 2, 8, 2, 0, 0, 0], &png[16..29]);
+        assert_eq!(b"IEND", &png[png.len() - 8..png.len() - 4]);
+    }
+
+    #[test]
+    fn splits_long_data_into_stored_blocks() {
+        let data = vec![7; 70_000];
+        let stream = zlib_stored(&data);
+        // two headers of five bytes, the zlib header and the checksum
+        assert_eq!(data.len() + 2 * 5 + 2 + 4, stream.len());
+        assert_eq!(0, stream[2]);
+        assert_eq!(1, stream[2 + 5 + 65535]);
+        assert_eq!(&[1, 0, 0, 0xff, 0xff], &zlib_stored(&[])[2..7]);
+    }
+}
@@ -0,0 +1,242 @@
+use std::ops::Range;
+
+/// A set of `u64` values stored as sorted, disjoint, non-adjacent half-open ranges.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct IntervalSet {
+    ranges: Vec<Range<u64>>
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet {
+            ranges: vec![]
+        }
+    }
+
+    pub fn from_range(range: Range<u64>) -> IntervalSet {
+        IntervalSet::from_ranges(vec![range])
+    }
+
+    /// Builds a set from ranges that may overlap, touch or be empty.
+    pub fn from_ranges<I>(ranges: I) -> IntervalSet where I: IntoIterator<Item = Range<u64>> {
+        let mut sorted: Vec<Range<u64>> = ranges.into_iter().filter(|r| !r.is_empty()).collect();
+        sorted.sort_by_key(|r| r.start);
+
+        let mut normalized: Vec<Range<u64>> = vec![];
+        for range in sorted {
+            match normalized.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => normalized.push(range)
+            }
+        }
+
+        IntervalSet {
+            ranges: normalized
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn ranges(&self) -> &[Range<u64>] {
+        &self.ranges
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of values in the set.
+    #[allow(dead_code)]
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, value: u64) -> bool {
+        self.ranges.iter().any(|r| r.contains(&value))
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::from_ranges(self.ranges.iter().chain(other.ranges.iter()).cloned())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = vec![];
+        let (mut left, mut right) = (0, 0);
+        while left < self.ranges.len() && right < other.ranges.len() {
+            let a = &self.ranges[left];
+            let b = &other.ranges[right];
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                result.push(start..end);
+            }
+
+            // advance whichever range finishes first; the other may still overlap the next one
+            if a.end < b.end {
+                left += 1;
+            } else {
+                right += 1;
+            }
+        }
+
+        IntervalSet {
+            ranges: result
+        }
+    }
+
+    /// Every value in `self` that is not in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = vec![];
+        let mut subtract = other.ranges.iter().peekable();
+        for range in &self.ranges {
+            let mut start = range.start;
+            // skip subtracted ranges that end before this one starts
+            while subtract.peek().is_some_and(|s| s.end <= start) {
+                subtract.next();
+            }
+
+            let mut remaining = subtract.clone();
+            while let Some(s) = remaining.peek().filter(|s| s.start < range.end) {
+                if s.start > start {
+                    result.push(start..s.start);
+                }
+                start = start.max(s.end);
+                remaining.next();
+            }
+
+            if start < range.end {
+                result.push(start..range.end);
+            }
+        }
+
+        IntervalSet {
+            ranges: result
+        }
+    }
+
+    /// Splits into the values below `at` and the values at or above it.
+    #[allow(dead_code)]
+    pub fn split_at(&self, at: u64) -> (IntervalSet, IntervalSet) {
+        let below = self.intersection(&IntervalSet::from_range(0..at));
+        let above = self.difference(&below);
+        (below, above)
+    }
+
+    /// Moves every value by `offset`. Returns `None` if any value would leave the `u64` range.
+    pub fn shift(&self, offset: i64) -> Option<IntervalSet> {
+        let mut ranges = vec![];
+        for range in &self.ranges {
+            ranges.push(range.start.checked_add_signed(offset)?..range.end.checked_add_signed(offset)?);
+        }
+
+        Some(IntervalSet {
+            ranges
+        })
+    }
+}
+
+/// An axis-aligned box in `N` dimensions, each side a half-open range.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct IntervalBox<const N: usize> {
+    sides: [Range<u64>; N]
+}
+
+impl<const N: usize> IntervalBox<N> {
+    pub fn new(sides: [Range<u64>; N]) -> IntervalBox<N> {
+        IntervalBox {
+            sides
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn side(&self, dimension: usize) -> &Range<u64> {
+        &self.sides[dimension]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sides.iter().any(|s| s.is_empty())
+    }
+
+    /// The number of integer points in the box.
+    pub fn volume(&self) -> u128 {
+        self.sides.iter().map(|s| (s.end.saturating_sub(s.start)) as u128).product()
+    }
+
+    /// Cuts the box along `dimension` into the part below `at` and the part at or above it.
+    /// Either part is `None` if it would be empty.
+    pub fn split_at(&self, dimension: usize, at: u64) -> (Option<IntervalBox<N>>, Option<IntervalBox<N>>) {
+        let side = &self.sides[dimension];
+        let cut = at.clamp(side.start, side.end.max(side.start));
+
+        let mut below = self.clone();
+        below.sides[dimension] = side.start..cut;
+        let mut above = self.clone();
+        above.sides[dimension] = cut..side.end;
+
+        (Some(below).filter(|b| !b.is_empty()), Some(above).filter(|b| !b.is_empty()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::interval::{IntervalBox, IntervalSet};
+
+    #[test]
+    fn normalizes() {
+        let set = IntervalSet::from_ranges(vec![5..10, 0..2, 2..3, 8..12, 20..20]);
+        assert_eq!(&[0..3, 5..12], set.ranges());
+        assert_eq!(10, set.len());
+        assert_eq!(Some(0), set.min());
+        assert!(set.contains(11));
+        assert!(!set.contains(12));
+    }
+
+    #[test]
+    fn combines() {
+        let a = IntervalSet::from_ranges(vec![0..10, 20..30]);
+        let b = IntervalSet::from_range(5..25);
+        assert_eq!(IntervalSet::from_range(0..30), a.union(&b));
+        assert_eq!(&[5..10, 20..25], a.intersection(&b).ranges());
+        assert_eq!(&[0..5, 25..30], a.difference(&b).ranges());
+        assert_eq!(IntervalSet::from_range(10..20), b.difference(&a));
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn difference_handles_many_holes() {
+        let a = IntervalSet::from_range(0..100);
+        let holes = IntervalSet::from_ranges(vec![10..20, 30..40, 90..200]);
+        assert_eq!(&[0..10, 20..30, 40..90], a.difference(&holes).ranges());
+    }
+
+    #[test]
+    fn splits_and_shifts() {
+        let set = IntervalSet::from_ranges(vec![0..10, 20..30]);
+        let (below, above) = set.split_at(25);
+        assert_eq!(&[0..10, 20..25], below.ranges());
+        assert_eq!(IntervalSet::from_range(25..30), above);
+
+        assert_eq!(&[5..15, 25..35], set.shift(5).unwrap().ranges());
+        assert_eq!(Some(IntervalSet::from_range(0..5)), IntervalSet::from_range(5..10).shift(-5));
+        assert!(set.shift(-1).is_none());
+    }
+
+    #[test]
+    fn splits_boxes() {
+        let hyper_rectangle = IntervalBox::new([1..4001, 1..4001, 1..4001, 1..4001]);
+        assert_eq!(256000000000000, hyper_rectangle.volume());
+
+        let (below, above) = hyper_rectangle.split_at(1, 1001);
+        assert_eq!(&(1..1001), below.unwrap().side(1));
+        assert_eq!(&(1001..4001), above.unwrap().side(1));
+
+        let (below, above) = hyper_rectangle.split_at(0, 1);
+        assert!(below.is_none());
+        assert_eq!(hyper_rectangle, above.unwrap());
+    }
+}
@@ -1,24 +1,238 @@
-// https://www.hackertouch.com/least-common-multiple-in-rust.html
+/// Greatest common divisor. `gcd(n, 0)` is `n`, so zero arguments are fine.
+pub fn gcd(first: usize, second: usize) -> usize {
+    gcd_u128(first as u128, second as u128) as usize
+}
+
+// https://en.wikipedia.org/wiki/Euclidean_algorithm
+pub fn gcd_u128(first: u128, second: u128) -> u128 {
+    let (mut a, mut b) = (first, second);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Least common multiple. Panics if the result does not fit in a `usize`; see [`checked_lcm`].
+#[allow(dead_code)]
 pub fn lcm(first: usize, second: usize) -> usize {
-    first * second / gcd(first, second)
+    checked_lcm(first, second).expect("lcm should fit in a usize")
 }
 
-pub fn gcd(first: usize, second: usize) -> usize {
-    let mut max = first;
-    let mut min = second;
-    if min > max {
-        let val = max;
-        max = min;
-        min = val;
+/// Least common multiple, or `None` if the result overflows. Divides before multiplying,
+/// so it only overflows when the result itself does.
+#[allow(dead_code)]
+pub fn checked_lcm(first: usize, second: usize) -> Option<usize> {
+    checked_lcm_u128(first as u128, second as u128)?.try_into().ok()
+}
+
+pub fn checked_lcm_u128(first: u128, second: u128) -> Option<u128> {
+    if first == 0 || second == 0 {
+        return Some(0);
+    }
+
+    (first / gcd_u128(first, second)).checked_mul(second)
+}
+
+/// Least common multiple of every value, or `None` if it overflows. An empty iterator gives 1.
+pub fn checked_lcm_all<I>(values: I) -> Option<u128> where I: IntoIterator<Item = u128> {
+    values.into_iter().try_fold(1, checked_lcm_u128)
+}
+
+/// Returns `(g, x, y)` where `g` is the non-negative gcd of `a` and `b` and `a * x + b * y == g`.
+// https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        return (-old_r, -old_x, -old_y);
+    }
+
+    (old_r, old_x, old_y)
+}
+
+/// The `x` in `0..modulus` with `value * x ≡ 1 (mod modulus)`, or `None` if `value` and
+/// `modulus` share a factor or `modulus` is not positive.
+pub fn mod_inverse(value: i128, modulus: i128) -> Option<i128> {
+    if modulus <= 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(value.rem_euclid(modulus), modulus);
+    if g != 1 {
+        return None;
+    }
+
+    Some(x.rem_euclid(modulus))
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair. The moduli
+/// do not need to be coprime. Returns `(x, m)` with `0 <= x < m`, where `m` is the lcm of the moduli
+/// and every solution is `x + k * m`.
+///
+/// Returns `None` if the congruences contradict each other (no solution), if a modulus is not
+/// positive, or if `m` does not fit in an `i128`.
+// https://en.wikipedia.org/wiki/Chinese_remainder_theorem#Generalization_to_non-coprime_moduli
+pub fn crt<I>(congruences: I) -> Option<(i128, i128)> where I: IntoIterator<Item = (i128, i128)> {
+    let mut solution = (0, 1);
+    for (residue, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+
+        let (current, current_modulus) = solution;
+        let residue = residue.rem_euclid(modulus);
+        let (g, _, _) = extended_gcd(current_modulus, modulus);
+        let difference = residue - current;
+        if difference % g != 0 {
+            return None;
+        }
+
+        // current + current_modulus * k ≡ residue (mod modulus), solved for k modulo modulus / g
+        let reduced_modulus = modulus / g;
+        let inverse = mod_inverse(current_modulus / g, reduced_modulus)?;
+        let k = ((difference / g).rem_euclid(reduced_modulus)).checked_mul(inverse)?.rem_euclid(reduced_modulus);
+
+        let combined_modulus = current_modulus.checked_mul(reduced_modulus)?;
+        let combined = current.checked_add(current_modulus.checked_mul(k)?)?.rem_euclid(combined_modulus);
+        solution = (combined, combined_modulus);
+    }
+
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::math::{checked_lcm, checked_lcm_all, checked_lcm_u128, crt, extended_gcd, gcd, gcd_u128, lcm, mod_inverse};
+
+    // small deterministic xorshift so the property tests are repeatable without extra dependencies
+    struct Values(u64);
+
+    impl Values {
+        fn next(&mut self, below: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % below
+        }
+    }
+
+    #[test]
+    fn gcd_divides_both_and_is_greatest() {
+        assert_eq!(7, gcd(7, 0));
+        assert_eq!(7, gcd(0, 7));
+        assert_eq!(0, gcd(0, 0));
+
+        let mut values = Values(0x2545f4914f6cdd1d);
+        for _ in 0..1000 {
+            let a = values.next(500) as usize;
+            let b = values.next(500) as usize;
+            let g = gcd(a, b);
+            assert_eq!(g, gcd(b, a));
+            if g == 0 {
+                assert_eq!((0, 0), (a, b));
+                continue;
+            }
+
+            assert_eq!(0, a % g);
+            assert_eq!(0, b % g);
+            assert!((g + 1..=a.max(b)).all(|d| !(a.is_multiple_of(d) && b.is_multiple_of(d))));
+        }
     }
 
-    loop {
-        let res = max % min;
-        if res == 0 {
-            return min;
+    #[test]
+    fn lcm_does_not_overflow_early() {
+        assert_eq!(1 << 40, lcm(1 << 40, 1 << 40));
+        assert_eq!(Some(0), checked_lcm(0, 5));
+        assert_eq!(None, checked_lcm(usize::MAX, usize::MAX - 1));
+        assert_eq!(Some(u128::MAX), checked_lcm_u128(u128::MAX, u128::MAX));
+
+        let mut values = Values(0x9e3779b97f4a7c15);
+        for _ in 0..1000 {
+            let a = values.next(1 << 32) + 1;
+            let b = values.next(1 << 32) + 1;
+            let l = checked_lcm_u128(a as u128, b as u128).unwrap();
+            assert_eq!(0, l % a as u128);
+            assert_eq!(0, l % b as u128);
+            assert_eq!(a as u128 * b as u128, l * gcd_u128(a as u128, b as u128));
         }
+    }
 
-        max = min;
-        min = res;
+    #[test]
+    fn lcm_over_iterators() {
+        assert_eq!(Some(1), checked_lcm_all(vec![]));
+        assert_eq!(Some(6), checked_lcm_all(vec![2, 3]));
+        assert_eq!(Some(2520), checked_lcm_all(1..=10));
+        assert_eq!(None, checked_lcm_all(vec![u128::MAX, u128::MAX - 1]));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn extended_gcd_satisfies_bezout() {
+        let mut values = Values(0xdeadbeefcafebabe);
+        for _ in 0..1000 {
+            let a = values.next(1 << 40) as i128 - (1 << 39);
+            let b = values.next(1 << 40) as i128 - (1 << 39);
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(gcd_u128(a.unsigned_abs(), b.unsigned_abs()) as i128, g);
+            assert_eq!(g, a * x + b * y);
+        }
+    }
+
+    #[test]
+    fn mod_inverse_inverts_coprime_values() {
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(None, mod_inverse(4, 8));
+        assert_eq!(None, mod_inverse(3, 0));
+
+        let mut values = Values(0x0123456789abcdef);
+        for _ in 0..1000 {
+            let modulus = values.next(10_000) as i128 + 1;
+            let value = values.next(1 << 20) as i128 - (1 << 19);
+            match mod_inverse(value, modulus) {
+                Some(inverse) => {
+                    assert!((0..modulus).contains(&inverse));
+                    assert_eq!(1 % modulus, (value * inverse).rem_euclid(modulus));
+                },
+                None => assert_ne!(1, gcd_u128(value.unsigned_abs(), modulus as u128))
+            }
+        }
+    }
+
+    #[test]
+    fn crt_solves_non_coprime_moduli() {
+        assert_eq!(Some((23, 105)), crt(vec![(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((10, 12)), crt(vec![(2, 4), (4, 6)]));
+        assert_eq!(None, crt(vec![(1, 4), (2, 6)]));
+        assert_eq!(Some((0, 1)), crt(vec![]));
+
+        let mut values = Values(0x1badb002feedface);
+        for _ in 0..1000 {
+            let x = values.next(1 << 30) as i128;
+            let moduli: Vec<i128> = (0..values.next(4) + 1).map(|_| values.next(1000) as i128 + 1).collect();
+            let (solution, modulus) = crt(moduli.iter().map(|m| (x % m, *m))).unwrap();
+            assert_eq!(checked_lcm_all(moduli.iter().map(|m| *m as u128)).unwrap() as i128, modulus);
+            assert_eq!(x % modulus, solution);
+        }
+    }
+
+    #[test]
+    fn crt_agrees_with_brute_force() {
+        let mut values = Values(0x5851f42d4c957f2d);
+        for _ in 0..500 {
+            let congruences: Vec<(i128, i128)> = (0..2).map(|_| {
+                let modulus = values.next(12) as i128 + 1;
+                (values.next(12) as i128, modulus)
+            }).collect();
+            let brute_force = (0..144).find(|x| congruences.iter().all(|(r, m)| x % m == r % m));
+            assert_eq!(brute_force, crt(congruences.clone()).map(|(solution, _)| solution));
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// How often a `Memo` was able to answer from what it had already computed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize
+}
+
+impl MemoStats {
+    /// The share of lookups answered from the cache, or 0 if nothing has been looked up.
+    #[allow(dead_code)]
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+
+        self.hits as f64 / lookups as f64
+    }
+}
+
+/// A cache of answers to a recursive problem. Lookups take any borrowed form of the key, so a key is only
+/// copied into the cache the first time its answer is computed.
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    stats: MemoStats
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            values: HashMap::new(),
+            stats: MemoStats::default()
+        }
+    }
+}
+
+impl<K, V> Memo<K, V> where K: Hash + Eq {
+    pub fn new() -> Memo<K, V> {
+        Memo::default()
+    }
+
+    /// The answer for `key`, computing it with `compute` if it is not cached yet. `compute` is handed the
+    /// memo so it can recurse into smaller problems.
+    pub fn get_or_compute<Q, F>(&mut self, key: &Q, compute: F) -> V
+    where K: Borrow<Q>, Q: Hash + Eq + ToOwned<Owned = K> + ?Sized, V: Clone, F: FnOnce(&mut Memo<K, V>) -> V {
+        if let Some(value) = self.values.get(key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = compute(self);
+        self.values.insert(key.to_owned(), value.clone());
+        value
+    }
+
+    /// The cached answer for `key`, without computing it. Counts towards the statistics like any other lookup.
+    #[allow(dead_code)]
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        match self.values.get(key) {
+            Some(value) => {
+                self.stats.hits += 1;
+                Some(value)
+            },
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    /// Forgets every cached answer and resets the statistics.
+    #[allow(dead_code)]
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.stats = MemoStats::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::memo::{Memo, MemoStats};
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        memo.get_or_compute(&n, |memo| {
+            if n < 2 {
+                n
+            } else {
+                fibonacci(n - 1, memo) + fibonacci(n - 2, memo)
+            }
+        })
+    }
+
+    #[test]
+    fn caches_recursive_answers() {
+        let mut memo = Memo::new();
+        assert_eq!(12586269025, fibonacci(50, &mut memo));
+        assert_eq!(51, memo.len());
+        assert_eq!(MemoStats { hits: 48, misses: 51 }, memo.stats());
+
+        assert_eq!(55, fibonacci(10, &mut memo));
+        assert_eq!(49, memo.stats().hits);
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+
+    #[test]
+    fn looks_up_by_borrowed_keys() {
+        let mut memo: Memo<String, usize> = Memo::new();
+        assert_eq!(None, memo.get("seven"));
+        assert_eq!(5, memo.get_or_compute("seven", |_| 5));
+        assert_eq!(Some(&5), memo.get("seven"));
+        assert_eq!(MemoStats { hits: 1, misses: 2 }, memo.stats());
+        assert_eq!(1.0 / 3.0, memo.stats().hit_rate());
+    }
+}
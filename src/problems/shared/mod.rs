@@ -1,4 +1,28 @@
 pub mod grid_point_2d;
 pub mod grid_2d_direction;
 pub mod grid_2d;
-pub mod math;
\ No newline at end of file
+pub mod math;
+pub mod search;
+pub mod polygon;
+pub mod interval;
+pub mod cycle;
+pub mod graph;
+pub mod union_find;
+pub mod undirected_graph;
+pub mod rational;
+pub mod vector_3d;
+pub mod box_3d;
+pub mod height_map;
+pub mod parse;
+pub mod sparse_grid;
+pub mod tiled_grid;
+pub mod bitset;
+pub mod bit_grid;
+pub mod memo;
+pub mod corridors;
+pub mod compression;
+pub mod image;
+pub mod render;
+pub mod gif;
+pub mod animation;
+pub mod aho_corasick;
\ No newline at end of file
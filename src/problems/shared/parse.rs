@@ -0,0 +1,198 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Why an input could not be parsed, and the (1-based) line it happened on.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A line of input borrowed from the original text, remembering where it came from so errors can say so.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Line<'a> {
+    /// 1-based line number within the input.
+    pub number: usize,
+    pub text: &'a str
+}
+
+impl<'a> Line<'a> {
+    pub fn new(number: usize, text: &'a str) -> Line<'a> {
+        Line {
+            number,
+            text
+        }
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.number,
+            message: message.into()
+        }
+    }
+
+    /// Another part of this line, keeping its line number.
+    pub fn with_text(&self, text: &'a str) -> Line<'a> {
+        Line::new(self.number, text.trim())
+    }
+
+    /// Parses the whole (trimmed) line as a `T`.
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError> {
+        self.text.trim().parse::<T>().map_err(|_| self.error(format!("could not parse {:?}", self.text.trim())))
+    }
+
+    /// The whitespace separated words of the line.
+    pub fn fields(&self) -> impl Iterator<Item = &'a str> {
+        self.text.split_whitespace()
+    }
+
+    /// Splits around the first `separator`, trimming both halves.
+    pub fn split_once(&self, separator: &str) -> Result<(Line<'a>, Line<'a>), ParseError> {
+        let (before, after) = self.text.split_once(separator).ok_or_else(|| self.error(format!("expected {:?}", separator)))?;
+        Ok((self.with_text(before), self.with_text(after)))
+    }
+
+    /// Splits on every `separator`, trimming each part.
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Line<'a>> + 'a {
+        let line = *self;
+        self.text.split(separator).map(move |part| line.with_text(part))
+    }
+
+    /// Strips a header like `Card 1:` or `seeds:`, returning whatever sits between the label and the
+    /// colon (`"1"` or `""`) and the rest of the line.
+    pub fn strip_label(&self, label: &str) -> Result<(&'a str, Line<'a>), ParseError> {
+        let rest = self.text.trim_start().strip_prefix(label).ok_or_else(|| self.error(format!("expected {:?}", label)))?;
+        let (identifier, rest) = rest.split_once(':').ok_or_else(|| self.error(format!("expected ':' after {:?}", label)))?;
+        Ok((identifier.trim(), self.with_text(rest)))
+    }
+
+    /// Every integer in the line, in order, ignoring whatever separates them. A `-` directly in front of
+    /// a number makes it negative unless it follows another digit (so `3-4` is 3 and 4).
+    pub fn integers<T: FromStr>(&self) -> Result<Vec<T>, ParseError> {
+        integer_tokens(self.text)
+            .map(|token| token.parse::<T>().map_err(|_| self.error(format!("{} does not fit the expected integer type", token))))
+            .collect()
+    }
+}
+
+fn integer_tokens(text: &str) -> impl Iterator<Item = &str> {
+    let bytes = text.as_bytes();
+    let mut position = 0;
+    std::iter::from_fn(move || {
+        while position < bytes.len() {
+            let is_sign = bytes[position] == b'-'
+                && bytes.get(position + 1).is_some_and(u8::is_ascii_digit)
+                && (position == 0 || !bytes[position - 1].is_ascii_digit());
+            if bytes[position].is_ascii_digit() || is_sign {
+                let start = position;
+                position += 1;
+                while position < bytes.len() && bytes[position].is_ascii_digit() {
+                    position += 1;
+                }
+                return Some(&text[start..position]);
+            }
+            position += 1;
+        }
+        None
+    })
+}
+
+/// Every non-blank line, numbered from 1, with trailing whitespace (including a Windows `\r`) removed.
+pub fn lines(contents: &str) -> impl Iterator<Item = Line<'_>> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, text)| Line::new(index + 1, text.trim_end()))
+        .filter(|line| !line.text.is_empty())
+}
+
+/// Groups of consecutive non-blank lines, as separated by one or more blank lines.
+pub fn blocks(contents: &str) -> Vec<Vec<Line<'_>>> {
+    let mut blocks: Vec<Vec<Line>> = vec![];
+    let mut current = vec![];
+    for (index, text) in contents.lines().enumerate() {
+        let text = text.trim_end();
+        if text.is_empty() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        current.push(Line::new(index + 1, text));
+    }
+
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+
+    blocks
+}
+
+/// An error for input that stops before `what` was found, pointing just past its last line.
+pub fn unexpected_end(contents: &str, what: &str) -> ParseError {
+    ParseError {
+        line: contents.lines().count() + 1,
+        message: format!("input ended before {}", what)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::parse::{blocks, lines, unexpected_end, Line, ParseError};
+
+    #[test]
+    fn numbers_lines_and_normalizes_endings() {
+        let parsed: Vec<Line> = lines("first  \r\n\r\n  second\r\nthird").collect();
+        assert_eq!(vec![Line::new(1, "first"), Line::new(3, "  second"), Line::new(4, "third")], parsed);
+    }
+
+    #[test]
+    fn groups_blocks() {
+        let grouped = blocks("\na\nb\n\n\r\nc\n\n");
+        assert_eq!(vec![vec![Line::new(2, "a"), Line::new(3, "b")], vec![Line::new(6, "c")]], grouped);
+    }
+
+    #[test]
+    fn finds_integers() {
+        let line = Line::new(7, "Time: 7  15   30 @ -2, x=-14 3-4");
+        assert_eq!(Ok(vec![7, 15, 30, -2, -14, 3, 4]), line.integers::<i64>());
+        assert_eq!(
+            Err(ParseError { line: 7, message: "-2 does not fit the expected integer type".into() }),
+            line.integers::<u64>()
+        );
+    }
+
+    #[test]
+    fn strips_labels() {
+        let (identifier, rest) = Line::new(2, "Card   1: 41 48 | 83").strip_label("Card").unwrap();
+        assert_eq!("1", identifier);
+        assert_eq!(Line::new(2, "41 48 | 83"), rest);
+
+        let (identifier, rest) = Line::new(1, "seeds: 79 14").strip_label("seeds").unwrap();
+        assert_eq!("", identifier);
+        assert_eq!(Ok(vec![79, 14]), rest.integers::<u64>());
+
+        let error = Line::new(5, "Game 1 3 blue").strip_label("Game").unwrap_err();
+        assert_eq!("line 5: expected ':' after \"Game\"", error.to_string());
+    }
+
+    #[test]
+    fn splits_and_parses() {
+        let line = Line::new(4, "jqt: rhn xhk");
+        let (name, rest) = line.split_once(":").unwrap();
+        assert_eq!(Ok("jqt".to_string()), name.parse::<String>());
+        assert_eq!(vec!["rhn", "xhk"], rest.fields().collect::<Vec<&str>>());
+        assert_eq!(vec![Line::new(4, "a"), Line::new(4, "b")], Line::new(4, "a, b").split(",").collect::<Vec<Line>>());
+        assert_eq!(4, Line::new(4, "nope").parse::<u32>().unwrap_err().line);
+        assert_eq!("line 3: input ended before the distances", unexpected_end("a\nb", "the distances").to_string());
+    }
+}
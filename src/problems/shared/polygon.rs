@@ -0,0 +1,195 @@
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+use crate::problems::shared::math::gcd;
+
+#[allow(dead_code)]
+#[derive(PartialEq, Eq, Debug)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Degenerate
+}
+
+#[allow(dead_code)]
+#[derive(PartialEq, Eq, Debug)]
+pub enum PointLocation {
+    Inside,
+    Boundary,
+    Outside
+}
+
+/// A simple polygon whose vertices all sit on integer coordinates.
+/// The closing edge from the last vertex back to the first is implied.
+///
+/// Orientation follows the usual right-handed `(x, y)` convention on the coordinates as given.
+/// Grid code passes `(row, col)`, which mirrors the picture on screen, so "clockwise" there
+/// means counter-clockwise as drawn. Interior and boundary counts do not depend on this.
+///
+/// Every computation is overflow checked and returns `None` rather than a wrong answer.
+pub struct LatticePolygon {
+    vertices: Vec<(i64, i64)>
+}
+
+impl LatticePolygon {
+    pub fn from_vertices(vertices: Vec<(i64, i64)>) -> LatticePolygon {
+        LatticePolygon {
+            vertices
+        }
+    }
+
+    /// Walks from `(0, 0)` following each `(direction, length)` step, in `(row, col)` coordinates.
+    /// Returns `None` if a coordinate overflows.
+    pub fn from_steps<I>(steps: I) -> Option<LatticePolygon> where I: IntoIterator<Item = (Grid2dDirection, i64)> {
+        let mut vertices = vec![];
+        let mut cursor: (i64, i64) = (0, 0);
+        for (direction, length) in steps {
+            vertices.push(cursor);
+            let (row_vel, col_vel) = direction.as_vector();
+            cursor = (
+                cursor.0.checked_add(row_vel.checked_mul(length)?)?,
+                cursor.1.checked_add(col_vel.checked_mul(length)?)?
+            );
+        }
+
+        Some(LatticePolygon {
+            vertices
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn vertices(&self) -> &[(i64, i64)] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    /// Twice the signed area, via the shoelace formula. Doubling keeps half-integer areas exact.
+    /// Positive for counter-clockwise vertex order.
+    /// https://en.wikipedia.org/wiki/Shoelace_formula
+    pub fn double_signed_area(&self) -> Option<i128> {
+        let mut determinant: i128 = 0;
+        for ((x1, y1), (x2, y2)) in self.edges() {
+            // ad - bc
+            let cross = (x1 as i128).checked_mul(y2 as i128)?.checked_sub((x2 as i128).checked_mul(y1 as i128)?)?;
+            determinant = determinant.checked_add(cross)?;
+        }
+        Some(determinant)
+    }
+
+    #[allow(dead_code)]
+    pub fn orientation(&self) -> Option<Orientation> {
+        Some(match self.double_signed_area()?.signum() {
+            1 => Orientation::CounterClockwise,
+            -1 => Orientation::Clockwise,
+            _ => Orientation::Degenerate
+        })
+    }
+
+    /// The number of lattice points on the boundary.
+    /// For rectilinear polygons, such as the grid days produce, this is also the ordinary perimeter.
+    pub fn perimeter(&self) -> Option<u128> {
+        let mut boundary: u128 = 0;
+        for ((x1, y1), (x2, y2)) in self.edges() {
+            let dx = x1.abs_diff(x2) as usize;
+            let dy = y1.abs_diff(y2) as usize;
+            let points_on_edge = match (dx, dy) {
+                (0, 0) => 0,
+                (0, d) | (d, 0) => d,
+                (dx, dy) => gcd(dx, dy)
+            };
+            boundary = boundary.checked_add(points_on_edge as u128)?;
+        }
+        Some(boundary)
+    }
+
+    /// The number of lattice points strictly inside the polygon, via Pick's theorem.
+    /// https://en.wikipedia.org/wiki/Pick's_theorem
+    pub fn interior_point_count(&self) -> Option<u128> {
+        // A = i + b/2 - 1
+        // 2i = 2A - b + 2
+        let double_area = self.double_signed_area()?.unsigned_abs();
+        let boundary = self.perimeter()?;
+        Some(double_area.checked_add(2)?.checked_sub(boundary)? / 2)
+    }
+
+    /// Lattice points inside or on the boundary.
+    pub fn enclosed_point_count(&self) -> Option<u128> {
+        self.interior_point_count()?.checked_add(self.perimeter()?)
+    }
+
+    /// Whether `point` is inside, outside or on the boundary of the polygon.
+    #[allow(dead_code)]
+    pub fn locate(&self, point: (i64, i64)) -> Option<PointLocation> {
+        let (px, py) = (point.0 as i128, point.1 as i128);
+        let mut inside = false;
+        for ((x1, y1), (x2, y2)) in self.edges() {
+            let (x1, y1, x2, y2) = (x1 as i128, y1 as i128, x2 as i128, y2 as i128);
+            let (dx, dy) = (x2.checked_sub(x1)?, y2.checked_sub(y1)?);
+            let (lhs, rhs) = (dx.checked_mul(py.checked_sub(y1)?)?, dy.checked_mul(px.checked_sub(x1)?)?);
+            if lhs == rhs && px >= x1.min(x2) && px <= x1.max(x2) && py >= y1.min(y2) && py <= y1.max(y2) {
+                return Some(PointLocation::Boundary);
+            }
+
+            // cast a ray towards +y and count the edges it crosses; the edge's y at px is
+            // y1 + (px - x1) * (y2 - y1) / (x2 - x1), compared without dividing
+            if (x1 > px) != (x2 > px) && ((x2 > x1 && lhs < rhs) || (x2 < x1 && lhs > rhs)) {
+                inside = !inside;
+            }
+        }
+
+        Some(if inside { PointLocation::Inside } else { PointLocation::Outside })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+    use crate::problems::shared::polygon::{LatticePolygon, Orientation, PointLocation};
+
+    #[test]
+    fn measures_a_square() {
+        let square = LatticePolygon::from_vertices(vec![(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(Some(32), square.double_signed_area());
+        assert_eq!(Some(Orientation::CounterClockwise), square.orientation());
+        assert_eq!(Some(16), square.perimeter());
+        assert_eq!(Some(9), square.interior_point_count());
+        assert_eq!(Some(25), square.enclosed_point_count());
+    }
+
+    #[test]
+    fn measures_a_triangle_with_diagonal_edges() {
+        let triangle = LatticePolygon::from_vertices(vec![(0, 0), (0, 4), (4, 0)]);
+        assert_eq!(Some(-16), triangle.double_signed_area());
+        assert_eq!(Some(Orientation::Clockwise), triangle.orientation());
+        assert_eq!(Some(12), triangle.perimeter());
+        assert_eq!(Some(3), triangle.interior_point_count());
+    }
+
+    #[test]
+    fn builds_from_steps() {
+        let square = LatticePolygon::from_steps(vec![
+            (Grid2dDirection::Right, 2),
+            (Grid2dDirection::Down, 2),
+            (Grid2dDirection::Left, 2),
+            (Grid2dDirection::Up, 2)
+        ]).unwrap();
+        assert_eq!(&[(0, 0), (0, 2), (2, 2), (2, 0)], square.vertices());
+        assert_eq!(Some(9), square.enclosed_point_count());
+        assert!(LatticePolygon::from_steps(vec![(Grid2dDirection::Right, i64::MAX), (Grid2dDirection::Right, 1)]).is_none());
+    }
+
+    #[test]
+    fn locates_points() {
+        let shape = LatticePolygon::from_vertices(vec![(0, 0), (4, 0), (4, 4), (2, 2), (0, 4)]);
+        assert_eq!(Some(PointLocation::Inside), shape.locate((1, 1)));
+        assert_eq!(Some(PointLocation::Boundary), shape.locate((3, 3)));
+        assert_eq!(Some(PointLocation::Boundary), shape.locate((0, 2)));
+        assert_eq!(Some(PointLocation::Outside), shape.locate((2, 3)));
+        assert_eq!(Some(PointLocation::Outside), shape.locate((5, 1)));
+
+        let huge = LatticePolygon::from_vertices(vec![(i64::MIN, i64::MIN), (i64::MAX, i64::MIN), (i64::MAX, i64::MAX)]);
+        assert_eq!(None, huge.locate((i64::MIN, i64::MAX)));
+    }
+}
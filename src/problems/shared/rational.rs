@@ -0,0 +1,183 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use crate::problems::shared::math::gcd_u128;
+
+/// An exact fraction backed by `i128`, always stored in lowest terms with a positive denominator.
+/// Arithmetic panics rather than silently wrapping if an intermediate value leaves the `i128` range.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numerator: 0, denominator: 1 };
+
+    /// Panics if `denominator` is zero.
+    pub fn new(numerator: i128, denominator: i128) -> Rational {
+        assert_ne!(0, denominator, "A rational cannot have a zero denominator");
+        let divisor = gcd_u128(numerator.unsigned_abs(), denominator.unsigned_abs()) as i128;
+        let sign = denominator.signum();
+        Rational {
+            numerator: sign * (numerator / divisor),
+            denominator: sign * (denominator / divisor)
+        }
+    }
+
+    pub fn from_integer(value: i128) -> Rational {
+        Rational {
+            numerator: value,
+            denominator: 1
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    #[allow(dead_code)]
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    #[allow(dead_code)]
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    /// The value as an integer, or `None` if it has a fractional part.
+    pub fn to_integer(self) -> Option<i128> {
+        Some(self.numerator).filter(|_| self.denominator == 1)
+    }
+}
+
+fn checked(value: Option<i128>) -> i128 {
+    value.expect("Rational arithmetic overflowed an i128")
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Rational {
+        Rational::from_integer(value as i128)
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        // divide out the shared part of the denominators first so the products stay small
+        let shared = gcd_u128(self.denominator as u128, other.denominator as u128) as i128;
+        let numerator = checked(self.numerator.checked_mul(other.denominator / shared))
+            .checked_add(checked(other.numerator.checked_mul(self.denominator / shared)));
+        Rational::new(checked(numerator), checked((self.denominator / shared).checked_mul(other.denominator)))
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            numerator: -self.numerator,
+            denominator: self.denominator
+        }
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        // cross-cancel before multiplying so the products stay small
+        let first = Rational::new(self.numerator, other.denominator);
+        let second = Rational::new(other.numerator, self.denominator);
+        Rational::new(
+            checked(first.numerator.checked_mul(second.numerator)),
+            checked(first.denominator.checked_mul(second.denominator))
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// Panics when dividing by zero.
+    fn div(self, other: Rational) -> Rational {
+        assert!(!other.is_zero(), "Cannot divide a rational by zero");
+        self * Rational::new(other.denominator, other.numerator)
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self - *other).numerator.cmp(&0)
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator == 1 {
+            return write!(f, "{}", self.numerator);
+        }
+
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::rational::Rational;
+
+    #[test]
+    fn normalizes() {
+        assert_eq!(Rational::new(1, 2), Rational::new(-3, -6));
+        assert_eq!(Rational::new(-1, 2), Rational::new(2, -4));
+        assert_eq!(Rational::ZERO, Rational::new(0, -7));
+        assert_eq!("-1/2", Rational::new(3, -6).to_string());
+        assert_eq!("4", Rational::new(8, 2).to_string());
+        assert_eq!(Some(4), Rational::new(8, 2).to_integer());
+        assert_eq!(None, Rational::new(8, 3).to_integer());
+    }
+
+    #[test]
+    fn does_arithmetic() {
+        let half = Rational::new(1, 2);
+        let third = Rational::new(1, 3);
+        assert_eq!(Rational::new(5, 6), half + third);
+        assert_eq!(Rational::new(1, 6), half - third);
+        assert_eq!(Rational::new(1, 6), half * third);
+        assert_eq!(Rational::new(3, 2), half / third);
+        assert_eq!(Rational::new(-1, 2), -half);
+        assert!(third < half);
+        assert!(-half < third);
+    }
+
+    #[test]
+    fn stays_exact_for_large_values() {
+        // 10^30 / 3 is far beyond what an f64 can hold exactly
+        let big = Rational::new(10i128.pow(30), 3);
+        assert_eq!(Rational::from_integer(10i128.pow(30)), big * Rational::from(3));
+        assert_eq!(Rational::from_integer(1), big / big);
+        assert_eq!(Rational::new(1, 3), big - Rational::from_integer(10i128.pow(30) / 3));
+    }
+}
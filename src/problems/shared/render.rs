@@ -0,0 +1,289 @@
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+use crate::problems::shared::image::{Image, Rgb};
+
+/// How one cell of a drawing looks: the character used for text and the colour used for terminals and images.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Rgb
+}
+
+impl Cell {
+    pub const fn new(glyph: char, colour: Rgb) -> Cell {
+        Cell {
+            glyph,
+            colour
+        }
+    }
+}
+
+/// A puzzle that can be drawn as a grid of cells.
+pub trait Render {
+    /// How many rows and columns the drawing has.
+    fn render_size(&self) -> (usize, usize);
+
+    /// How the cell at `point` looks, before any overlay is drawn over it.
+    fn render_cell(&self, point: GridPoint2D) -> Cell;
+
+    /// Draws every cell onto a canvas, ready for overlays and output.
+    fn canvas(&self) -> Canvas {
+        let (height, width) = self.render_size();
+        Canvas {
+            cells: Grid2D::new(height, width, Cell::new(' ', Rgb::BLACK)).map(|point, _| self.render_cell(point))
+        }
+    }
+}
+
+/// A set of cells to draw attention to, such as a path, energized tiles or reachable plots.
+#[derive(Clone, Debug)]
+pub struct Overlay {
+    points: Vec<GridPoint2D>,
+    glyph: Option<char>,
+    colour: Rgb
+}
+
+impl Overlay {
+    /// Recolours `points`, keeping whatever character they already have.
+    pub fn new<I>(points: I, colour: Rgb) -> Overlay where I: IntoIterator<Item = GridPoint2D> {
+        Overlay {
+            points: points.into_iter().collect(),
+            glyph: None,
+            colour
+        }
+    }
+
+    /// Also replaces the character of every point with `glyph`.
+    #[allow(dead_code)]
+    pub fn with_glyph(mut self, glyph: char) -> Overlay {
+        self.glyph = Some(glyph);
+        self
+    }
+}
+
+/// A drawing of a grid, with any overlays already applied.
+#[derive(Clone, Debug)]
+pub struct Canvas {
+    cells: Grid2D<Cell>
+}
+
+impl Canvas {
+    /// Draws `overlay` over the canvas. Points outside the canvas are ignored.
+    pub fn with_overlay(mut self, overlay: &Overlay) -> Canvas {
+        for point in overlay.points.iter() {
+            if let Some(cell) = self.cells.get_mut(point) {
+                cell.glyph = overlay.glyph.unwrap_or(cell.glyph);
+                cell.colour = overlay.colour;
+            }
+        }
+
+        self
+    }
+
+    /// Adds row numbers down the left and column numbers, written top to bottom, across the top, counting
+    /// from zero like `GridPoint2D`.
+    pub fn with_rulers(self) -> Canvas {
+        const RULER: Rgb = Rgb::new(110, 110, 110);
+        let digits = |n: usize| n.max(1).ilog10() as usize + 1;
+        let (height, width) = (self.cells.height(), self.cells.width());
+        let label_width = digits(height.saturating_sub(1)) + 1;
+        let label_height = digits(width.saturating_sub(1));
+
+        let mut cells = Grid2D::new(height + label_height, width + label_width, Cell::new(' ', RULER));
+        for col in 0..width {
+            let label = format!("{:>label_height$}", col);
+            for (row, c) in label.chars().enumerate() {
+                cells[GridPoint2D::new(row, col + label_width)] = Cell::new(c, RULER);
+            }
+        }
+
+        for row in 0..height {
+            let label = format!("{:>width$} ", row, width = label_width - 1);
+            for (col, c) in label.chars().enumerate() {
+                cells[GridPoint2D::new(row + label_height, col)] = Cell::new(c, RULER);
+            }
+        }
+
+        for (point, cell) in self.cells.iter() {
+            cells[GridPoint2D::new(point.x + label_height, point.y + label_width)] = *cell;
+        }
+
+        Canvas {
+            cells
+        }
+    }
+
+    /// `canvases` drawn one above another with a blank row between each, as wide as the widest.
+    pub fn stacked<I>(canvases: I) -> Canvas where I: IntoIterator<Item = Canvas> {
+        let canvases: Vec<Canvas> = canvases.into_iter().collect();
+        let height = canvases.iter().map(|canvas| canvas.cells.height() + 1).sum::<usize>().saturating_sub(1);
+        let width = canvases.iter().map(|canvas| canvas.cells.width()).max().unwrap_or(0);
+
+        let mut cells = Grid2D::new(height, width, Cell::new(' ', Rgb::BLACK));
+        let mut top = 0;
+        for canvas in canvases.iter() {
+            for (point, cell) in canvas.cells.iter() {
+                cells[GridPoint2D::new(point.x + top, point.y)] = *cell;
+            }
+            top += canvas.cells.height() + 1;
+        }
+
+        Canvas {
+            cells
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn cell(&self, point: &GridPoint2D) -> Option<&Cell> {
+        self.cells.get(point)
+    }
+
+    /// The characters of every cell, one line per row.
+    pub fn to_text(&self) -> String {
+        let mut text = String::with_capacity(self.cells.height() * (self.cells.width() + 1));
+        for row in self.cells.rows() {
+            text.extend(row.iter().map(|cell| cell.glyph));
+            text.push('\n');
+        }
+
+        text
+    }
+
+    /// Like `to_text`, with each character coloured by 24-bit ANSI escape codes. Runs of the same colour
+    /// share an escape code, and every line resets the colour at its end.
+    pub fn to_ansi(&self) -> String {
+        let mut text = String::new();
+        for row in self.cells.rows() {
+            let mut current = None;
+            for cell in row {
+                if current != Some(cell.colour) {
+                    text.push_str(&format!("\x1b[38;2;{};{};{}m", cell.colour.r, cell.colour.g, cell.colour.b));
+                    current = Some(cell.colour);
+                }
+                text.push(cell.glyph);
+            }
+            text.push_str("\x1b[0m\n");
+        }
+
+        text
+    }
+
+    /// Like `to_ansi`, as an HTML `pre` block on a black background with a `span` for each run of colour.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<pre style=\"background:#000000\">");
+        for row in self.cells.rows() {
+            let mut current = None;
+            for cell in row {
+                if current != Some(cell.colour) {
+                    if current.is_some() {
+                        html.push_str("</span>");
+                    }
+                    html.push_str(&format!("<span style=\"color:#{:02x}{:02x}{:02x}\">", cell.colour.r, cell.colour.g, cell.colour.b));
+                    current = Some(cell.colour);
+                }
+
+                match cell.glyph {
+                    '<' => html.push_str("&lt;"),
+                    '>' => html.push_str("&gt;"),
+                    '&' => html.push_str("&amp;"),
+                    c => html.push(c)
+                }
+            }
+
+            if current.is_some() {
+                html.push_str("</span>");
+            }
+            html.push('\n');
+        }
+
+        html.push_str("</pre>\n");
+        html
+    }
+
+    /// A picture with each cell drawn as a `scale` by `scale` square of its colour.
+    pub fn to_image(&self, scale: usize) -> Image {
+        let mut image = Image::new(self.cells.height() * scale, self.cells.width() * scale, Rgb::BLACK);
+        for (point, cell) in self.cells.iter() {
+            image.fill_square(point.x * scale, point.y * scale, scale, cell.colour);
+        }
+
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::grid_point_2d::GridPoint2D;
+    use crate::problems::shared::image::Rgb;
+    use crate::problems::shared::render::{Canvas, Cell, Overlay, Render};
+
+    struct Checkerboard;
+
+    impl Render for Checkerboard {
+        fn render_size(&self) -> (usize, usize) {
+            (2, 3)
+        }
+
+        fn render_cell(&self, point: GridPoint2D) -> Cell {
+            if (point.x + point.y).is_multiple_of(2) {
+                Cell::new('#', Rgb::WHITE)
+            } else {
+                Cell::new('.', Rgb::BLACK)
+            }
+        }
+    }
+
+    #[test]
+    fn draws_text_with_overlays() {
+        assert_eq!("#.#\n.#.\n", Checkerboard.canvas().to_text());
+
+        let path = Overlay::new([GridPoint2D::new(0, 1), GridPoint2D::new(1, 1), GridPoint2D::new(5, 5)], Rgb::new(255, 0, 0)).with_glyph('*');
+        let canvas = Checkerboard.canvas().with_overlay(&path);
+        assert_eq!("#*#\n.*.\n", canvas.to_text());
+        assert_eq!(Some(&Cell::new('*', Rgb::new(255, 0, 0))), canvas.cell(&GridPoint2D::new(1, 1)));
+
+        let recoloured = Checkerboard.canvas().with_overlay(&Overlay::new([GridPoint2D::new(0, 0)], Rgb::BLACK));
+        assert_eq!(Some(&Cell::new('#', Rgb::BLACK)), recoloured.cell(&GridPoint2D::new(0, 0)));
+    }
+
+    #[test]
+    fn draws_ansi_runs() {
+        let ansi = Checkerboard.canvas().with_overlay(&Overlay::new([GridPoint2D::new(0, 1)], Rgb::WHITE)).to_ansi();
+        assert!(ansi.starts_with("\x1b[38;2;255;255;255m#.#\x1b[0m\n"));
+        assert!(ansi.ends_with("\x1b[38;2;0;0;0m.\x1b[38;2;255;255;255m#\x1b[38;2;0;0;0m.\x1b[0m\n"));
+    }
+
+    #[test]
+    fn draws_rulers() {
+        let ruled = Checkerboard.canvas().with_rulers().to_text();
+        assert_eq!("  012\n0 #.#\n1 .#.\n", ruled);
+    }
+
+    #[test]
+    fn stacks_canvases() {
+        let narrow = Checkerboard.canvas().with_overlay(&Overlay::new([GridPoint2D::new(0, 0)], Rgb::WHITE).with_glyph('x'));
+        let stacked = Canvas::stacked([narrow, Checkerboard.canvas().with_rulers()]);
+        assert_eq!("x.#  \n.#.  \n     \n  012\n0 #.#\n1 .#.\n", stacked.to_text());
+        assert_eq!("", Canvas::stacked([]).to_text());
+    }
+
+    #[test]
+    fn draws_html_runs() {
+        let html = Checkerboard.canvas().with_overlay(&Overlay::new([GridPoint2D::new(0, 0)], Rgb::new(255, 0, 0)).with_glyph('<')).to_html();
+        assert_eq!(concat!(
+            "<pre style=\"background:#000000\">",
+            "<span style=\"color:#ff0000\">&lt;</span><span style=\"color:#000000\">.</span><span style=\"color:#ffffff\">#</span>\n",
+            "<span style=\"color:#000000\">.</span><span style=\"color:#ffffff\">#</span><span style=\"color:#000000\">.</span>\n",
+            "</pre>\n"
+        ), html);
+    }
+
+    #[test]
+    fn draws_scaled_images() {
+        let image = Checkerboard.canvas().to_image(2);
+        assert_eq!((4, 6), (image.height(), image.width()));
+        assert_eq!(Rgb::WHITE, image.get(1, 1));
+        assert_eq!(Rgb::BLACK, image.get(1, 2));
+        assert_eq!(Rgb::WHITE, image.get(3, 3));
+    }
+}
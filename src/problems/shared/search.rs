@@ -0,0 +1,207 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// The outcome of a successful search.
+#[allow(dead_code)]
+pub struct SearchResult<TState> {
+    /// Total cost of the cheapest path to the goal.
+    pub cost: u64,
+    /// Every state along the cheapest path, from the start to the goal inclusive.
+    pub path: Vec<TState>,
+    /// The best known cost to every state discovered before the goal was reached.
+    pub distances: HashMap<TState, u64>
+}
+
+/// Bookkeeping shared by every search: states are interned into indices so the
+/// priority queue only ever orders integers and `TState` never needs to implement `Ord`.
+struct SearchSpace<TState> {
+    indices: HashMap<TState, usize>,
+    states: Vec<TState>,
+    costs: Vec<u64>,
+    parents: Vec<Option<usize>>
+}
+
+impl<TState: Clone + Eq + Hash> SearchSpace<TState> {
+    fn new() -> SearchSpace<TState> {
+        SearchSpace {
+            indices: HashMap::new(),
+            states: vec![],
+            costs: vec![],
+            parents: vec![]
+        }
+    }
+
+    /// Records `state` as reachable for `cost` via `parent`.
+    /// Returns its index if this is the cheapest way found to reach it so far.
+    fn relax(&mut self, state: TState, cost: u64, parent: Option<usize>) -> Option<usize> {
+        if let Some(&index) = self.indices.get(&state) {
+            if cost >= self.costs[index] {
+                return None;
+            }
+            self.costs[index] = cost;
+            self.parents[index] = parent;
+            return Some(index);
+        }
+
+        let index = self.states.len();
+        self.indices.insert(state.clone(), index);
+        self.states.push(state);
+        self.costs.push(cost);
+        self.parents.push(parent);
+        Some(index)
+    }
+
+    fn path_to(&self, index: usize) -> Vec<TState> {
+        let mut path = vec![];
+        let mut current = Some(index);
+        while let Some(index) = current {
+            path.push(self.states[index].clone());
+            current = self.parents[index];
+        }
+        path.reverse();
+        path
+    }
+
+    fn into_distances(self) -> HashMap<TState, u64> {
+        self.states.into_iter().zip(self.costs).collect()
+    }
+}
+
+/// Finds the cheapest path from `start` to any state satisfying `is_goal` using A*.
+/// `successors` yields each neighbouring state with the cost of moving to it, and
+/// `heuristic` must never overestimate the remaining cost or the result may not be optimal.
+/// Returns `None` if no goal is reachable.
+pub fn a_star<TState, FSuccessors, TSuccessors, FHeuristic, FGoal>(
+    start: TState,
+    mut successors: FSuccessors,
+    heuristic: FHeuristic,
+    is_goal: FGoal
+) -> Option<SearchResult<TState>>
+where
+    TState: Clone + Eq + Hash,
+    FSuccessors: FnMut(&TState) -> TSuccessors,
+    TSuccessors: IntoIterator<Item = (TState, u64)>,
+    FHeuristic: Fn(&TState) -> u64,
+    FGoal: Fn(&TState) -> bool
+{
+    let mut space = SearchSpace::new();
+    let mut frontier = BinaryHeap::new();
+    let start_index = space.relax(start.clone(), 0, None).unwrap();
+    frontier.push(Reverse((heuristic(&start), 0, start_index)));
+
+    while let Some(Reverse((_, cost, index))) = frontier.pop() {
+        if cost > space.costs[index] {
+            continue; // a cheaper route to this state was already expanded
+        }
+
+        if is_goal(&space.states[index]) {
+            let path = space.path_to(index);
+            return Some(SearchResult {
+                cost,
+                path,
+                distances: space.into_distances()
+            });
+        }
+
+        let state = space.states[index].clone();
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            let priority = next_cost + heuristic(&next);
+            if let Some(next_index) = space.relax(next, next_cost, Some(index)) {
+                frontier.push(Reverse((priority, next_cost, next_index)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the cheapest path from `start` to any state satisfying `is_goal` using Dijkstra's algorithm.
+#[allow(dead_code)]
+pub fn dijkstra<TState, FSuccessors, TSuccessors, FGoal>(start: TState, successors: FSuccessors, is_goal: FGoal) -> Option<SearchResult<TState>>
+where
+    TState: Clone + Eq + Hash,
+    FSuccessors: FnMut(&TState) -> TSuccessors,
+    TSuccessors: IntoIterator<Item = (TState, u64)>,
+    FGoal: Fn(&TState) -> bool
+{
+    a_star(start, successors, |_| 0, is_goal)
+}
+
+/// Computes the cheapest cost from `start` to every state reachable for at most `max_cost`.
+pub fn dijkstra_distances<TState, FSuccessors, TSuccessors>(start: TState, max_cost: u64, mut successors: FSuccessors) -> HashMap<TState, u64>
+where
+    TState: Clone + Eq + Hash,
+    FSuccessors: FnMut(&TState) -> TSuccessors,
+    TSuccessors: IntoIterator<Item = (TState, u64)>
+{
+    let mut space = SearchSpace::new();
+    let mut frontier = BinaryHeap::new();
+    frontier.push(Reverse((0, space.relax(start, 0, None).unwrap())));
+
+    while let Some(Reverse((cost, index))) = frontier.pop() {
+        if cost > space.costs[index] {
+            continue;
+        }
+
+        let state = space.states[index].clone();
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost.saturating_add(step_cost);
+            if next_cost > max_cost {
+                continue;
+            }
+            if let Some(next_index) = space.relax(next, next_cost, Some(index)) {
+                frontier.push(Reverse((next_cost, next_index)));
+            }
+        }
+    }
+
+    space.into_distances()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::search::{a_star, dijkstra, dijkstra_distances};
+
+    // 0 -1-> 1 -1-> 2 -1-> 3, plus a direct but expensive 0 -5-> 3
+    fn successors(node: &u32) -> Vec<(u32, u64)> {
+        match node {
+            0 => vec![(1, 1), (3, 5)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![]
+        }
+    }
+
+    #[test]
+    fn finds_cheapest_path() {
+        let result = dijkstra(0, successors, |n| *n == 3).unwrap();
+        assert_eq!(3, result.cost);
+        assert_eq!(vec![0, 1, 2, 3], result.path);
+        assert_eq!(Some(&2), result.distances.get(&2));
+    }
+
+    #[test]
+    fn a_star_with_admissible_heuristic_matches_dijkstra() {
+        let result = a_star(0, successors, |n| 3 - (*n as u64).min(3), |n| *n == 3).unwrap();
+        assert_eq!(3, result.cost);
+        assert_eq!(vec![0, 1, 2, 3], result.path);
+    }
+
+    #[test]
+    fn reports_unreachable_goals() {
+        assert!(dijkstra(1, successors, |n| *n == 0).is_none());
+    }
+
+    #[test]
+    fn computes_all_distances() {
+        let distances = dijkstra_distances(0, u64::MAX, successors);
+        assert_eq!(4, distances.len());
+        assert_eq!(Some(&3), distances.get(&3));
+
+        let nearby = dijkstra_distances(0, 2, successors);
+        assert_eq!(3, nearby.len());
+        assert_eq!(None, nearby.get(&3));
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::fmt;
+use crate::problems::shared::box_3d::Rectangle;
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+
+/// A grid without edges, keyed by signed `(row, col)` points, that only stores the tiles that have been set.
+/// It keeps track of the smallest rectangle covering every stored tile so it can be drawn.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<TTile> {
+    tiles: HashMap<(i64, i64), TTile>,
+    bounds: Option<Rectangle>
+}
+
+impl<TTile> Default for SparseGrid<TTile> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<TTile> SparseGrid<TTile> {
+    pub fn new() -> SparseGrid<TTile> {
+        SparseGrid {
+            tiles: HashMap::new(),
+            bounds: None
+        }
+    }
+
+    /// Sets the tile at `point`, returning whatever was there before.
+    pub fn insert(&mut self, point: (i64, i64), tile: TTile) -> Option<TTile> {
+        self.bounds = Some(match self.bounds {
+            Some(bounds) => bounds.including(point),
+            None => Rectangle::new(point, point)
+        });
+        self.tiles.insert(point, tile)
+    }
+
+    /// Clears the tile at `point`, shrinking the bounds if it was on their edge.
+    #[allow(dead_code)]
+    pub fn remove(&mut self, point: &(i64, i64)) -> Option<TTile> {
+        let removed = self.tiles.remove(point)?;
+        let on_edge = self.bounds.is_some_and(|b| point.0 == b.min.0 || point.0 == b.max.0 || point.1 == b.min.1 || point.1 == b.max.1);
+        if on_edge {
+            self.bounds = self.tiles.keys().fold(None, |bounds: Option<Rectangle>, p| {
+                Some(bounds.map_or(Rectangle::new(*p, *p), |b| b.including(*p)))
+            });
+        }
+
+        Some(removed)
+    }
+
+    #[allow(dead_code)]
+    pub fn get(&self, point: &(i64, i64)) -> Option<&TTile> {
+        self.tiles.get(point)
+    }
+
+    #[allow(dead_code)]
+    pub fn get_mut(&mut self, point: &(i64, i64)) -> Option<&mut TTile> {
+        self.tiles.get_mut(point)
+    }
+
+    pub fn contains(&self, point: &(i64, i64)) -> bool {
+        self.tiles.contains_key(point)
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// The stored tiles, in no particular order.
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), &TTile)> {
+        self.tiles.iter().map(|(point, tile)| (*point, tile))
+    }
+
+    /// The smallest rectangle covering every stored tile, or `None` if the grid is empty.
+    pub fn bounds(&self) -> Option<Rectangle> {
+        self.bounds
+    }
+
+    /// The four points next to `point`; they always exist since the grid has no edges.
+    pub fn get_adjacents(&self, point: &(i64, i64)) -> [(i64, i64); 4] {
+        Grid2dDirection::all().map(|direction| direction.step_signed(*point))
+    }
+
+    /// Draws the bounded area a row at a time, using `empty` wherever no tile is stored.
+    #[allow(dead_code)]
+    pub fn render(&self, empty: char) -> String where TTile: fmt::Display {
+        let Some(bounds) = self.bounds else {
+            return String::new();
+        };
+
+        let mut rendered = String::new();
+        for row in bounds.min.0..=bounds.max.0 {
+            for col in bounds.min.1..=bounds.max.1 {
+                match self.tiles.get(&(row, col)) {
+                    Some(tile) => rendered.push_str(&tile.to_string()),
+                    None => rendered.push(empty)
+                }
+            }
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::box_3d::Rectangle;
+    use crate::problems::shared::sparse_grid::SparseGrid;
+
+    #[test]
+    fn tracks_bounds_through_negative_coordinates() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(None, grid.bounds());
+        assert_eq!("", grid.render('.'));
+
+        grid.insert((0, 0), '#');
+        grid.insert((-2, 3), '#');
+        grid.insert((1, -1), 'S');
+        assert_eq!(Some(Rectangle::new((-2, -1), (1, 3))), grid.bounds());
+        assert_eq!(Some(&'S'), grid.get(&(1, -1)));
+        assert_eq!("....#\n.....\n.#...\nS....\n", grid.render('.'));
+
+        assert_eq!(Some('#'), grid.remove(&(-2, 3)));
+        assert_eq!(Some(Rectangle::new((0, -1), (1, 0))), grid.bounds());
+        assert_eq!(2, grid.len());
+        assert!(grid.get_adjacents(&(0, 0)).contains(&(-1, 0)));
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+use std::ops::Index;
+use crate::problems::shared::box_3d::Rectangle;
+use crate::problems::shared::grid_2d::Grid2D;
+use crate::problems::shared::grid_2d_direction::Grid2dDirection;
+use crate::problems::shared::grid_point_2d::GridPoint2D;
+
+/// A finite grid repeated forever in every direction, addressed by signed `(row, col)` points.
+/// The base tile sits at `(0, 0)` to `(height - 1, width - 1)`.
+pub struct TiledGrid<TTile> {
+    base: Grid2D<TTile>
+}
+
+impl<TTile> TiledGrid<TTile> {
+    /// Panics if `base` is empty, as there would be nothing to repeat.
+    pub fn new(base: Grid2D<TTile>) -> TiledGrid<TTile> {
+        assert!(base.height() > 0 && base.width() > 0, "A tiled grid needs a non-empty base tile");
+        TiledGrid {
+            base
+        }
+    }
+
+    pub fn base(&self) -> &Grid2D<TTile> {
+        &self.base
+    }
+
+    /// Where `point` falls within the base tile.
+    pub fn wrap(&self, point: (i64, i64)) -> GridPoint2D {
+        GridPoint2D::new(
+            point.0.rem_euclid(self.base.height() as i64) as usize,
+            point.1.rem_euclid(self.base.width() as i64) as usize
+        )
+    }
+
+    /// Which copy of the base tile `point` falls in, counting copies from `(0, 0)` like points.
+    #[allow(dead_code)]
+    pub fn copy_of(&self, point: (i64, i64)) -> (i64, i64) {
+        (point.0.div_euclid(self.base.height() as i64), point.1.div_euclid(self.base.width() as i64))
+    }
+
+    /// The four points next to `point`; they always exist since the tiling never ends.
+    pub fn get_adjacents(&self, point: &(i64, i64)) -> [(i64, i64); 4] {
+        Grid2dDirection::all().map(|direction| direction.step_signed(*point))
+    }
+
+    /// Draws the tiles inside `area` a row at a time.
+    #[allow(dead_code)]
+    pub fn render(&self, area: &Rectangle) -> String where TTile: fmt::Display {
+        let mut rendered = String::new();
+        for row in area.min.0..=area.max.0 {
+            for col in area.min.1..=area.max.1 {
+                rendered.push_str(&self[(row, col)].to_string());
+            }
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+}
+
+impl<TTile> Index<(i64, i64)> for TiledGrid<TTile> {
+    type Output = TTile;
+
+    fn index(&self, point: (i64, i64)) -> &TTile {
+        &self.base[self.wrap(point)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::box_3d::Rectangle;
+    use crate::problems::shared::grid_2d::Grid2D;
+    use crate::problems::shared::grid_point_2d::GridPoint2D;
+    use crate::problems::shared::tiled_grid::TiledGrid;
+
+    #[test]
+    fn wraps_in_every_direction() {
+        let tiled = TiledGrid::new(Grid2D::parse("ab\ncd\nef", Some).unwrap());
+        assert_eq!('a', tiled[(0, 0)]);
+        assert_eq!('f', tiled[(-1, -1)]);
+        assert_eq!('c', tiled[(7, -4)]);
+        assert_eq!(GridPoint2D::new(1, 0), tiled.wrap((7, -4)));
+        assert_eq!((2, -2), tiled.copy_of((7, -4)));
+        assert_eq!((-1, -1), tiled.copy_of((-1, -1)));
+        assert_eq!("fef\nbab\n", tiled.render(&Rectangle::new((-1, -1), (0, 1))));
+    }
+}
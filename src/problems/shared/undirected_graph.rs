@@ -0,0 +1,370 @@
+use std::collections::{BinaryHeap, HashMap};
+use crate::problems::shared::union_find::UnionFind;
+
+/// An undirected graph with weighted edges, stored as an edge list. Nodes are addressed by the
+/// index `add_node` returns. Parallel edges are allowed and behave like a single heavier edge.
+#[derive(Clone, Debug)]
+pub struct UndirectedGraph<N> {
+    nodes: Vec<N>,
+    edges: Vec<(usize, usize, u64)>
+}
+
+/// A split of the nodes into two non-empty parts, and the edges that cross between them.
+#[derive(Clone, Debug)]
+pub struct MinCut {
+    /// Total weight of the edges crossing the cut.
+    pub weight: u64,
+    /// Every edge crossing the cut, as node index pairs.
+    pub cut_edges: Vec<(usize, usize)>,
+    in_first_part: Vec<bool>
+}
+
+impl MinCut {
+    #[allow(dead_code)]
+    pub fn is_in_first_part(&self, node: usize) -> bool {
+        self.in_first_part[node]
+    }
+
+    /// The number of nodes on each side of the cut.
+    pub fn partition_sizes(&self) -> (usize, usize) {
+        let first = self.in_first_part.iter().filter(|in_first| **in_first).count();
+        (first, self.in_first_part.len() - first)
+    }
+}
+
+impl<N> Default for UndirectedGraph<N> {
+    fn default() -> Self {
+        UndirectedGraph::new()
+    }
+}
+
+impl<N> UndirectedGraph<N> {
+    pub fn new() -> UndirectedGraph<N> {
+        UndirectedGraph {
+            nodes: vec![],
+            edges: vec![]
+        }
+    }
+
+    pub fn add_node(&mut self, node: N) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, first: usize, second: usize, weight: u64) {
+        self.edges.push((first, second, weight));
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn node(&self, index: usize) -> &N {
+        &self.nodes[index]
+    }
+
+    #[allow(dead_code)]
+    pub fn edges(&self) -> &[(usize, usize, u64)] {
+        &self.edges
+    }
+
+    /// Groups the nodes into connected components.
+    #[allow(dead_code)]
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut sets = UnionFind::new(self.node_count());
+        for (first, second, _) in &self.edges {
+            sets.union(*first, *second);
+        }
+
+        let mut components: HashMap<usize, Vec<usize>> = HashMap::new();
+        for node in 0..self.node_count() {
+            components.entry(sets.find(node)).or_default().push(node);
+        }
+
+        let mut components: Vec<Vec<usize>> = components.into_values().collect();
+        components.sort();
+        components
+    }
+
+    fn build_cut(&self, in_first_part: Vec<bool>) -> MinCut {
+        let crossing: Vec<&(usize, usize, u64)> = self.edges
+            .iter()
+            .filter(|(first, second, _)| first != second && in_first_part[*first] != in_first_part[*second])
+            .collect();
+
+        MinCut {
+            weight: crossing.iter().map(|(_, _, weight)| weight).sum(),
+            cut_edges: crossing.iter().map(|(first, second, _)| (*first, *second)).collect(),
+            in_first_part
+        }
+    }
+
+    /// Finds a global minimum cut with the Stoer–Wagner algorithm. Deterministic, and runs in
+    /// `O(V * E log E)`. Returns `None` if there are fewer than two nodes.
+    pub fn stoer_wagner(&self) -> Option<MinCut> {
+        // https://en.wikipedia.org/wiki/Stoer%E2%80%93Wagner_algorithm
+        if self.node_count() < 2 {
+            return None;
+        }
+
+        let mut adjacency: Vec<HashMap<usize, u64>> = vec![HashMap::new(); self.node_count()];
+        for (first, second, weight) in &self.edges {
+            if first != second {
+                *adjacency[*first].entry(*second).or_default() += weight;
+                *adjacency[*second].entry(*first).or_default() += weight;
+            }
+        }
+
+        // each remaining vertex stands for every original node merged into it
+        let mut members: Vec<Vec<usize>> = (0..self.node_count()).map(|n| vec![n]).collect();
+        let mut active: Vec<usize> = (0..self.node_count()).collect();
+        let mut best: Option<(u64, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            // minimum cut phase: repeatedly add the vertex most tightly connected to everything added so far
+            let mut added = vec![false; self.node_count()];
+            let mut connection = vec![0u64; self.node_count()];
+            let mut queue: BinaryHeap<(u64, usize)> = BinaryHeap::new();
+            let (mut previous, mut last) = (active[0], active[0]);
+            for _ in 0..active.len() {
+                let next = loop {
+                    match queue.pop() {
+                        Some((weight, vertex)) if !added[vertex] && connection[vertex] == weight => break vertex,
+                        Some(_) => continue, // stale entry
+                        // nothing left is connected to the added vertices; any of them will do
+                        None => break *active.iter().find(|v| !added[**v]).unwrap()
+                    }
+                };
+
+                added[next] = true;
+                previous = last;
+                last = next;
+                for (neighbor, weight) in &adjacency[next] {
+                    if !added[*neighbor] {
+                        connection[*neighbor] += weight;
+                        queue.push((connection[*neighbor], *neighbor));
+                    }
+                }
+            }
+
+            // the cut of the phase separates the last vertex from everything else
+            if best.as_ref().is_none_or(|(weight, _)| connection[last] < *weight) {
+                best = Some((connection[last], members[last].clone()));
+            }
+
+            // merge the last vertex into the one added before it
+            let last_edges = std::mem::take(&mut adjacency[last]);
+            for (neighbor, weight) in last_edges {
+                adjacency[neighbor].remove(&last);
+                if neighbor != previous {
+                    *adjacency[previous].entry(neighbor).or_default() += weight;
+                    *adjacency[neighbor].entry(previous).or_default() += weight;
+                }
+            }
+
+            let last_members = std::mem::take(&mut members[last]);
+            members[previous].extend(last_members);
+            active.retain(|v| *v != last);
+        }
+
+        let (_, side) = best?;
+        let mut in_first_part = vec![false; self.node_count()];
+        for node in side {
+            in_first_part[node] = true;
+        }
+
+        Some(self.build_cut(in_first_part))
+    }
+
+    /// Finds a minimum cut with high probability using the randomized Karger–Stein algorithm, keeping
+    /// the best of `trials` independent runs. Each run finds a minimum cut with probability
+    /// `Ω(1 / log V)`, so a few dozen trials are plenty for graphs of a few thousand nodes.
+    /// The same `seed` always gives the same result. Returns `None` if there are fewer than two nodes.
+    #[allow(dead_code)]
+    pub fn karger_stein(&self, trials: usize, seed: u64) -> Option<MinCut> {
+        // https://en.wikipedia.org/wiki/Karger%27s_algorithm#Karger%E2%80%93Stein_algorithm
+        if self.node_count() < 2 {
+            return None;
+        }
+
+        let mut random = XorShift::new(seed);
+        let edges: Vec<(usize, usize, u64)> = self.edges.iter().filter(|(first, second, _)| first != second).copied().collect();
+        (0..trials.max(1))
+            .map(|_| self.build_cut(recursive_contract(self.node_count(), &edges, &mut random)))
+            .min_by_key(|cut| cut.weight)
+    }
+}
+
+/// Splits `vertex_count` vertices joined by `edges` into two parts, returning which side each vertex is on.
+fn recursive_contract(vertex_count: usize, edges: &[(usize, usize, u64)], random: &mut XorShift) -> Vec<bool> {
+    if vertex_count <= 6 {
+        let (mapping, _) = contract(vertex_count, edges, 2, random);
+        return mapping.iter().map(|v| *v == 0).collect();
+    }
+
+    // contracting to n / √2 vertices keeps a given minimum cut with probability at least 1/2
+    let target = (1.0 + vertex_count as f64 / std::f64::consts::SQRT_2).ceil() as usize;
+    let mut best: Option<(u64, Vec<bool>)> = None;
+    for _ in 0..2 {
+        let (mapping, contracted_edges) = contract(vertex_count, edges, target, random);
+        let contracted_side = recursive_contract(target, &contracted_edges, random);
+        let side: Vec<bool> = mapping.iter().map(|v| contracted_side[*v]).collect();
+        let weight: u64 = edges.iter().filter(|(first, second, _)| side[*first] != side[*second]).map(|(_, _, weight)| weight).sum();
+        if best.as_ref().is_none_or(|(best_weight, _)| weight < *best_weight) {
+            best = Some((weight, side));
+        }
+    }
+
+    best.unwrap().1
+}
+
+/// Randomly contracts edges until `target` vertices remain. Returns the contracted vertex each original
+/// vertex became (numbered `0..target`) and the edges that still join different contracted vertices.
+fn contract(vertex_count: usize, edges: &[(usize, usize, u64)], target: usize, random: &mut XorShift) -> (Vec<usize>, Vec<(usize, usize, u64)>) {
+    // sorting by u^(1/weight) visits the edges in the order weighted random picks would,
+    // so heavier edges are contracted sooner
+    let mut order: Vec<(f64, usize)> = edges
+        .iter()
+        .enumerate()
+        .map(|(index, (_, _, weight))| (random.next_unit().powf(1.0 / (*weight).max(1) as f64), index))
+        .collect();
+    order.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut sets = UnionFind::new(vertex_count);
+    for (_, index) in order {
+        if sets.set_count() <= target {
+            break;
+        }
+        let (first, second, _) = edges[index];
+        sets.union(first, second);
+    }
+
+    // a disconnected graph runs out of edges early; joining whole components never adds to the cut
+    let mut next = 1;
+    while sets.set_count() > target {
+        if sets.union(0, next) {
+            continue;
+        }
+        next += 1;
+    }
+
+    let mut labels: HashMap<usize, usize> = HashMap::new();
+    let mapping: Vec<usize> = (0..vertex_count)
+        .map(|vertex| {
+            let root = sets.find(vertex);
+            let label = labels.len();
+            *labels.entry(root).or_insert(label)
+        })
+        .collect();
+
+    let contracted_edges = edges
+        .iter()
+        .map(|(first, second, weight)| (mapping[*first], mapping[*second], *weight))
+        .filter(|(first, second, _)| first != second)
+        .collect();
+
+    (mapping, contracted_edges)
+}
+
+/// A small xorshift generator, so the randomized algorithms don't need an extra dependency.
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> XorShift {
+        // xorshift never leaves zero, so nudge that seed
+        XorShift(seed.max(1))
+    }
+
+    /// A value in `(0, 1]`.
+    fn next_unit(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        ((self.0 >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::undirected_graph::UndirectedGraph;
+
+    // two 4-cliques joined by a pair of light edges and one heavy edge
+    fn barbell() -> UndirectedGraph<usize> {
+        let mut graph = UndirectedGraph::new();
+        for node in 0..8 {
+            graph.add_node(node);
+        }
+
+        for clique in [0, 4] {
+            for a in clique..clique + 4 {
+                for b in a + 1..clique + 4 {
+                    graph.add_edge(a, b, 3);
+                }
+            }
+        }
+
+        graph.add_edge(0, 4, 1);
+        graph.add_edge(1, 5, 1);
+        graph.add_edge(3, 7, 2);
+        graph
+    }
+
+    #[test]
+    fn stoer_wagner_finds_minimum_cut() {
+        let graph = barbell();
+        let cut = graph.stoer_wagner().unwrap();
+        assert_eq!(4, cut.weight);
+        assert_eq!((4, 4), cut.partition_sizes());
+
+        let mut cut_edges = cut.cut_edges.clone();
+        cut_edges.sort();
+        assert_eq!(vec![(0, 4), (1, 5), (3, 7)], cut_edges);
+        assert_ne!(cut.is_in_first_part(0), cut.is_in_first_part(4));
+    }
+
+    #[test]
+    fn karger_stein_finds_minimum_cut() {
+        let graph = barbell();
+        let cut = graph.karger_stein(20, 7).unwrap();
+        assert_eq!(4, cut.weight);
+        assert_eq!((4, 4), cut.partition_sizes());
+    }
+
+    #[test]
+    fn handles_disconnected_and_tiny_graphs() {
+        let mut graph = UndirectedGraph::new();
+        for node in 0..5 {
+            graph.add_node(node);
+        }
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(3, 4, 1);
+
+        assert_eq!(vec![vec![0, 1, 2], vec![3, 4]], graph.connected_components());
+        assert_eq!(0, graph.stoer_wagner().unwrap().weight);
+        assert_eq!(0, graph.karger_stein(5, 1).unwrap().weight);
+
+        let mut single: UndirectedGraph<usize> = UndirectedGraph::new();
+        single.add_node(0);
+        assert!(single.stoer_wagner().is_none());
+        assert!(single.karger_stein(5, 1).is_none());
+    }
+
+    #[test]
+    fn randomized_and_deterministic_cuts_agree() {
+        // a ring of 40 nodes with chords: every node has degree 4, so the minimum cut is 4
+        let mut graph = UndirectedGraph::new();
+        for node in 0..40 {
+            graph.add_node(node);
+        }
+        for node in 0..40 {
+            graph.add_edge(node, (node + 1) % 40, 1);
+            graph.add_edge(node, (node + 7) % 40, 1);
+        }
+
+        let deterministic = graph.stoer_wagner().unwrap();
+        let randomized = graph.karger_stein(30, 42).unwrap();
+        assert_eq!(deterministic.weight, randomized.weight);
+    }
+}
@@ -0,0 +1,108 @@
+/// Disjoint sets over the elements `0..len`, with path compression and union by rank.
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    set_count: usize
+}
+
+impl UnionFind {
+    /// Starts with every element in a set of its own.
+    pub fn new(len: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            set_count: len
+        }
+    }
+
+    /// The representative of the set containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // point everything on the way straight at the root so later finds are quick
+        let mut current = element;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Merges the sets containing `first` and `second`. Returns `false` if they were already the same set.
+    pub fn union(&mut self, first: usize, second: usize) -> bool {
+        let (mut first, mut second) = (self.find(first), self.find(second));
+        if first == second {
+            return false;
+        }
+
+        // hang the shallower tree under the deeper one
+        if self.ranks[first] < self.ranks[second] {
+            std::mem::swap(&mut first, &mut second);
+        }
+        if self.ranks[first] == self.ranks[second] {
+            self.ranks[first] += 1;
+        }
+
+        self.parents[second] = first;
+        self.sizes[first] += self.sizes[second];
+        self.set_count -= 1;
+        true
+    }
+
+    #[allow(dead_code)]
+    pub fn is_same_set(&mut self, first: usize, second: usize) -> bool {
+        self.find(first) == self.find(second)
+    }
+
+    /// How many elements are in the set containing `element`.
+    #[allow(dead_code)]
+    pub fn set_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::union_find::UnionFind;
+
+    #[test]
+    fn merges_sets() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(6, sets.set_count());
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert_eq!(3, sets.set_count());
+        assert!(sets.is_same_set(0, 3));
+        assert!(!sets.is_same_set(0, 4));
+        assert_eq!(4, sets.set_size(2));
+        assert_eq!(1, sets.set_size(5));
+    }
+
+    #[test]
+    fn handles_long_chains() {
+        let mut sets = UnionFind::new(100_000);
+        for element in 1..100_000 {
+            sets.union(element - 1, element);
+        }
+
+        assert_eq!(1, sets.set_count());
+        assert_eq!(100_000, sets.set_size(0));
+        assert!(sets.is_same_set(0, 99_999));
+    }
+}
@@ -0,0 +1,103 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A point or direction in 3D space. Usually `i64`, but any numeric type works, e.g.
+/// `i128` for cross products of large vectors or `Rational` for exact division.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Vector3D<T = i64> {
+    pub x: T,
+    pub y: T,
+    pub z: T
+}
+
+impl<T> Vector3D<T> {
+    pub fn new(x: T, y: T, z: T) -> Vector3D<T> {
+        Vector3D {
+            x,
+            y,
+            z
+        }
+    }
+
+    /// Converts every component, e.g. `v.map(i128::from)` to widen before multiplying.
+    pub fn map<U, F>(self, f: F) -> Vector3D<U> where F: Fn(T) -> U {
+        Vector3D::new(f(self.x), f(self.y), f(self.z))
+    }
+}
+
+impl<T> Vector3D<T> where T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> {
+    pub fn dot(&self, other: &Vector3D<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &Vector3D<T>) -> Vector3D<T> {
+        Vector3D::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x
+        )
+    }
+}
+
+impl<T> Add for Vector3D<T> where T: Add<Output = T> {
+    type Output = Vector3D<T>;
+
+    fn add(self, other: Vector3D<T>) -> Vector3D<T> {
+        Vector3D::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T> Sub for Vector3D<T> where T: Sub<Output = T> {
+    type Output = Vector3D<T>;
+
+    fn sub(self, other: Vector3D<T>) -> Vector3D<T> {
+        Vector3D::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T> Neg for Vector3D<T> where T: Neg<Output = T> {
+    type Output = Vector3D<T>;
+
+    fn neg(self) -> Vector3D<T> {
+        Vector3D::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// Scales every component.
+impl<T> Mul<T> for Vector3D<T> where T: Copy + Mul<Output = T> {
+    type Output = Vector3D<T>;
+
+    fn mul(self, scale: T) -> Vector3D<T> {
+        Vector3D::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::problems::shared::rational::Rational;
+    use crate::problems::shared::vector_3d::Vector3D;
+
+    #[test]
+    fn does_vector_arithmetic() {
+        let a = Vector3D::new(1, 2, 3);
+        let b = Vector3D::new(4, -5, 6);
+        assert_eq!(Vector3D::new(5, -3, 9), a + b);
+        assert_eq!(Vector3D::new(-3, 7, -3), a - b);
+        assert_eq!(Vector3D::new(-1, -2, -3), -a);
+        assert_eq!(Vector3D::new(2, 4, 6), a * 2);
+        assert_eq!(12, a.dot(&b));
+
+        let normal = a.cross(&b);
+        assert_eq!(Vector3D::new(27, 6, -13), normal);
+        assert_eq!(0, normal.dot(&a));
+        assert_eq!(0, normal.dot(&b));
+    }
+
+    #[test]
+    fn works_with_other_component_types() {
+        let large = Vector3D::new(400_000_000_000_000i64, 1, 0).map(i128::from);
+        assert_eq!(160_000_000_000_000_000_000_000_000_001, large.dot(&large));
+
+        let exact = Vector3D::new(1, 2, 3).map(Rational::from) * Rational::new(1, 2);
+        assert_eq!(Vector3D::new(Rational::new(1, 2), Rational::from(1), Rational::new(3, 2)), exact);
+    }
+}
@@ -0,0 +1,195 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use crate::problems::aocday::{AoCDay, DayOptions};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The answers produced by a single run of a day against one input.
+/// An answer is `None` when that part panicked, which is common while a solution is half written.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>
+}
+
+impl Answers {
+    /// Solves both parts, under `options` if any are given. An options run that fails gives its error as
+    /// both answers.
+    pub fn compute(day: &AoCDay, input: &PathBuf, options: &DayOptions) -> Answers {
+        let Some(with_options) = day.with_options.as_ref().filter(|_| !options.is_empty()) else {
+            return Answers {
+                part1: run_part(&day.part1, input),
+                part2: run_part(&day.part2, input)
+            };
+        };
+
+        match panic::catch_unwind(AssertUnwindSafe(|| (with_options.run)(input, options))) {
+            Ok(Ok(output)) => Answers {
+                part1: Some(output.part1),
+                part2: Some(output.part2)
+            },
+            Ok(Err(error)) => Answers {
+                part1: Some(format!("Error: {}", error)),
+                part2: Some(format!("Error: {}", error))
+            },
+            Err(_) => Answers {
+                part1: None,
+                part2: None
+            }
+        }
+    }
+
+    fn part(&self, part: usize) -> &Option<String> {
+        match part {
+            1 => &self.part1,
+            _ => &self.part2
+        }
+    }
+}
+
+fn run_part(part: &dyn Fn(&PathBuf) -> String, input: &PathBuf) -> Option<String> {
+    panic::catch_unwind(AssertUnwindSafe(|| part(input))).ok()
+}
+
+/// A watched input file along with the last answers it produced.
+struct WatchedInput {
+    label: String,
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    answers: Option<Answers>
+}
+
+impl WatchedInput {
+    fn new(label: &str, path: &Path) -> WatchedInput {
+        WatchedInput {
+            label: label.to_string(),
+            path: path.to_path_buf(),
+            modified: None,
+            answers: None
+        }
+    }
+
+    fn last_modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path).and_then(|m| m.modified()).ok()
+    }
+}
+
+/// Polls the given inputs and re-runs the day, under `options` if any are given, whenever any of them changes,
+/// printing each part's answer next to the answer from the previous run.
+/// Never returns; stop it with Ctrl-C.
+pub fn watch(day: &AoCDay, input: &Path, example: Option<&Path>, options: &DayOptions) {
+    let mut inputs = vec![];
+    if let Some(example) = example {
+        inputs.push(WatchedInput::new("example", example));
+    }
+    inputs.push(WatchedInput::new("input", input));
+
+    println!("Watching {} for day {}", inputs.iter().map(|i| i.path.display().to_string()).collect::<Vec<String>>().join(", "), day.day);
+    loop {
+        let mut changed = false;
+        for watched in inputs.iter_mut() {
+            let modified = watched.last_modified();
+            if modified.is_some() && modified != watched.modified {
+                watched.modified = modified;
+                changed = true;
+            }
+        }
+
+        if changed {
+            let previous: Vec<Option<Answers>> = inputs.iter().map(|i| i.answers.clone()).collect();
+            for watched in inputs.iter_mut() {
+                watched.answers = Some(Answers::compute(day, &watched.path, options));
+            }
+
+            let current: Vec<(&str, &Answers)> = inputs.iter().map(|i| (i.label.as_str(), i.answers.as_ref().unwrap())).collect();
+            println!("--- day {} re-run ---", day.day);
+            print!("{}", format_report(&current, &previous));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Lays out the answers for each input side by side, one row per part.
+/// When `previous` holds answers from an earlier run, changed answers are annotated with their old value.
+pub fn format_report(current: &[(&str, &Answers)], previous: &[Option<Answers>]) -> String {
+    let mut cells: Vec<Vec<String>> = vec![];
+    cells.push(std::iter::once(String::new()).chain(current.iter().map(|(label, _)| label.to_string())).collect());
+    for part in 1..=2 {
+        let mut row = vec![format!("Part {}", part)];
+        for (index, (_, answers)) in current.iter().enumerate() {
+            let answer = answers.part(part);
+            let previous_answer = previous.get(index).and_then(|p| p.as_ref()).map(|p| p.part(part));
+            row.push(format_answer(answer, previous_answer));
+        }
+        cells.push(row);
+    }
+
+    let column_count = cells[0].len();
+    let widths: Vec<usize> = (0..column_count).map(|col| cells.iter().map(|row| row[col].len()).max().unwrap()).collect();
+    let mut report = String::new();
+    for row in cells {
+        let line = row.iter().zip(widths.iter()).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect::<Vec<String>>().join("  ");
+        report.push_str(line.trim_end());
+        report.push('\n');
+    }
+    report
+}
+
+fn format_answer(answer: &Option<String>, previous: Option<&Option<String>>) -> String {
+    let text = answer.clone().unwrap_or_else(|| "<panicked>".to_string());
+    match previous {
+        None => text,
+        Some(previous) if previous == answer => format!("{} (unchanged)", text),
+        Some(previous) => format!("{} (was {})", text, previous.clone().unwrap_or_else(|| "<panicked>".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use crate::problems::aocday::{AoCDay, DayOptions, OptionsOutput, WithOptions};
+    use crate::watch::{Answers, format_report};
+
+    #[test]
+    fn computes_answers_under_options() {
+        let day = AoCDay {
+            day: 1,
+            part1: Box::new(|_| "1".to_string()),
+            part2: Box::new(|_| panic!("part 2 is not written yet")),
+            visualize: None,
+            render: None,
+            with_options: Some(WithOptions {
+                accepts: &["bag"],
+                run: Box::new(|_, options| match options.bag.as_deref() {
+                    Some("none") => Err("no bag".to_string()),
+                    _ => Ok(OptionsOutput { part1: "3".to_string(), part2: "4".to_string(), notes: String::new() })
+                })
+            })
+        };
+        let input = PathBuf::from("input.txt");
+
+        assert_eq!(Answers { part1: Some("1".to_string()), part2: None }, Answers::compute(&day, &input, &DayOptions::default()));
+        let options = DayOptions { bag: Some("1 red".to_string()), ..DayOptions::default() };
+        assert_eq!(Answers { part1: Some("3".to_string()), part2: Some("4".to_string()) }, Answers::compute(&day, &input, &options));
+        let failing = DayOptions { bag: Some("none".to_string()), ..DayOptions::default() };
+        assert_eq!(Some("Error: no bag".to_string()), Answers::compute(&day, &input, &failing).part2);
+    }
+
+    #[test]
+    fn report_shows_changes_against_previous_run() {
+        let example = Answers { part1: Some("142".to_string()), part2: None };
+        let input = Answers { part1: Some("54331".to_string()), part2: Some("54518".to_string()) };
+        let previous = vec![
+            Some(Answers { part1: Some("142".to_string()), part2: None }),
+            Some(Answers { part1: Some("54330".to_string()), part2: Some("54518".to_string()) })
+        ];
+        let report = format_report(&[("example", &example), ("input", &input)], &previous);
+        assert_eq!("        example                 input
+Part 1  142 (unchanged)         54331 (was 54330)
+Part 2  <panicked> (unchanged)  54518 (unchanged)
+", report);
+    }
+}